@@ -0,0 +1,226 @@
+// Adaptor signatures which adapt into Schnorr signatures over Decaf448
+//
+// Decaf448 has no standardised signature scheme, so this defines one:
+//
+//      k = SHAKE256(domain || "Decaf448 challenge" || R || A || M, 114) mod ℓ
+//      [s]B = R + [k]A
+//
+// with 56 byte encodings for R and A, and a 57 byte little-endian s.
+
+use super::{hash_to_scalar, AdaptorError};
+use crate::decaf::{CompressedDecaf, DecafPoint};
+use crate::field::Scalar;
+
+/// The length of an encoded Decaf448 Schnorr signature or pre-signature, in bytes
+pub const SIGNATURE_LENGTH: usize = 56 + 57;
+
+/// Parses `R || s`, where `s` must be reduced modulo ℓ
+fn parse(bytes: &[u8; SIGNATURE_LENGTH]) -> Result<(CompressedDecaf, Scalar), AdaptorError> {
+    let R = CompressedDecaf(std::array::from_fn(|i| bytes[i]));
+    let s = Scalar::from_canonical_bytes(std::array::from_fn(|i| bytes[56 + i]))
+        .ok_or(AdaptorError::InvalidEncoding)?;
+    Ok((R, s))
+}
+
+/// Encodes `R || s`
+fn encode(R: &CompressedDecaf, s: &Scalar) -> [u8; SIGNATURE_LENGTH] {
+    let s = s.to_bytes_rfc_8032();
+    std::array::from_fn(|i| if i < 56 { R.0[i] } else { s[i - 56] })
+}
+
+/// Computes the challenge k
+fn challenge(R: &CompressedDecaf, public_key: &DecafPoint, message: &[u8]) -> Scalar {
+    hash_to_scalar(
+        b"Decaf448 challenge",
+        &[R.as_bytes(), public_key.compress().as_bytes(), message],
+    )
+}
+
+/// A Schnorr signature over Decaf448
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Signature {
+    R: CompressedDecaf,
+    s: Scalar,
+}
+
+impl Signature {
+    /// Encodes the signature as `R || s`
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_LENGTH] {
+        encode(&self.R, &self.s)
+    }
+
+    /// Parses a signature encoded as `R || s`
+    pub fn from_bytes(bytes: &[u8; SIGNATURE_LENGTH]) -> Result<Signature, AdaptorError> {
+        parse(bytes).map(|(R, s)| Signature { R, s })
+    }
+
+    /// Checks the signature over `message` under `public_key`
+    pub fn verify(&self, public_key: &DecafPoint, message: &[u8]) -> Result<(), AdaptorError> {
+        let R = self.R.decompress().ok_or(AdaptorError::InvalidEncoding)?;
+        let k = challenge(&self.R, public_key, message);
+        if DecafPoint::generator() * self.s == R + public_key * &k {
+            Ok(())
+        } else {
+            Err(AdaptorError::InvalidSignature)
+        }
+    }
+}
+
+/// Signs `message` with the secret key `secret_key`, whose public key is `[secret_key]B`
+pub fn sign(secret_key: &Scalar, message: &[u8]) -> Signature {
+    let public_key = &DecafPoint::generator() * secret_key;
+    let r = hash_to_scalar(
+        b"Decaf448 nonce",
+        &[&secret_key.to_bytes_rfc_8032(), message],
+    );
+    let R = (DecafPoint::generator() * r).compress();
+    let k = challenge(&R, &public_key, message);
+    Signature {
+        R,
+        s: r + k * *secret_key,
+    }
+}
+
+/// A Decaf448 Schnorr signature which is encrypted under an adaptor point, encoded as `R || s'`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PreSignature {
+    R: CompressedDecaf,
+    s: Scalar,
+}
+
+impl PreSignature {
+    /// Encodes the pre-signature as `R || s'`
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_LENGTH] {
+        encode(&self.R, &self.s)
+    }
+
+    /// Parses a pre-signature encoded as `R || s'`
+    pub fn from_bytes(bytes: &[u8; SIGNATURE_LENGTH]) -> Result<PreSignature, AdaptorError> {
+        parse(bytes).map(|(R, s)| PreSignature { R, s })
+    }
+}
+
+/// Computes a pre-signature over `message`, which adapts into a signature with the discrete log of `adaptor_point`
+pub fn pre_sign(
+    secret_key: &Scalar,
+    message: &[u8],
+    adaptor_point: &DecafPoint,
+) -> Result<PreSignature, AdaptorError> {
+    if *adaptor_point == DecafPoint::identity() {
+        return Err(AdaptorError::InvalidAdaptorPoint);
+    }
+
+    let public_key = &DecafPoint::generator() * secret_key;
+    let T = adaptor_point.compress();
+    let r = hash_to_scalar(
+        b"Decaf448 adaptor nonce",
+        &[&secret_key.to_bytes_rfc_8032(), T.as_bytes(), message],
+    );
+    let R = (DecafPoint::generator() * r + *adaptor_point).compress();
+
+    let k = challenge(&R, &public_key, message);
+    Ok(PreSignature {
+        R,
+        s: r + k * *secret_key,
+    })
+}
+
+/// Checks that `pre_signature` adapts into a valid signature over `message` with the discrete log of `adaptor_point`
+pub fn pre_verify(
+    public_key: &DecafPoint,
+    message: &[u8],
+    adaptor_point: &DecafPoint,
+    pre_signature: &PreSignature,
+) -> Result<(), AdaptorError> {
+    if *adaptor_point == DecafPoint::identity() {
+        return Err(AdaptorError::InvalidAdaptorPoint);
+    }
+    let R = pre_signature
+        .R
+        .decompress()
+        .ok_or(AdaptorError::InvalidEncoding)?;
+
+    // [s']B + T = R + [k]A
+    let k = challenge(&pre_signature.R, public_key, message);
+    if DecafPoint::generator() * pre_signature.s + *adaptor_point == R + public_key * &k {
+        Ok(())
+    } else {
+        Err(AdaptorError::InvalidPreSignature)
+    }
+}
+
+/// Completes the pre-signature with the adaptor secret `t`
+pub fn adapt(pre_signature: &PreSignature, adaptor_secret: &Scalar) -> Signature {
+    Signature {
+        R: pre_signature.R,
+        s: pre_signature.s + *adaptor_secret,
+    }
+}
+
+/// Recovers the adaptor secret `t` from a signature which was adapted from `pre_signature`
+pub fn extract(
+    signature: &Signature,
+    pre_signature: &PreSignature,
+) -> Result<Scalar, AdaptorError> {
+    if signature.R != pre_signature.R {
+        return Err(AdaptorError::Mismatch);
+    }
+    Ok(signature.s - pre_signature.s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adapt_and_extract() {
+        let secret_key = Scalar::from(0x0bad_cafe);
+        let public_key = DecafPoint::generator() * secret_key;
+        let t = Scalar::from(0xdead_beef) * Scalar::from(0x1234_5678);
+        let T = DecafPoint::generator() * t;
+        let message = b"swap";
+
+        let signature = sign(&secret_key, message);
+        assert!(signature.verify(&public_key, message).is_ok());
+        assert_eq!(
+            Signature::from_bytes(&signature.to_bytes()).unwrap(),
+            signature
+        );
+
+        let pre_signature = pre_sign(&secret_key, message, &T).unwrap();
+        assert!(pre_verify(&public_key, message, &T, &pre_signature).is_ok());
+        assert_eq!(
+            PreSignature::from_bytes(&pre_signature.to_bytes()).unwrap(),
+            pre_signature
+        );
+        assert_eq!(
+            pre_verify(&public_key, b"other", &T, &pre_signature),
+            Err(AdaptorError::InvalidPreSignature)
+        );
+        assert_eq!(
+            pre_verify(&public_key, message, &(T + T), &pre_signature),
+            Err(AdaptorError::InvalidPreSignature)
+        );
+
+        // The adapted signature is an ordinary signature
+        let adapted = adapt(&pre_signature, &t);
+        assert!(adapted.verify(&public_key, message).is_ok());
+        assert_eq!(
+            adapt(&pre_signature, &(t + Scalar::one())).verify(&public_key, message),
+            Err(AdaptorError::InvalidSignature)
+        );
+
+        // Publishing it reveals the discrete log of T
+        let extracted = extract(&adapted, &pre_signature).unwrap();
+        assert_eq!(DecafPoint::generator() * extracted, T);
+        assert_eq!(
+            extract(&signature, &pre_signature),
+            Err(AdaptorError::Mismatch)
+        );
+
+        assert_eq!(
+            pre_sign(&secret_key, message, &DecafPoint::identity()),
+            Err(AdaptorError::InvalidAdaptorPoint)
+        );
+    }
+}
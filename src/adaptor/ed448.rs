@@ -0,0 +1,204 @@
+// Adaptor signatures which adapt into RFC 8032 Ed448 signatures
+
+use super::{hash_to_scalar, AdaptorError};
+use crate::curve::edwards::{CompressedEdwardsY, ExtendedPoint};
+use crate::ed448::{self, Signature, SigningKey, VerifyingKey, SIGNATURE_LENGTH};
+use crate::field::Scalar;
+
+/// An Ed448 signature which is encrypted under an adaptor point, encoded as `R || s'` in 114 bytes
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PreSignature {
+    R: CompressedEdwardsY,
+    s: Scalar,
+}
+
+impl PreSignature {
+    /// Encodes the pre-signature as `R || s'`
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_LENGTH] {
+        let s = self.s.to_bytes_rfc_8032();
+        std::array::from_fn(|i| if i < 57 { self.R.0[i] } else { s[i - 57] })
+    }
+
+    /// Parses a pre-signature encoded as `R || s'`, where `s'` must be reduced modulo ℓ
+    pub fn from_bytes(bytes: &[u8; SIGNATURE_LENGTH]) -> Result<PreSignature, AdaptorError> {
+        let R = CompressedEdwardsY(std::array::from_fn(|i| bytes[i]));
+        let s = Scalar::from_canonical_bytes(std::array::from_fn(|i| bytes[57 + i]))
+            .ok_or(AdaptorError::InvalidEncoding)?;
+        Ok(PreSignature { R, s })
+    }
+}
+
+/// Checks that the adaptor point is a non-identity element of the prime-order subgroup
+fn check_adaptor_point(adaptor_point: &ExtendedPoint) -> Result<(), AdaptorError> {
+    if *adaptor_point == ExtendedPoint::identity() || !adaptor_point.is_torsion_free() {
+        return Err(AdaptorError::InvalidAdaptorPoint);
+    }
+    Ok(())
+}
+
+/// Computes the RFC 8032 challenge k = SHAKE256(dom4(0, "") || R || A || M, 114)
+fn challenge(R: &CompressedEdwardsY, verifying_key: &VerifyingKey, message: &[u8]) -> Scalar {
+    ed448::hash_to_scalar(0, &[], &[R.as_bytes(), verifying_key.as_bytes(), message])
+}
+
+/// Computes a pre-signature over `message`, which adapts into a signature with the discrete log of `adaptor_point`
+///
+/// The nonce is derived from the nonce prefix of the key, the adaptor point and the message,
+/// so it never coincides with the nonce of an ordinary signature over the same message.
+pub fn pre_sign(
+    signing_key: &SigningKey,
+    message: &[u8],
+    adaptor_point: &ExtendedPoint,
+) -> Result<PreSignature, AdaptorError> {
+    check_adaptor_point(adaptor_point)?;
+
+    let T = adaptor_point.compress();
+    let r = hash_to_scalar(
+        b"Ed448 nonce",
+        &[&signing_key.prefix, T.as_bytes(), message],
+    );
+    let R = (ExtendedPoint::generator().scalar_mul(&r) + adaptor_point).compress();
+
+    let k = challenge(&R, &signing_key.verifying_key, message);
+    Ok(PreSignature {
+        R,
+        s: r + k * signing_key.scalar,
+    })
+}
+
+/// Checks that `pre_signature` adapts into a valid signature over `message` with the discrete log of `adaptor_point`
+///
+/// The equation `[s']B + T = R + [k]A` is checked without the cofactor, so the adapted
+/// signature is accepted by every RFC 8032 verifier.
+pub fn pre_verify(
+    verifying_key: &VerifyingKey,
+    message: &[u8],
+    adaptor_point: &ExtendedPoint,
+    pre_signature: &PreSignature,
+) -> Result<(), AdaptorError> {
+    check_adaptor_point(adaptor_point)?;
+    let R = pre_signature
+        .R
+        .decompress_strict()
+        .ok_or(AdaptorError::InvalidEncoding)?;
+
+    let k = challenge(&pre_signature.R, verifying_key, message);
+    let lhs = ExtendedPoint::generator().scalar_mul(&pre_signature.s) + adaptor_point;
+    let rhs = R + verifying_key.to_edwards().scalar_mul(&k);
+    if lhs == rhs {
+        Ok(())
+    } else {
+        Err(AdaptorError::InvalidPreSignature)
+    }
+}
+
+/// Completes the pre-signature with the adaptor secret `t`
+pub fn adapt(pre_signature: &PreSignature, adaptor_secret: &Scalar) -> Signature {
+    let s = pre_signature.s + *adaptor_secret;
+    let mut bytes = [0u8; SIGNATURE_LENGTH];
+    bytes[..57].copy_from_slice(&pre_signature.R.0);
+    bytes[57..].copy_from_slice(&s.to_bytes_rfc_8032());
+    Signature::from_bytes(&bytes)
+}
+
+/// Recovers the adaptor secret `t` from a signature which was adapted from `pre_signature`
+pub fn extract(
+    signature: &Signature,
+    pre_signature: &PreSignature,
+) -> Result<Scalar, AdaptorError> {
+    if signature.r_bytes() != pre_signature.R.as_bytes() {
+        return Err(AdaptorError::Mismatch);
+    }
+    let s =
+        Scalar::from_canonical_bytes(*signature.s_bytes()).ok_or(AdaptorError::InvalidEncoding)?;
+    Ok(s - pre_signature.s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::edwards::affine::AffinePoint;
+    use crate::field::FieldElement;
+
+    fn adaptor() -> (Scalar, ExtendedPoint) {
+        let t = Scalar::from(0xdead_beef) * Scalar::from(0x1234_5678);
+        (t, ExtendedPoint::generator().scalar_mul(&t))
+    }
+
+    #[test]
+    fn test_adapt_and_extract() {
+        let signing_key = SigningKey::from_seed(&[3; 57]);
+        let verifying_key = signing_key.verifying_key();
+        let (t, T) = adaptor();
+        let message = b"swap";
+
+        let pre_signature = pre_sign(&signing_key, message, &T).unwrap();
+        assert!(pre_verify(&verifying_key, message, &T, &pre_signature).is_ok());
+        assert_eq!(
+            PreSignature::from_bytes(&pre_signature.to_bytes()).unwrap(),
+            pre_signature
+        );
+
+        // The pre-signature itself is not a valid signature
+        let bytes = pre_signature.to_bytes();
+        assert!(verifying_key
+            .verify(message, &Signature::from_bytes(&bytes))
+            .is_err());
+
+        // The adapted signature is an ordinary Ed448 signature
+        let signature = adapt(&pre_signature, &t);
+        assert!(verifying_key.verify_strict(message, &signature).is_ok());
+
+        // Publishing it reveals the discrete log of T
+        let extracted = extract(&signature, &pre_signature).unwrap();
+        assert_eq!(extracted, t);
+        assert_eq!(ExtendedPoint::generator().scalar_mul(&extracted), T);
+
+        // A pre-signature is not reused for ordinary signing
+        assert_ne!(signing_key.sign(message).r_bytes(), signature.r_bytes());
+        assert_eq!(
+            extract(&signing_key.sign(message), &pre_signature),
+            Err(AdaptorError::Mismatch)
+        );
+    }
+
+    #[test]
+    fn test_pre_verify_rejects() {
+        let signing_key = SigningKey::from_seed(&[3; 57]);
+        let verifying_key = signing_key.verifying_key();
+        let (_, T) = adaptor();
+        let message = b"swap";
+        let pre_signature = pre_sign(&signing_key, message, &T).unwrap();
+
+        // Wrong message, key or adaptor point
+        assert_eq!(
+            pre_verify(&verifying_key, b"other", &T, &pre_signature),
+            Err(AdaptorError::InvalidPreSignature)
+        );
+        let other_key = SigningKey::from_seed(&[4; 57]).verifying_key();
+        assert_eq!(
+            pre_verify(&other_key, message, &T, &pre_signature),
+            Err(AdaptorError::InvalidPreSignature)
+        );
+        let other_T = T.double();
+        assert_eq!(
+            pre_verify(&verifying_key, message, &other_T, &pre_signature),
+            Err(AdaptorError::InvalidPreSignature)
+        );
+
+        // Adaptor points outside the prime-order subgroup
+        let torsion = AffinePoint {
+            x: FieldElement::one(),
+            y: FieldElement::zero(),
+        }
+        .to_extended();
+        assert_eq!(
+            pre_sign(&signing_key, message, &(T + torsion)),
+            Err(AdaptorError::InvalidAdaptorPoint)
+        );
+        assert_eq!(
+            pre_sign(&signing_key, message, &ExtendedPoint::identity()),
+            Err(AdaptorError::InvalidAdaptorPoint)
+        );
+    }
+}
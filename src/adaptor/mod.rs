@@ -0,0 +1,68 @@
+// This is the module for Schnorr adaptor signatures, over Ed448 and over Decaf448
+//
+// A pre-signature is a signature that is "encrypted" under an adaptor point T = [t]B. It is
+// computed with the nonce point R = R' + T, where R' = [r]B is the nonce point of the signer:
+//
+//      k = H(R || A || M)
+//      s' = r + k * a
+//
+// Anyone can check that [s']B + T = R + [k]A. Whoever knows t can adapt the pre-signature into
+// the signature (R, s' + t), and once that signature is published, anyone who holds the
+// pre-signature learns t = s - s'. This is what makes atomic swaps and payment channels work.
+//
+// On Ed448 the adapted signature is an RFC 8032 signature (with an empty context), on Decaf448
+// it is a Schnorr signature with a SHAKE256 challenge, see `decaf448::Signature`.
+
+pub mod decaf448;
+pub mod ed448;
+
+use crate::field::Scalar;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use std::fmt;
+
+/// Domain separator for the hashes of this module, apart from the RFC 8032 challenge
+const ADAPTOR_DOMAIN: &[u8] = b"Ed448-Goldilocks adaptor signatures";
+
+/// Errors which can occur when creating, checking, adapting or extracting from a pre-signature
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AdaptorError {
+    /// The adaptor point is the identity or not in the prime-order subgroup
+    InvalidAdaptorPoint,
+    /// A point or scalar in the (pre-)signature is not canonically encoded
+    InvalidEncoding,
+    /// The pre-signature does not verify for this key, message and adaptor point
+    InvalidPreSignature,
+    /// The signature does not verify
+    InvalidSignature,
+    /// The signature was not adapted from this pre-signature
+    Mismatch,
+}
+
+impl fmt::Display for AdaptorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdaptorError::InvalidAdaptorPoint => write!(f, "invalid adaptor point"),
+            AdaptorError::InvalidEncoding => write!(f, "malformed signature or pre-signature"),
+            AdaptorError::InvalidPreSignature => write!(f, "pre-signature verification failed"),
+            AdaptorError::InvalidSignature => write!(f, "signature verification failed"),
+            AdaptorError::Mismatch => {
+                write!(f, "signature was not adapted from the pre-signature")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AdaptorError {}
+
+/// Computes SHAKE256(domain || tag || parts[0] || ... || parts[n], 114) as a scalar
+fn hash_to_scalar(tag: &[u8], parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Shake256::default().chain(ADAPTOR_DOMAIN).chain(tag);
+    for part in parts {
+        hasher.update(part);
+    }
+
+    let mut output = [0u8; 114];
+    hasher.finalize_xof().read(&mut output);
+    Scalar::from_bytes_mod_order_wide(&output)
+}
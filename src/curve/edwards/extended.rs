@@ -1,4 +1,5 @@
 use std::borrow::Borrow;
+use std::fmt;
 use std::iter::Sum;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
@@ -35,18 +36,95 @@ impl ConditionallySelectable for ExtendedPoint {
     }
 }
 
+/// How much freedom `CompressedEdwardsY` decoding allows in the encoding of a point
+///
+/// Both policies reject encodings of a `y` for which there is no point on the curve,
+/// they only differ on encodings which are not the one produced by `compress`.
+/// Consensus-critical code should choose one of them explicitly, so that every
+/// node gives the same answer for the same bytes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DecodingPolicy {
+    /// The decoding rules of RFC 8032 Section 5.2.3
+    ///
+    /// * `y` must be fully reduced, i.e. less than p
+    /// * the seven unused bits of the last byte must be zero
+    /// * when x = 0, the sign bit must not be set
+    ///
+    /// Every point has exactly one encoding which is accepted.
+    Strict,
+    /// Accepts every encoding which can be interpreted as a point
+    ///
+    /// * `y` is read as a 448-bit integer and reduced modulo p
+    /// * the seven unused bits of the last byte are ignored
+    /// * when x = 0, the sign bit is ignored
+    ///
+    /// This is the analogue of the ZIP-215 rules for Ed25519.
+    Permissive,
+}
+
+#[derive(Copy, Clone)]
 pub struct CompressedEdwardsY(pub [u8; 57]);
 
+impl fmt::Debug for CompressedEdwardsY {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0[..].fmt(formatter)
+    }
+}
+
+impl ConstantTimeEq for CompressedEdwardsY {
+    fn ct_eq(&self, other: &CompressedEdwardsY) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl PartialEq for CompressedEdwardsY {
+    fn eq(&self, other: &CompressedEdwardsY) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+impl Eq for CompressedEdwardsY {}
+
 impl CompressedEdwardsY {
+    /// View the compressed point as a byte slice
+    pub fn as_bytes(&self) -> &[u8; 57] {
+        &self.0
+    }
+
+    /// Decodes the point, accepting every encoding which names a point on the curve
+    /// See [`DecodingPolicy::Permissive`]
     pub fn decompress(&self) -> Option<ExtendedPoint> {
+        self.decompress_with_policy(DecodingPolicy::Permissive)
+    }
+
+    /// Decodes the point, only accepting the canonical encoding
+    /// See [`DecodingPolicy::Strict`]
+    pub fn decompress_strict(&self) -> Option<ExtendedPoint> {
+        self.decompress_with_policy(DecodingPolicy::Strict)
+    }
+
+    /// Decodes the point according to `policy`
+    ///
+    /// N.B. This does not run in constant time, encodings are assumed to be public.
+    pub fn decompress_with_policy(&self, policy: DecodingPolicy) -> Option<ExtendedPoint> {
+        let strict = policy == DecodingPolicy::Strict;
+
         // Safe to unwrap here as the underlying data structure is a slice
         let (sign, b) = self.0.split_last().unwrap();
 
+        // Only the top bit of the last byte is used
+        if strict && (sign & 0x7f) != 0 {
+            return None;
+        }
+
         let mut y_bytes: [u8; 56] = [0; 56];
         y_bytes.copy_from_slice(&b);
 
         // Recover x using y
         let y = FieldElement::from_bytes(&y_bytes);
+        if strict && y.to_bytes() != y_bytes {
+            // y >= p
+            return None;
+        }
         let yy = y.square();
         let dyy = EDWARDS_D * yy;
         let numerator = FieldElement::one() - yy;
@@ -59,6 +137,10 @@ impl CompressedEdwardsY {
 
         // Compute correct sign of x
         let compressed_sign_bit = Choice::from(sign >> 7);
+        if strict && (x.is_zero() & compressed_sign_bit).unwrap_u8() == 1 {
+            // -0 is not a valid encoding of x
+            return None;
+        }
         let is_negative = x.is_negative();
         x.conditional_negate(compressed_sign_bit ^ is_negative);
 
@@ -299,6 +381,12 @@ impl ExtendedPoint {
     pub fn is_torsion_free(&self) -> bool {
         (self * BASEPOINT_ORDER) == Self::identity()
     }
+
+    /// Determine if this point is of small order, i.e. lies in the
+    /// torsion subgroup of order 4 (this includes the identity)
+    pub fn is_small_order(&self) -> bool {
+        self.double().double() == Self::identity()
+    }
 }
 
 // ------------------------------------------------------------------------
@@ -414,8 +502,8 @@ impl<'a, 'b> Mul<&'b ExtendedPoint> for &'a Scalar {
 
 #[cfg(test)]
 mod tests {
-    use hex_literal::hex;
     use super::*;
+    use hex_literal::hex;
 
     fn hex_to_field(hex: &'static str) -> FieldElement {
         assert_eq!(hex.len(), 56 * 2);
@@ -514,4 +602,121 @@ mod tests {
         let decompressed = compressed.decompress().unwrap();
         assert!(!decompressed.is_torsion_free());
     }
+
+    #[test]
+    fn test_is_small_order() {
+        assert!(ExtendedPoint::identity().is_small_order());
+        assert!(!ExtendedPoint::generator().is_small_order());
+
+        // (1, 0) has order 4
+        let torsion = AffinePoint {
+            x: FieldElement::one(),
+            y: FieldElement::zero(),
+        }
+        .to_extended();
+        assert!(torsion.is_small_order());
+        assert!(!(ExtendedPoint::generator() + torsion).is_small_order());
+    }
+
+    #[test]
+    fn test_decoding_policy_corpus() {
+        use DecodingPolicy::{Permissive, Strict};
+
+        fn encoding(y: [u8; 56], last: u8) -> CompressedEdwardsY {
+            let mut bytes = [0u8; 57];
+            bytes[..56].copy_from_slice(&y);
+            bytes[56] = last;
+            CompressedEdwardsY(bytes)
+        }
+        fn small_y(y: u8) -> [u8; 56] {
+            let mut bytes = [0u8; 56];
+            bytes[0] = y;
+            bytes
+        }
+
+        let generator = ExtendedPoint::generator();
+        let mut generator_y = [0u8; 56];
+        generator_y.copy_from_slice(&generator.compress().0[..56]);
+
+        let minus_one = FieldElement::one().negate();
+        let order_two = AffinePoint {
+            x: FieldElement::zero(),
+            y: minus_one,
+        }
+        .to_extended();
+        let order_four = AffinePoint {
+            x: FieldElement::one(),
+            y: FieldElement::zero(),
+        }
+        .to_extended();
+
+        // p + 1 and p, which are non-canonical encodings of 1 and 0
+        let p_plus_one = hex!("00000000000000000000000000000000000000000000000000000000ffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+        let p = hex!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+
+        // (encoding, expected under Strict, expected under Permissive)
+        let corpus = [
+            // The canonical encoding of the generator
+            (
+                encoding(generator_y, 0x00),
+                Some(generator),
+                Some(generator),
+            ),
+            // The generator, with the unused bits of the last byte set
+            (encoding(generator_y, 0x01), None, Some(generator)),
+            (encoding(generator_y, 0x7f), None, Some(generator)),
+            // The identity
+            (
+                encoding(small_y(1), 0x00),
+                Some(ExtendedPoint::identity()),
+                Some(ExtendedPoint::identity()),
+            ),
+            // The identity, with the sign bit set on x = 0
+            (
+                encoding(small_y(1), 0x80),
+                None,
+                Some(ExtendedPoint::identity()),
+            ),
+            // The identity, with y = p + 1
+            (
+                encoding(p_plus_one, 0x00),
+                None,
+                Some(ExtendedPoint::identity()),
+            ),
+            // The point of order 2, with and without the sign bit
+            (
+                encoding(minus_one.to_bytes(), 0x00),
+                Some(order_two),
+                Some(order_two),
+            ),
+            (encoding(minus_one.to_bytes(), 0x80), None, Some(order_two)),
+            // The points of order 4, with y = 0 and y = p. Since x = 1 is odd, it is "negative"
+            (
+                encoding(small_y(0), 0x00),
+                Some(order_four.negate()),
+                Some(order_four.negate()),
+            ),
+            (
+                encoding(small_y(0), 0x80),
+                Some(order_four),
+                Some(order_four),
+            ),
+            (encoding(p, 0x00), None, Some(order_four.negate())),
+            // y = 2 is not the y-coordinate of a point on the curve
+            (encoding(small_y(2), 0x00), None, None),
+            (encoding(small_y(2), 0x80), None, None),
+        ];
+
+        for (compressed, strict, permissive) in corpus.iter() {
+            assert_eq!(compressed.decompress_with_policy(Strict), *strict);
+            assert_eq!(compressed.decompress_strict(), *strict);
+            assert_eq!(compressed.decompress_with_policy(Permissive), *permissive);
+            assert_eq!(compressed.decompress(), *permissive);
+
+            // Whatever is accepted strictly round-trips
+            if let Some(point) = strict {
+                assert_eq!(point.compress(), *compressed);
+            }
+        }
+    }
 }
@@ -0,0 +1,426 @@
+#![allow(non_snake_case)]
+
+use super::montgomery::{ladder, MontgomeryPoint, ProjectiveMontgomeryPoint};
+use crate::constants::MONTGOMERY_A;
+use crate::curve::edwards::affine::AffinePoint;
+use crate::curve::edwards::extended::ExtendedPoint;
+use crate::field::{FieldElement, Scalar};
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+/// A point (u, v) on Curve448: v^2 = u^3 + Au^2 + u, or the point at infinity
+///
+/// Unlike `MontgomeryPoint`, which only keeps u, this supports the full group law.
+/// Every addition and doubling costs an inversion.
+#[derive(Copy, Clone)]
+pub struct AffineMontgomeryPoint {
+    pub(crate) u: FieldElement,
+    pub(crate) v: FieldElement,
+    // Set for the point at infinity, in which case u and v are zero
+    pub(crate) infinity: Choice,
+}
+
+impl fmt::Debug for AffineMontgomeryPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AffineMontgomeryPoint")
+            .field("u", &self.u.to_bytes())
+            .field("v", &self.v.to_bytes())
+            .field("infinity", &bool::from(self.infinity))
+            .finish()
+    }
+}
+
+impl ConstantTimeEq for AffineMontgomeryPoint {
+    fn ct_eq(&self, other: &AffineMontgomeryPoint) -> Choice {
+        !(self.infinity ^ other.infinity) & self.u.ct_eq(&other.u) & self.v.ct_eq(&other.v)
+    }
+}
+
+impl PartialEq for AffineMontgomeryPoint {
+    fn eq(&self, other: &AffineMontgomeryPoint) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+impl Eq for AffineMontgomeryPoint {}
+
+impl ConditionallySelectable for AffineMontgomeryPoint {
+    fn conditional_select(
+        a: &AffineMontgomeryPoint,
+        b: &AffineMontgomeryPoint,
+        choice: Choice,
+    ) -> AffineMontgomeryPoint {
+        AffineMontgomeryPoint {
+            u: FieldElement::conditional_select(&a.u, &b.u, choice),
+            v: FieldElement::conditional_select(&a.v, &b.v, choice),
+            infinity: (a.infinity & !choice) | (b.infinity & choice),
+        }
+    }
+}
+
+impl AffineMontgomeryPoint {
+    /// The point at infinity
+    pub fn identity() -> AffineMontgomeryPoint {
+        AffineMontgomeryPoint {
+            u: FieldElement::zero(),
+            v: FieldElement::zero(),
+            infinity: Choice::from(1),
+        }
+    }
+
+    /// Returns the generator specified in RFC7748, with the v-coordinate given there
+    pub fn generator() -> AffineMontgomeryPoint {
+        let mut u = [0u8; 56];
+        u[0] = 5;
+        let v = [
+            0x1a, 0x5b, 0x7b, 0x45, 0x3d, 0x22, 0xd7, 0x6f, 0xf7, 0x7a, 0x67, 0x50, 0xb1, 0xc4,
+            0x12, 0x13, 0x21, 0x0d, 0x43, 0x46, 0x23, 0x7e, 0x02, 0xb8, 0xed, 0xf6, 0xf3, 0x8d,
+            0xc2, 0x5d, 0xf7, 0x60, 0xd0, 0x45, 0x55, 0xf5, 0x34, 0x5d, 0xae, 0xcb, 0xce, 0x6f,
+            0x32, 0x58, 0x6e, 0xab, 0x98, 0x6c, 0xf6, 0xb1, 0xf5, 0x95, 0x12, 0x5d, 0x23, 0x7d,
+        ];
+        AffineMontgomeryPoint::from_coordinates(
+            FieldElement::from_bytes(&u),
+            FieldElement::from_bytes(&v),
+        )
+    }
+
+    pub(crate) fn from_coordinates(u: FieldElement, v: FieldElement) -> AffineMontgomeryPoint {
+        AffineMontgomeryPoint {
+            u,
+            v,
+            infinity: Choice::from(0),
+        }
+    }
+
+    /// Returns true if the point is the point at infinity
+    pub fn is_identity(&self) -> bool {
+        self.infinity.into()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_on_curve(&self) -> bool {
+        let rhs = ((self.u + MONTGOMERY_A) * self.u + FieldElement::one()) * self.u;
+        bool::from(self.infinity | self.v.square().ct_eq(&rhs))
+    }
+
+    /// Drops the v-coordinate. The point at infinity becomes u = 0, like in the ladder.
+    pub fn to_montgomery(&self) -> MontgomeryPoint {
+        MontgomeryPoint(self.u.to_bytes())
+    }
+
+    /// Maps an Ed448-Goldilocks point to Curve448 with the 4-isogeny from RFC 7748 section 4.2
+    ///
+    /// (u, v) = (y^2/x^2, (2 - x^2 - y^2)y/x^3)
+    ///
+    /// This is a group homomorphism, whose kernel is the identity and the point (0, -1).
+    /// It is a bijection between the prime-order subgroups, see [`Self::to_edwards`].
+    pub fn from_edwards(point: &ExtendedPoint) -> AffineMontgomeryPoint {
+        let affine = point.to_affine();
+        let xx = affine.x.square();
+        let yy = affine.y.square();
+        let inv_xxx = (xx * affine.x).invert();
+
+        let two = FieldElement::one() + FieldElement::one();
+        AffineMontgomeryPoint {
+            u: yy * affine.x * inv_xxx,
+            v: (two - xx - yy) * affine.y * inv_xxx,
+            infinity: affine.x.is_zero(),
+        }
+    }
+
+    /// Maps a point in the prime-order subgroup back to Ed448-Goldilocks, inverting [`Self::from_edwards`]
+    ///
+    /// The point at infinity maps to the identity. Returns `None` for every other point
+    /// which is not in the image of the prime-order subgroup.
+    pub fn to_edwards(&self) -> Option<ExtendedPoint> {
+        if self.is_identity() {
+            return Some(ExtendedPoint::identity());
+        }
+        let (u, v) = (self.u, self.v);
+
+        // The dual isogeny from RFC 7748 section 4.2 sends (u, v) to [4]P, where P maps to (u, v):
+        // x = 4v(u^2 - 1) / ((u^2 - 1)^2 + 4v^2)
+        // y = -u((u^2 - 1)^2 - 4v^2) / (u(u^2 - 1)^2 - 2v^2(u^2 + 1))
+        let uu = u.square();
+        let uu_minus_one = uu - FieldElement::one();
+        let uu_minus_one_sq = uu_minus_one.square();
+        let vv = v.square();
+        let two_vv = vv + vv;
+        let four_vv = two_vv + two_vv;
+        let two_v = v + v;
+
+        let x_numerator = (two_v + two_v) * uu_minus_one;
+        let x_denominator = uu_minus_one_sq + four_vv;
+        let y_numerator = (u * (uu_minus_one_sq - four_vv)).negate();
+        let y_denominator = u * uu_minus_one_sq - two_vv * (uu + FieldElement::one());
+
+        let four_p = AffinePoint {
+            x: x_numerator * x_denominator.invert(),
+            y: y_numerator * y_denominator.invert(),
+        }
+        .to_extended();
+
+        // [4]P lies in the prime-order subgroup, so multiplying by 1/4 mod ℓ gives P if P is
+        // in the subgroup. Otherwise this lands on a different point.
+        let point = four_p.scalar_mul(&Scalar::from(4).invert());
+        if AffineMontgomeryPoint::from_edwards(&point) != *self {
+            return None;
+        }
+        Some(point)
+    }
+
+    /// Computes 2P
+    pub fn double(&self) -> AffineMontgomeryPoint {
+        // lambda = (3u^2 + 2Au + 1) / 2v
+        let uu = self.u.square();
+        let numerator = uu + uu + uu + (MONTGOMERY_A + MONTGOMERY_A) * self.u + FieldElement::one();
+        let lambda = numerator * (self.v + self.v).invert();
+
+        let u = lambda.square() - MONTGOMERY_A - self.u - self.u;
+        let v = lambda * (self.u - u) - self.v;
+
+        // The points with v = 0 have order 2, or are the point at infinity
+        AffineMontgomeryPoint::conditional_select(
+            &AffineMontgomeryPoint::from_coordinates(u, v),
+            &AffineMontgomeryPoint::identity(),
+            self.v.is_zero(),
+        )
+    }
+
+    /// Computes P + Q in constant time
+    pub fn add(&self, other: &AffineMontgomeryPoint) -> AffineMontgomeryPoint {
+        // lambda = (v2 - v1) / (u2 - u1)
+        let lambda = (other.v - self.v) * (other.u - self.u).invert();
+        let u = lambda.square() - MONTGOMERY_A - self.u - other.u;
+        let v = lambda * (self.u - u) - self.v;
+        let mut result = AffineMontgomeryPoint::from_coordinates(u, v);
+
+        // The chord is vertical if u1 = u2, then P = Q or P = -Q
+        let same_u = self.u.ct_eq(&other.u);
+        result.conditional_assign(&self.double(), same_u & self.v.ct_eq(&other.v));
+        result.conditional_assign(
+            &AffineMontgomeryPoint::identity(),
+            same_u & self.v.ct_eq(&other.v.negate()),
+        );
+
+        result.conditional_assign(other, self.infinity);
+        result.conditional_assign(self, other.infinity);
+        result
+    }
+
+    /// Computes -P
+    pub fn negate(&self) -> AffineMontgomeryPoint {
+        AffineMontgomeryPoint {
+            u: self.u,
+            v: self.v.negate(),
+            infinity: self.infinity,
+        }
+    }
+
+    /// Computes [k]P in constant time with the ladder and y-recovery
+    pub fn scalar_mul(&self, scalar: &Scalar) -> AffineMontgomeryPoint {
+        let (Q, R) = ladder(&self.u, scalar);
+        let mut result = self.recover_v(&Q, &R);
+
+        // The recovery divides by v, [k]P and [k+1]P, so these cases are handled apart:
+        // [k+1]P is the point at infinity if [k]P = -P
+        result.conditional_assign(&self.negate(), R.W.is_zero());
+        // [k]P is the point at infinity
+        result.conditional_assign(&AffineMontgomeryPoint::identity(), Q.W.is_zero());
+        // P has order 2, so [k]P is P for odd k and the point at infinity otherwise
+        let odd = Choice::from((scalar[0] & 1) as u8);
+        let order_two = AffineMontgomeryPoint::conditional_select(
+            &AffineMontgomeryPoint::identity(),
+            self,
+            odd,
+        );
+        result.conditional_assign(&order_two, self.v.is_zero());
+        result
+    }
+
+    /// Recovers [k]P from the outputs Q = [k]P and R = [k+1]P of the ladder
+    ///
+    /// This is the Okeya-Sakurai y-coordinate recovery, Algorithm 5 of Costello-Smith 2017.
+    fn recover_v(
+        &self,
+        Q: &ProjectiveMontgomeryPoint,
+        R: &ProjectiveMontgomeryPoint,
+    ) -> AffineMontgomeryPoint {
+        let two_A = MONTGOMERY_A + MONTGOMERY_A;
+
+        let v1 = self.u * Q.W;
+        let v2 = Q.U + v1;
+        let v3 = (Q.U - v1).square() * R.U;
+        let v1 = two_A * Q.W;
+        let v2 = v2 + v1;
+        let v4 = self.u * Q.U + Q.W;
+        let v2 = (v2 * v4 - v1 * Q.W) * R.W;
+        let Y = v2 - v3;
+        let v1 = (self.v + self.v) * Q.W * R.W;
+        let X = v1 * Q.U;
+        let Z = v1 * Q.W;
+
+        let inv_Z = Z.invert();
+        AffineMontgomeryPoint::from_coordinates(X * inv_Z, Y * inv_Z)
+    }
+}
+
+impl Mul<&Scalar> for &AffineMontgomeryPoint {
+    type Output = AffineMontgomeryPoint;
+    fn mul(self, scalar: &Scalar) -> AffineMontgomeryPoint {
+        self.scalar_mul(scalar)
+    }
+}
+
+impl Mul<&AffineMontgomeryPoint> for &Scalar {
+    type Output = AffineMontgomeryPoint;
+    fn mul(self, point: &AffineMontgomeryPoint) -> AffineMontgomeryPoint {
+        point * self
+    }
+}
+
+impl Add<&AffineMontgomeryPoint> for &AffineMontgomeryPoint {
+    type Output = AffineMontgomeryPoint;
+    fn add(self, other: &AffineMontgomeryPoint) -> AffineMontgomeryPoint {
+        self.add(other)
+    }
+}
+
+impl Sub<&AffineMontgomeryPoint> for &AffineMontgomeryPoint {
+    type Output = AffineMontgomeryPoint;
+    fn sub(self, other: &AffineMontgomeryPoint) -> AffineMontgomeryPoint {
+        self.add(&other.negate())
+    }
+}
+
+impl Neg for &AffineMontgomeryPoint {
+    type Output = AffineMontgomeryPoint;
+    fn neg(self) -> AffineMontgomeryPoint {
+        self.negate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::GOLDILOCKS_BASE_POINT as bp;
+
+    fn scalars() -> Vec<Scalar> {
+        let mut scalar = Scalar::from(0x1234_5678);
+        (0..6)
+            .map(|_| {
+                scalar = scalar * scalar + Scalar::from(0xdead_beef);
+                scalar
+            })
+            .chain([
+                Scalar::zero(),
+                Scalar::one(),
+                Scalar::from(2),
+                Scalar::from(201),
+            ])
+            .collect()
+    }
+
+    #[test]
+    fn test_group_law() {
+        let G = AffineMontgomeryPoint::generator();
+        let O = AffineMontgomeryPoint::identity();
+        assert!(G.is_on_curve());
+        assert_eq!(G.to_montgomery(), MontgomeryPoint::generator());
+        assert_eq!(AffineMontgomeryPoint::from_edwards(&bp), G);
+
+        assert_eq!(&G + &O, G);
+        assert_eq!(&O + &G, G);
+        assert_eq!(&G - &G, O);
+        assert_eq!(&G + &G, G.double());
+        assert_eq!(O.double(), O);
+
+        let P = G.double().double();
+        let Q = &P + &G;
+        assert!(P.is_on_curve() && Q.is_on_curve());
+        assert_eq!(&(&Q - &G) - &P, O);
+        assert_eq!(&(&P + &G) + &Q, &P + &(&G + &Q));
+
+        // The point (0, 0) has order 2
+        let T = MontgomeryPoint([0; 56]).to_affine(0).unwrap();
+        assert!(T.is_on_curve() && !T.is_identity());
+        assert_eq!(T.double(), O);
+        assert_eq!(&T + &T, O);
+        assert_eq!(-&T, T);
+    }
+
+    #[test]
+    fn test_scalar_mul() {
+        let G = AffineMontgomeryPoint::generator();
+        for scalar in scalars() {
+            // The ladder with y-recovery agrees with Edwards scalar multiplication
+            let expected = AffineMontgomeryPoint::from_edwards(&bp.scalar_mul(&scalar));
+            assert_eq!(&G * &scalar, expected);
+            assert!(expected.is_on_curve());
+            assert_eq!(
+                expected.to_montgomery(),
+                &MontgomeryPoint::generator() * &scalar
+            );
+        }
+
+        // [ℓ-1]G = -G, where [k+1]G is the point at infinity
+        let minus_one = Scalar::zero() - Scalar::one();
+        assert_eq!(&G * &minus_one, -&G);
+
+        // Points of order 2 and the point at infinity
+        let T = MontgomeryPoint([0; 56]).to_affine(0).unwrap();
+        assert_eq!(&T * &Scalar::from(3), T);
+        assert_eq!(&T * &Scalar::from(4), AffineMontgomeryPoint::identity());
+        assert_eq!(
+            &AffineMontgomeryPoint::identity() * &Scalar::from(3),
+            AffineMontgomeryPoint::identity()
+        );
+    }
+
+    #[test]
+    fn test_decompress() {
+        for scalar in scalars() {
+            let P = &AffineMontgomeryPoint::generator() * &scalar;
+            if P.is_identity() {
+                continue;
+            }
+            let sign = P.v.is_negative().unwrap_u8();
+            let u = P.to_montgomery();
+            assert_eq!(u.to_affine(sign), Some(P));
+            assert_eq!(u.to_affine(1 - sign), Some(-&P));
+        }
+
+        // u = 6 is on the twist
+        let mut six = [0u8; 56];
+        six[0] = 6;
+        assert!(MontgomeryPoint(six).to_affine(0).is_none());
+    }
+
+    #[test]
+    fn test_edwards_round_trip() {
+        for scalar in scalars() {
+            let point = bp.scalar_mul(&scalar);
+            let P = AffineMontgomeryPoint::from_edwards(&point);
+            assert_eq!(P.to_edwards(), Some(point));
+            assert_eq!(P.negate().to_edwards(), Some(point.negate()));
+        }
+
+        // The kernel of the isogeny maps to the point at infinity
+        let O = AffineMontgomeryPoint::identity();
+        assert_eq!(
+            AffineMontgomeryPoint::from_edwards(&ExtendedPoint::identity()),
+            O
+        );
+        assert_eq!(
+            AffineMontgomeryPoint::from_edwards(&ExtendedPoint::identity().torque()),
+            O
+        );
+        assert_eq!(O.to_edwards(), Some(ExtendedPoint::identity()));
+
+        // Points outside the image of the prime-order subgroup
+        let T = MontgomeryPoint([0; 56]).to_affine(0).unwrap();
+        assert!(T.to_edwards().is_none());
+        let G = AffineMontgomeryPoint::generator();
+        assert!((&G + &T).to_edwards().is_none());
+    }
+}
@@ -0,0 +1,170 @@
+// Elligator 2 for Curve448, see https://elligator.cr.yp.to/elligator-20130828.pdf
+//
+// The map follows RFC 9380 section 6.7.1 with the non-square Z = -1, as p = 3 mod 4:
+// r is sent to u = -A/(1 - r^2) if that is on the curve, with v negative, and to -A - u
+// otherwise, with v non-negative. r and -r map to the same point, so representatives are taken
+// from the canonical half 0 <= r <= (p - 1)/2. That leaves the top bit of the 56 bytes free,
+// which the map ignores and which should be set at random.
+//
+// About half of the points have a representative. As u is hit by both r and 1/r, one in each
+// case, a u-coordinate has two representatives and a point (u, v) has one.
+
+use super::affine::AffineMontgomeryPoint;
+use super::montgomery::MontgomeryPoint;
+use crate::constants::MONTGOMERY_A;
+use crate::field::FieldElement;
+use subtle::{ConditionallyNegatable, ConditionallySelectable};
+
+/// Decodes a representative, ignoring the top bit
+fn decode(representative: &[u8; 56]) -> FieldElement {
+    let mut bytes = *representative;
+    bytes[55] &= 0x7f;
+    FieldElement::from_bytes(&bytes)
+}
+
+/// The Elligator 2 map from a field element to a point on Curve448
+pub(crate) fn map(r: &FieldElement) -> AffineMontgomeryPoint {
+    let one = FieldElement::one();
+    let minus_a = MONTGOMERY_A.negate();
+
+    // u1 = -A/(1 - r^2), which is -A in the exceptional case r = 1 or r = -1
+    let denominator = one - r.square();
+    let mut u1 = minus_a * denominator.invert();
+    u1.conditional_assign(&minus_a, denominator.is_zero());
+
+    let g1 = ((u1 + MONTGOMERY_A) * u1 + one) * u1;
+    let first_case = g1.is_square();
+    let u = FieldElement::conditional_select(&(minus_a - u1), &u1, first_case);
+
+    let g = ((u + MONTGOMERY_A) * u + one) * u;
+    let (inv_v, _) = g.inverse_square_root();
+    let mut v = inv_v * g;
+    v.conditional_negate(v.is_negative() ^ first_case);
+
+    AffineMontgomeryPoint::from_coordinates(u, v)
+}
+
+impl AffineMontgomeryPoint {
+    /// Maps a representative to a point with Elligator 2, ignoring its top bit
+    pub fn from_representative(representative: &[u8; 56]) -> AffineMontgomeryPoint {
+        map(&decode(representative))
+    }
+
+    /// Returns the Elligator 2 representative of the point, which is in the canonical half and has the top bit clear
+    ///
+    /// Returns `None` if the point has no representative, which is the case for about half
+    /// of the points and for the point at infinity.
+    pub fn to_representative(&self) -> Option<[u8; 56]> {
+        if self.is_identity() {
+            return None;
+        }
+
+        // r^2 = (u + A)/u if v is negative, and r^2 = u/(u + A) otherwise
+        let u_plus_a = self.u + MONTGOMERY_A;
+        let first_case = self.v.is_negative();
+        let numerator = FieldElement::conditional_select(&self.u, &u_plus_a, first_case);
+        let denominator = FieldElement::conditional_select(&u_plus_a, &self.u, first_case);
+        let (mut r, is_square) = FieldElement::sqrt_ratio(&numerator, &denominator);
+
+        // r is in the canonical half if 2r < p, in which case 2r is even
+        r.conditional_negate((r + r).is_negative());
+
+        // The exceptional cases do not map back to the point
+        if !bool::from(is_square) || map(&r) != *self {
+            return None;
+        }
+        Some(r.to_bytes())
+    }
+}
+
+impl MontgomeryPoint {
+    /// Maps a representative to a u-coordinate with Elligator 2, ignoring its top bit
+    pub fn from_representative(representative: &[u8; 56]) -> MontgomeryPoint {
+        AffineMontgomeryPoint::from_representative(representative).to_montgomery()
+    }
+
+    /// Returns an Elligator 2 representative of the u-coordinate, which is in the canonical half and has the top bit clear
+    ///
+    /// Of the two representatives of u, this is the one which is mapped in the first case, that is
+    /// the representative of the point with negative v. Returns `None` if u has none, which is the
+    /// case for about half of the points on the curve and for every point on the twist.
+    /// The top bit should be set at random before the representative is sent.
+    pub fn to_representative(&self) -> Option<[u8; 56]> {
+        self.to_affine(1)?.to_representative()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::Scalar;
+
+    /// Representatives in the canonical half
+    fn representatives() -> Vec<[u8; 56]> {
+        (0..32u8)
+            .map(|i| {
+                let bytes: [u8; 56] = std::array::from_fn(|j| {
+                    (j as u8).wrapping_mul(29).wrapping_add(i.wrapping_mul(101))
+                });
+                let mut r = decode(&bytes);
+                r.conditional_negate((r + r).is_negative());
+                r.to_bytes()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut first_case = 0;
+        for r in representatives() {
+            // The map is injective on points
+            let point = AffineMontgomeryPoint::from_representative(&r);
+            assert!(point.is_on_curve());
+            assert_eq!(point.to_representative(), Some(r));
+
+            // The top bit is ignored
+            let mut padded = r;
+            padded[55] |= 0x80;
+            assert_eq!(AffineMontgomeryPoint::from_representative(&padded), point);
+
+            // A u-coordinate has two representatives, the canonical one is mapped in the first case
+            let u = MontgomeryPoint::from_representative(&r);
+            let representative = u.to_representative().unwrap();
+            assert_eq!(MontgomeryPoint::from_representative(&representative), u);
+            if bool::from(point.v.is_negative()) {
+                assert_eq!(representative, r);
+                first_case += 1;
+            } else {
+                assert_ne!(representative, r);
+            }
+        }
+        assert!(first_case > 0 && first_case < 32);
+    }
+
+    #[test]
+    fn test_not_representable() {
+        // About half of the multiples of the generator have a representative
+        let generator = AffineMontgomeryPoint::generator();
+        let representable = (1..=32u32)
+            .filter(|&k| {
+                let point = &generator * &Scalar::from(k);
+                match point.to_representative() {
+                    Some(r) => {
+                        assert_eq!(AffineMontgomeryPoint::from_representative(&r), point);
+                        true
+                    }
+                    None => false,
+                }
+            })
+            .count();
+        assert!(representable > 0 && representable < 32);
+
+        // The point at infinity and the twist
+        assert!(AffineMontgomeryPoint::identity()
+            .to_representative()
+            .is_none());
+        let mut six = [0u8; 56];
+        six[0] = 6;
+        assert!(MontgomeryPoint(six).to_representative().is_none());
+    }
+}
@@ -1,4 +1,8 @@
+// Curve448, both as u-coordinates for the ladder and as full (u, v) points,
+// with the 4-isogenies from and to Ed448-Goldilocks and the Elligator 2 map
+pub(crate) mod affine;
+pub(crate) mod elligator;
 pub(crate) mod montgomery;
 
-pub use montgomery::MontgomeryPoint;
-// This is incomplete. Missing the 4-isogenous maps from Goldilocks and test
+pub use affine::AffineMontgomeryPoint;
+pub use montgomery::{MontgomeryPoint, PointClass};
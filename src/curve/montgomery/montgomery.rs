@@ -11,36 +11,18 @@
 
 #![allow(non_snake_case)]
 
-use crate::constants::A_PLUS_TWO_OVER_FOUR;
+use super::affine::AffineMontgomeryPoint;
+use crate::constants::{A_PLUS_TWO_OVER_FOUR, MONTGOMERY_A};
 use crate::curve::edwards::extended::ExtendedPoint;
 use crate::field::{FieldElement, Scalar};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::Mul;
-use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use subtle::{Choice, ConditionallyNegatable, ConditionallySelectable, ConstantTimeEq};
 #[cfg(feature = "zeroize")]
 use zeroize::Zeroize;
 
-// Low order points on Curve448 and it's twist
-const LOW_A: MontgomeryPoint = MontgomeryPoint([
-    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-]);
-const LOW_B: MontgomeryPoint = MontgomeryPoint([
-    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-]);
-const LOW_C: MontgomeryPoint = MontgomeryPoint([
-    0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe, 0xff, 0xff, 0xff,
-    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-]);
-
-#[derive(Copy, Clone, Hash)]
+#[derive(Copy, Clone)]
 #[cfg_attr(feature = "zeroize", derive(Zeroize))]
 pub struct MontgomeryPoint(pub [u8; 56]);
 
@@ -63,36 +45,88 @@ impl PartialEq for MontgomeryPoint {
 }
 impl Eq for MontgomeryPoint {}
 
+// Equality compares the encodings, so the encoding is also what is hashed
+impl Hash for MontgomeryPoint {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+/// Where a u-coordinate lies, see [`MontgomeryPoint::classify`]
+///
+/// Every u-coordinate is either on Curve448 or on its quadratic twist, both of which have
+/// a cofactor of 4. The points of small order are u = 0 and u = -1 on the curve and u = 1
+/// on the twist; every other point has a large prime-order component.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PointClass {
+    /// A point on Curve448 of order 1, 2 or 4
+    CurveSmallOrder,
+    /// A point on Curve448 whose order is a multiple of ℓ
+    CurveLargeOrder,
+    /// A point on the twist of order 4
+    TwistSmallOrder,
+    /// A point on the twist whose order is a multiple of the large prime factor of the twist order
+    TwistLargeOrder,
+}
+
+impl PointClass {
+    /// Returns true if the u-coordinate is on the quadratic twist rather than on Curve448
+    pub fn is_on_twist(&self) -> bool {
+        matches!(
+            self,
+            PointClass::TwistSmallOrder | PointClass::TwistLargeOrder
+        )
+    }
+
+    /// Returns true if scalar multiplication by a clamped scalar sends the point to u = 0
+    pub fn is_small_order(&self) -> bool {
+        matches!(
+            self,
+            PointClass::CurveSmallOrder | PointClass::TwistSmallOrder
+        )
+    }
+}
+
 #[derive(Copy, Clone)]
 pub struct ProjectiveMontgomeryPoint {
-    U: FieldElement,
-    W: FieldElement,
+    pub(crate) U: FieldElement,
+    pub(crate) W: FieldElement,
+}
+
+/// Runs the Montgomery ladder over all 448 bits of the scalar and returns ([k]P, [k+1]P), where u is the u-coordinate of P
+pub(crate) fn ladder(
+    affine_u: &FieldElement,
+    scalar: &Scalar,
+) -> (ProjectiveMontgomeryPoint, ProjectiveMontgomeryPoint) {
+    // Algorithm 8 of Costello-Smith 2017
+    let mut x0 = ProjectiveMontgomeryPoint::identity();
+    let mut x1 = ProjectiveMontgomeryPoint {
+        U: *affine_u,
+        W: FieldElement::one(),
+    };
+
+    let bits = scalar.bits();
+    let mut swap = 0;
+    for s in (0..448).rev() {
+        let bit = bits[s] as u8;
+        let choice: u8 = (swap ^ bit) as u8;
+
+        ProjectiveMontgomeryPoint::conditional_swap(&mut x0, &mut x1, Choice::from(choice));
+        differential_add_and_double(&mut x0, &mut x1, affine_u);
+
+        swap = bit;
+    }
+    // Undo the swap of the last iteration, which is only needed for odd scalars
+    ProjectiveMontgomeryPoint::conditional_swap(&mut x0, &mut x1, Choice::from(swap));
+
+    (x0, x1)
 }
 
 impl Mul<&Scalar> for &MontgomeryPoint {
     type Output = MontgomeryPoint;
     fn mul(self, scalar: &Scalar) -> MontgomeryPoint {
-        // Algorithm 8 of Costello-Smith 2017
         let affine_u = FieldElement::from_bytes(&self.0);
-        let mut x0 = ProjectiveMontgomeryPoint::identity();
-        let mut x1 = ProjectiveMontgomeryPoint {
-            U: affine_u,
-            W: FieldElement::one(),
-        };
-
-        let bits = scalar.bits();
-        let mut swap = 0;
-        for s in (0..448).rev() {
-            let bit = bits[s] as u8;
-            let choice: u8 = (swap ^ bit) as u8;
-
-            ProjectiveMontgomeryPoint::conditional_swap(&mut x0, &mut x1, Choice::from(choice));
-            differential_add_and_double(&mut x0, &mut x1, &affine_u);
-
-            swap = bit;
-        }
-
-        x0.to_affine()
+        ladder(&affine_u, scalar).0.to_affine()
     }
 }
 
@@ -104,15 +138,73 @@ impl Mul<&MontgomeryPoint> for &Scalar {
 }
 
 impl MontgomeryPoint {
+    /// Maps the u-coordinate to the Ed448-Goldilocks point in the prime-order subgroup which has it as its image
+    ///
+    /// This inverts `ExtendedPoint::to_montgomery`. P and -P have the same u-coordinate, so
+    /// `sign` selects the sign of x, like the top bit of a compressed Edwards point does.
+    /// u = 0 maps to the identity. Returns `None` if u is on the twist or is not the image
+    /// of a point in the prime-order subgroup, which includes the other points of small order.
     pub fn to_edwards(&self, sign: u8) -> Option<ExtendedPoint> {
         // We use the 4-isogeny to map to the Ed448.
         // This is different to Curve25519, where we use a birational map.
-        todo!()
+        if bool::from(FieldElement::from_bytes(&self.0).is_zero()) {
+            return Some(ExtendedPoint::identity());
+        }
+        let point = self.to_affine(0)?.to_edwards()?;
+
+        let mut affine = point.to_affine();
+        let flip = affine.x.is_negative() ^ Choice::from(sign & 1);
+        affine.x.conditional_negate(flip);
+        Some(affine.to_extended())
+    }
+
+    /// Recovers the point (u, v) on Curve448, where `sign` selects the sign of v
+    ///
+    /// Returns `None` if u is on the twist. u = 0 is the point (0, 0) of order 2, not the
+    /// point at infinity. For the points with v = 0 the sign is ignored.
+    pub fn to_affine(&self, sign: u8) -> Option<AffineMontgomeryPoint> {
+        let u = FieldElement::from_bytes(&self.0);
+
+        // v^2 = u^3 + Au^2 + u, which is not a square if u is on the twist
+        let v_squared = ((u + MONTGOMERY_A) * u + FieldElement::one()) * u;
+        let (inv_v, is_square) = v_squared.inverse_square_root();
+        let mut v = inv_v * v_squared;
+        let flip = v.is_negative() ^ Choice::from(sign & 1);
+        v.conditional_negate(flip);
+
+        // inverse_square_root does not flag zero as a square
+        if !bool::from(is_square | v_squared.is_zero()) {
+            return None;
+        }
+        Some(AffineMontgomeryPoint::from_coordinates(u, v))
     }
 
-    /// Returns true if the point is one of the low order points
+    /// Returns true if the point is one of the low order points, including non-canonical encodings
     pub fn is_low_order(&self) -> bool {
-        (*self == LOW_A) || (*self == LOW_B) || (*self == LOW_C)
+        self.classify().is_small_order()
+    }
+
+    /// Reports whether the u-coordinate is on Curve448 or on its twist, and whether it has small order
+    ///
+    /// The u-coordinate is reduced modulo p first, so u >= p is classified like u - p.
+    /// u = 0 lies on both curves and is reported as being on Curve448.
+    /// This runs in constant time until the final conversion into a `PointClass`.
+    pub fn classify(&self) -> PointClass {
+        let u = FieldElement::from_bytes(&self.0);
+
+        // u is on Curve448 if u^3 + Au^2 + u is a square, otherwise on the twist
+        let v_squared = ((u + MONTGOMERY_A) * u + FieldElement::one()) * u;
+        let on_curve = v_squared.is_square();
+
+        let small_order =
+            u.is_zero() | u.ct_eq(&FieldElement::one()) | u.ct_eq(&FieldElement::minus_one());
+
+        match (bool::from(on_curve), bool::from(small_order)) {
+            (true, true) => PointClass::CurveSmallOrder,
+            (true, false) => PointClass::CurveLargeOrder,
+            (false, true) => PointClass::TwistSmallOrder,
+            (false, false) => PointClass::TwistLargeOrder,
+        }
     }
     /// View the point as a byte slice
     pub fn as_bytes(&self) -> &[u8; 56] {
@@ -206,6 +298,116 @@ impl ProjectiveMontgomeryPoint {
 mod tests {
 
     use super::*;
+    use crate::curve::edwards::affine::AffinePoint;
+
+    #[test]
+    fn test_classify() {
+        // p and p + 1, which are non-canonical encodings of u = 0 and u = 1
+        let mut p = [0xff; 56];
+        p[28] = 0xfe;
+        let mut p_plus_one = [0xff; 56];
+        p_plus_one[..28].fill(0);
+        let mut minus_one = [0xff; 56];
+        minus_one[0] = 0xfe;
+        minus_one[28] = 0xfe;
+        let mut one = [0u8; 56];
+        one[0] = 1;
+
+        // The points of small order, also in their non-canonical encodings u + p
+        let cases = [
+            (MontgomeryPoint([0; 56]), PointClass::CurveSmallOrder),
+            (MontgomeryPoint(minus_one), PointClass::CurveSmallOrder),
+            (MontgomeryPoint(one), PointClass::TwistSmallOrder),
+            (MontgomeryPoint(p), PointClass::CurveSmallOrder),
+            (MontgomeryPoint(p_plus_one), PointClass::TwistSmallOrder),
+        ];
+        for (point, class) in cases {
+            assert_eq!(point.classify(), class);
+            assert!(point.is_low_order());
+        }
+
+        // The generator u = 5 and its multiples are on the curve, u = 6 is on the twist
+        let generator = MontgomeryPoint::generator();
+        assert_eq!(generator.classify(), PointClass::CurveLargeOrder);
+        assert_eq!(
+            (&generator * &Scalar::from(1234)).classify(),
+            PointClass::CurveLargeOrder
+        );
+        let mut six = [0u8; 56];
+        six[0] = 6;
+        assert_eq!(MontgomeryPoint(six).classify(), PointClass::TwistLargeOrder);
+        assert!(MontgomeryPoint(six).classify().is_on_twist());
+        assert!(!generator.is_low_order());
+    }
+
+    #[test]
+    fn test_to_edwards() {
+        use crate::constants::GOLDILOCKS_BASE_POINT as bp;
+
+        let sign = |point: &ExtendedPoint| point.compress().0[56] >> 7;
+
+        // The basepoint maps to u = 5 and back
+        assert_eq!(bp.to_montgomery(), MontgomeryPoint::generator());
+        let generator = MontgomeryPoint::generator();
+        assert_eq!(generator.to_edwards(sign(&bp)), Some(bp));
+        assert_eq!(generator.to_edwards(1 - sign(&bp)), Some(bp.negate()));
+
+        // Multiples of the basepoint round trip, the sign picks between P and -P
+        let mut scalar = Scalar::from(0x1234_5678);
+        for _ in 0..8 {
+            scalar = scalar * scalar + Scalar::from(0xdead_beef);
+            let point = bp.scalar_mul(&scalar);
+            let u = point.to_montgomery();
+            assert_eq!(u.to_edwards(sign(&point)), Some(point));
+            assert_eq!(u.to_edwards(1 - sign(&point)), Some(point.negate()));
+            assert_eq!(u.to_edwards(sign(&point)).unwrap().to_montgomery(), u);
+
+            // The torsion component is lost in the isogeny
+            let torqued = point.torque();
+            assert_eq!(torqued.to_montgomery(), u);
+            assert_eq!(u.to_edwards(sign(&point)), Some(point));
+        }
+
+        // The points of small order on Ed448 all map to u = 0, which maps to the identity
+        let small_order = [
+            ExtendedPoint::identity(),
+            ExtendedPoint::identity().torque(),
+            AffinePoint {
+                x: FieldElement::one(),
+                y: FieldElement::zero(),
+            }
+            .to_extended(),
+            AffinePoint {
+                x: FieldElement::minus_one(),
+                y: FieldElement::zero(),
+            }
+            .to_extended(),
+        ];
+        for point in small_order {
+            let u = point.to_montgomery();
+            assert_eq!(u, MontgomeryPoint([0; 56]));
+            assert_eq!(u.to_edwards(0), Some(ExtendedPoint::identity()));
+            assert_eq!(u.to_edwards(1), Some(ExtendedPoint::identity()));
+        }
+
+        // u = -1 has order 4 on the curve and is not the image of the prime-order subgroup,
+        // u = 1 and u = 6 are on the twist
+        let mut minus_one = [0xff; 56];
+        minus_one[0] = 0xfe;
+        minus_one[28] = 0xfe;
+        let mut one = [0u8; 56];
+        one[0] = 1;
+        let mut six = [0u8; 56];
+        six[0] = 6;
+        for bytes in [minus_one, one, six] {
+            assert!(MontgomeryPoint(bytes).to_edwards(0).is_none());
+            assert!(MontgomeryPoint(bytes).to_edwards(1).is_none());
+        }
+
+        // A torsion component of order 4 survives the isogeny, so this u is not in the image either
+        let mixed = (bp + small_order[2]).to_montgomery();
+        assert!(mixed.to_edwards(0).is_none());
+    }
 
     #[test]
     fn test_montgomery_edwards() {
@@ -220,4 +422,17 @@ mod tests {
         let goldilocks_point = bp.scalar_mul(&scalar);
         assert_eq!(goldilocks_point.to_montgomery(), montgomery_res);
     }
+
+    #[test]
+    fn test_odd_scalars() {
+        use crate::constants::GOLDILOCKS_BASE_POINT as bp;
+
+        // The ladder ends on the lowest bit, so odd scalars must undo the last swap
+        let generator = MontgomeryPoint::generator();
+        assert_eq!(&generator * &Scalar::one(), generator);
+        for k in [3u32, 5, 201, 0xdead_beef] {
+            let scalar = Scalar::from(k);
+            assert_eq!(&generator * &scalar, bp.scalar_mul(&scalar).to_montgomery());
+        }
+    }
 }
@@ -1,14 +1,89 @@
 #![allow(non_snake_case)]
 
-use super::double_and_add;
+use super::window::wnaf::NafLookupTable;
 use crate::constants::TWISTED_EDWARDS_BASE_POINT;
-use crate::curve::twedwards::extended::ExtendedPoint;
+use crate::curve::twedwards::{extended::ExtendedPoint, extensible::ExtensiblePoint};
 use crate::field::Scalar;
-/// XXX: Really in-efficient way to do double base scala mul
-/// Replace it with endomorphism from pornin or use naf form
+use std::sync::OnceLock;
+
+/// Window width of the non-adjacent form used for arbitrary points
+pub(crate) const POINT_NAF_WIDTH: usize = 5;
+/// The odd multiples of a point needed for `POINT_NAF_WIDTH`
+pub(crate) type PointNafTable = NafLookupTable<8>;
+
+/// Window width of the non-adjacent form used for the basepoint, its table is computed once
+const BASEPOINT_NAF_WIDTH: usize = 8;
+type BasepointNafTable = NafLookupTable<64>;
+
+/// Returns the table of odd multiples of the TwistedEdwards basepoint
+fn basepoint_naf_table() -> &'static BasepointNafTable {
+    static TABLE: OnceLock<BasepointNafTable> = OnceLock::new();
+    TABLE.get_or_init(|| BasepointNafTable::from(&TWISTED_EDWARDS_BASE_POINT))
+}
+
 /// Computes aA + bB where B is the TwistedEdwards basepoint
+///
+/// N.B. This does not run in constant time, only use it with public scalars and points.
 pub(crate) fn double_base_scalar_mul(a: &Scalar, A: &ExtendedPoint, b: &Scalar) -> ExtendedPoint {
-    let part_a = double_and_add(A, a);
-    let part_b = double_and_add(&TWISTED_EDWARDS_BASE_POINT, b);
-    part_a.add(&part_b)
+    double_base_scalar_mul_with_table(a, &PointNafTable::from(A), b)
+}
+
+/// Computes aA + bB where B is the TwistedEdwards basepoint, given the precomputed
+/// odd multiples of A. Both scalars are recoded in non-adjacent form and share the doublings.
+///
+/// N.B. This does not run in constant time, only use it with public scalars and points.
+pub(crate) fn double_base_scalar_mul_with_table(
+    a: &Scalar,
+    A_table: &PointNafTable,
+    b: &Scalar,
+) -> ExtendedPoint {
+    let a_naf = a.non_adjacent_form(POINT_NAF_WIDTH);
+    let b_naf = b.non_adjacent_form(BASEPOINT_NAF_WIDTH);
+    let B_table = basepoint_naf_table();
+
+    // Skip the leading zeroes
+    let top = (0..448).rev().find(|&i| a_naf[i] != 0 || b_naf[i] != 0);
+
+    let mut result = ExtensiblePoint::identity();
+    for i in (0..=top.unwrap_or(0)).rev() {
+        result = result.double();
+
+        if a_naf[i] != 0 {
+            result = result.add_projective_niels(&A_table.select(a_naf[i]));
+        }
+        if b_naf[i] != 0 {
+            result = result.add_projective_niels(&B_table.select(b_naf[i]));
+        }
+    }
+
+    result.to_extended()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::curve::scalar_mul::variable_base;
+
+    #[test]
+    fn test_double_base_scalar_mul() {
+        let B = TWISTED_EDWARDS_BASE_POINT;
+        let A = variable_base(&B, &Scalar::from(31337));
+
+        let a = Scalar([
+            0x6ee372b7, 0xe128ae78, 0x1533427c, 0xad0b7015, 0x307f665e, 0xde8026c1, 0xb64629d1,
+            0xab454c66, 0x3fe5bf1a, 0x083f8304, 0x3c003777, 0xdef437f6, 0xee2e1b73, 0x05ca185a,
+        ]);
+        let b = Scalar::zero() - Scalar::from(12345);
+
+        for (a, b) in [
+            (a, b),
+            (b, a),
+            (Scalar::zero(), b),
+            (a, Scalar::zero()),
+            (Scalar::zero(), Scalar::zero()),
+        ] {
+            let expected = variable_base(&A, &a).add(&variable_base(&B, &b));
+            assert_eq!(double_base_scalar_mul(&a, &A, &b), expected);
+        }
+    }
 }
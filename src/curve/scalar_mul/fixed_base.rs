@@ -0,0 +1,79 @@
+#![allow(non_snake_case)]
+
+use super::window::wnaf::LookupTable;
+use crate::curve::edwards::ExtendedPoint as EdwardsExtendedPoint;
+use crate::curve::twedwards::{extended::ExtendedPoint, extensible::ExtensiblePoint};
+use crate::field::Scalar;
+use std::sync::OnceLock;
+use subtle::{Choice, ConditionallyNegatable};
+
+/// Number of signed radix-16 digits of a scalar
+const DIGITS: usize = 113;
+
+/// Returns the tables of the multiples [1..8][16^i]B for every digit position i,
+/// where B is the TwistedEdwards image of the generator. They are computed once.
+fn basepoint_table() -> &'static [LookupTable] {
+    static TABLE: OnceLock<Vec<LookupTable>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut point = EdwardsExtendedPoint::generator().to_twisted();
+        (0..DIGITS)
+            .map(|_| {
+                let table = LookupTable::from(&point);
+                point = point.double().double().double().double();
+                table
+            })
+            .collect()
+    })
+}
+
+/// Computes sB in constant time, where B is the TwistedEdwards image of the generator
+///
+/// Every radix-16 digit of the scalar selects a multiple from its own table, so there
+/// are no doublings, only one addition per digit. The scalar does not need to be reduced.
+pub(crate) fn fixed_base(s: &Scalar) -> ExtendedPoint {
+    let table = basepoint_table();
+    let scalar = s.to_radix_16();
+
+    let mut result = ExtensiblePoint::identity();
+    for (digit, lookup) in scalar.iter().zip(table) {
+        // The mask is the top bit, will be 1 for negative numbers, 0 for positive numbers
+        let mask = digit >> 7;
+        let sign = mask & 0x1;
+        // Use the mask to get the absolute value of the digit
+        let abs_value = ((digit + mask) ^ mask) as u32;
+
+        let mut P = lookup.select(abs_value);
+        P.conditional_negate(Choice::from(sign as u8));
+
+        result = result.add_projective_niels(&P);
+    }
+
+    result.to_extended()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::curve::scalar_mul::variable_base;
+
+    #[test]
+    fn test_fixed_base() {
+        let B = EdwardsExtendedPoint::generator().to_twisted();
+
+        let scalar = Scalar([
+            0x6ee372b7, 0xe128ae78, 0x1533427c, 0xad0b7015, 0x307f665e, 0xde8026c1, 0xb64629d1,
+            0xab454c66, 0x3fe5bf1a, 0x083f8304, 0x3c003777, 0xdef437f6, 0xee2e1b73, 0x05ca185a,
+        ]);
+        // Unreduced scalars up to 2^448 are handled as well
+        let large = Scalar([0xffff_ffff; 14]);
+        for s in [
+            Scalar::zero(),
+            Scalar::one(),
+            Scalar::from(16),
+            scalar,
+            large,
+        ] {
+            assert_eq!(fixed_base(&s), variable_base(&B, &s));
+        }
+    }
+}
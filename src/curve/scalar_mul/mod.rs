@@ -1,8 +1,12 @@
 pub(crate) mod double_and_add;
 pub(crate) mod double_base;
+pub(crate) mod fixed_base;
+pub(crate) mod multiscalar;
 pub(crate) mod variable_base;
 pub(crate) mod window;
 
 pub(crate) use double_and_add::double_and_add;
 pub(crate) use double_base::double_base_scalar_mul;
+pub(crate) use fixed_base::fixed_base;
+pub(crate) use multiscalar::multiscalar_mul;
 pub(crate) use variable_base::variable_base;
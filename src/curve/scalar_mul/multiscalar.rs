@@ -0,0 +1,71 @@
+#![allow(non_snake_case)]
+
+use super::window::wnaf::LookupTable;
+use crate::curve::twedwards::{extended::ExtendedPoint, extensible::ExtensiblePoint};
+use crate::field::Scalar;
+use subtle::{Choice, ConditionallyNegatable};
+
+/// Computes s_1 * P_1 + ... + s_n * P_n using Straus' method,
+/// so that the doublings are shared between all of the points.
+///
+/// The scalars are expected to be reduced modulo the group order.
+/// N.B. The digits are selected in constant time, but the number of additions leaks `n`.
+pub(crate) fn multiscalar_mul(scalars: &[Scalar], points: &[ExtendedPoint]) -> ExtendedPoint {
+    assert_eq!(scalars.len(), points.len());
+
+    let lookups: Vec<LookupTable> = points.iter().map(LookupTable::from).collect();
+    let digits: Vec<[i8; 113]> = scalars.iter().map(|s| s.to_radix_16()).collect();
+
+    let mut result = ExtensiblePoint::identity();
+
+    for i in (0..113).rev() {
+        result = result.double();
+        result = result.double();
+        result = result.double();
+        result = result.double();
+
+        for (scalar, lookup) in digits.iter().zip(lookups.iter()) {
+            // See variable_base for the explanation of the sign and absolute value
+            let mask = scalar[i] >> 7;
+            let sign = mask & 0x1;
+            let abs_value = ((scalar[i] + mask) ^ mask) as u32;
+
+            let mut P = lookup.select(abs_value);
+            P.conditional_negate(Choice::from(sign as u8));
+
+            result = result.add_projective_niels(&P);
+        }
+    }
+
+    result.to_extended()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::constants::TWISTED_EDWARDS_BASE_POINT;
+    use crate::curve::scalar_mul::variable_base;
+
+    #[test]
+    fn test_multiscalar_mul() {
+        let B = TWISTED_EDWARDS_BASE_POINT;
+        let P = variable_base(&B, &Scalar::from(1234));
+        let Q = variable_base(&B, &(Scalar::zero() - Scalar::from(99)));
+
+        let a = Scalar([
+            0x6ee372b7, 0xe128ae78, 0x1533427c, 0xad0b7015, 0x307f665e, 0xde8026c1, 0xb64629d1,
+            0xab454c66, 0x3fe5bf1a, 0x083f8304, 0x3c003777, 0xdef437f6, 0xee2e1b73, 0x05ca185a,
+        ]);
+        let b = Scalar::zero() - Scalar::from(7);
+        let c = Scalar::from(42);
+
+        let expected = variable_base(&B, &a)
+            .add(&variable_base(&P, &b))
+            .add(&variable_base(&Q, &c));
+        let got = multiscalar_mul(&[a, b, c], &[B, P, Q]);
+        assert_eq!(got, expected);
+
+        // The empty sum is the identity
+        assert_eq!(multiscalar_mul(&[], &[]), ExtendedPoint::identity());
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::curve::twedwards::extended::ExtendedPoint;
 use crate::curve::twedwards::projective::ProjectiveNielsPoint;
-use subtle::{ConditionallySelectable, ConstantTimeEq};
+use subtle::{Choice, ConditionallyNegatable, ConditionallySelectable, ConstantTimeEq};
 
 pub struct LookupTable([ProjectiveNielsPoint; 8]);
 
@@ -34,6 +34,41 @@ impl LookupTable {
     }
 }
 
+/// Holds the odd multiples P, 3P, ..., (2N - 1)P of a point, for use with the non-adjacent form
+///
+/// N.B. Selection is done in variable time, so this must only be used with public scalars.
+#[derive(Clone)]
+pub struct NafLookupTable<const N: usize>([ProjectiveNielsPoint; N]);
+
+impl<const N: usize> From<&ExtendedPoint> for NafLookupTable<N> {
+    fn from(point: &ExtendedPoint) -> NafLookupTable<N> {
+        let P = point.to_extensible();
+        let P2 = P.double().to_extended();
+
+        let mut table = [P.to_projective_niels(); N];
+        let mut multiple = P;
+        for entry in table.iter_mut().skip(1) {
+            multiple = multiple.add_extended(&P2);
+            *entry = multiple.to_projective_niels();
+        }
+
+        NafLookupTable(table)
+    }
+}
+
+impl<const N: usize> NafLookupTable<N> {
+    /// Returns `digit * P`, where `digit` is an odd, non-zero digit of the non-adjacent form
+    pub fn select(&self, digit: i8) -> ProjectiveNielsPoint {
+        debug_assert!(digit & 1 == 1);
+
+        let mut point = self.0[(digit.unsigned_abs() / 2) as usize];
+        if digit < 0 {
+            point.conditional_negate(Choice::from(1));
+        }
+        point
+    }
+}
+
 // XXX: Add back tests to ensure that select works correctly
 
 #[test]
@@ -52,3 +87,19 @@ fn test_lookup() {
             .to_extended();
     }
 }
+
+#[test]
+fn test_naf_lookup() {
+    let p = ExtendedPoint::generator();
+    let table = NafLookupTable::<8>::from(&p);
+
+    let mut multiple = p.to_extensible();
+    for digit in (1..16).step_by(2) {
+        assert_eq!(table.select(digit).to_extended(), multiple.to_extended());
+        assert_eq!(
+            table.select(-digit).to_extended(),
+            multiple.to_extended().negate()
+        );
+        multiple = multiple.add_extended(&p).add_extended(&p);
+    }
+}
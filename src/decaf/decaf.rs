@@ -6,7 +6,7 @@ use crate::field::FieldElement;
 use std::fmt;
 use subtle::{Choice, ConditionallyNegatable, ConstantTimeEq};
 
-#[derive(Debug)]
+#[derive(Copy, Clone, Debug)]
 pub struct DecafPoint(pub(crate) ExtendedPoint);
 
 #[derive(Copy, Clone)]
@@ -31,6 +31,13 @@ impl PartialEq for CompressedDecaf {
 }
 impl Eq for CompressedDecaf {}
 
+impl PartialEq for DecafPoint {
+    fn eq(&self, other: &DecafPoint) -> bool {
+        self.equals(other)
+    }
+}
+impl Eq for DecafPoint {}
+
 impl CompressedDecaf {
     pub fn as_bytes(&self) -> &[u8] {
         &self.0
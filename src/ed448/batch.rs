@@ -0,0 +1,354 @@
+// Batch verification of Ed448 signatures
+//
+// Instead of checking [4][S_i]B = [4]R_i + [4][k_i]A_i for every signature, we check a random
+// linear combination of the equations with a single multiscalar multiplication:
+//
+//      [4] ( [Σ z_i S_i]B - Σ [z_i]R_i - Σ [z_i k_i]A_i ) = 0
+//
+// The multiscalar multiplication is done on the Twisted-Goldilocks curve. Mapping the points across
+// with the 2-isogeny and mapping the result back with its dual multiplies the result by 4,
+// which is exactly the cofactor we need to clear, so no extra doublings are needed.
+//
+// `verify_batch` derives the coefficients from a hash of the whole batch, while
+// `verify_batch_with_rng` draws them from a random number generator.
+
+use super::{hash_to_scalar, Signature, SignatureError, VerifyingKey};
+use crate::constants::TWISTED_EDWARDS_BASE_POINT;
+use crate::curve::edwards::ExtendedPoint;
+use crate::curve::scalar_mul::multiscalar_mul;
+use crate::field::Scalar;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+
+/// Domain separator for deriving the coefficients of the linear combination
+const BATCH_DOMAIN: &[u8] = b"Ed448-Goldilocks batch verification";
+
+/// The length of the coefficients of the linear combination, in bytes
+const COEFFICIENT_LENGTH: usize = 16;
+
+/// Verifies a batch of Ed448 signatures (with empty contexts) at once
+///
+/// Returns `Ok(())` only if every signature is valid for its message and key.
+/// The verification equation is cofactored, so the answer is the same as calling
+/// `VerifyingKey::verify` on each signature individually (see `VerificationPolicy::Permissive`),
+/// but if the batch is rejected it does not say which signature was invalid.
+///
+/// The coefficients z_i are 128-bit values squeezed from SHAKE256 over the whole batch, so
+/// the result does not depend on a random number generator. The coefficients are not secret:
+/// anyone can predict them from the batch. Since they depend on every signature, key and
+/// message, changing any signature to cancel out another one also changes all coefficients.
+/// Use [`verify_batch_with_rng`] to pick the coefficients at random instead.
+pub fn verify_batch(
+    messages: &[&[u8]],
+    signatures: &[Signature],
+    verifying_keys: &[VerifyingKey],
+) -> Result<(), SignatureError> {
+    verify_batch_with_coefficients(messages, signatures, verifying_keys, |k_scalars| {
+        let mut transcript = Shake256::default().chain(BATCH_DOMAIN);
+        // k already binds the message, R and A, so it is enough to absorb it together with S
+        for (k, signature) in k_scalars.iter().zip(signatures) {
+            transcript.update(&k.to_bytes());
+            transcript.update(&signature.s);
+        }
+
+        let mut reader = transcript.finalize_xof();
+        k_scalars
+            .iter()
+            .map(|_| {
+                let mut z = [0u8; COEFFICIENT_LENGTH];
+                reader.read(&mut z);
+                coefficient(&z)
+            })
+            .collect()
+    })
+}
+
+/// Verifies a batch of Ed448 signatures (with empty contexts) at once, with random coefficients
+///
+/// Same as [`verify_batch`], but the coefficients z_i are 128-bit values drawn from `rng`.
+#[cfg(feature = "rand")]
+pub fn verify_batch_with_rng<R: RngCore + CryptoRng>(
+    messages: &[&[u8]],
+    signatures: &[Signature],
+    verifying_keys: &[VerifyingKey],
+    rng: &mut R,
+) -> Result<(), SignatureError> {
+    verify_batch_with_coefficients(messages, signatures, verifying_keys, |k_scalars| {
+        k_scalars
+            .iter()
+            .map(|_| {
+                let mut z = [0u8; COEFFICIENT_LENGTH];
+                rng.fill_bytes(&mut z);
+                coefficient(&z)
+            })
+            .collect()
+    })
+}
+
+/// Checks the linear combination of the verification equations, where `coefficients` returns
+/// one coefficient for each of the challenges k_i
+fn verify_batch_with_coefficients(
+    messages: &[&[u8]],
+    signatures: &[Signature],
+    verifying_keys: &[VerifyingKey],
+    coefficients: impl FnOnce(&[Scalar]) -> Vec<Scalar>,
+) -> Result<(), SignatureError> {
+    if messages.len() != signatures.len() || signatures.len() != verifying_keys.len() {
+        return Err(SignatureError::LengthMismatch);
+    }
+
+    let n = signatures.len();
+    let mut R_points = Vec::with_capacity(n);
+    let mut s_scalars = Vec::with_capacity(n);
+    let mut k_scalars = Vec::with_capacity(n);
+
+    for ((message, signature), verifying_key) in messages.iter().zip(signatures).zip(verifying_keys)
+    {
+        let R = signature
+            .R
+            .decompress()
+            .ok_or(SignatureError::InvalidSignature)?;
+        let s =
+            Scalar::from_canonical_bytes(signature.s).ok_or(SignatureError::InvalidSignature)?;
+
+        let k = hash_to_scalar(
+            0,
+            &[],
+            &[
+                signature.R.as_bytes(),
+                verifying_key.compressed.as_bytes(),
+                message,
+            ],
+        );
+
+        R_points.push(R);
+        s_scalars.push(s);
+        k_scalars.push(k);
+    }
+
+    let z_scalars = coefficients(&k_scalars);
+
+    let mut scalars = Vec::with_capacity(2 * n + 1);
+    let mut points = Vec::with_capacity(2 * n + 1);
+
+    let mut B_coefficient = Scalar::zero();
+    for i in 0..n {
+        let z = z_scalars[i];
+
+        B_coefficient = B_coefficient + z * s_scalars[i];
+
+        scalars.push(Scalar::zero() - z);
+        points.push(R_points[i].to_twisted());
+
+        scalars.push(Scalar::zero() - z * k_scalars[i]);
+        points.push(verifying_keys[i].point.to_twisted());
+    }
+    scalars.push(B_coefficient);
+    points.push(TWISTED_EDWARDS_BASE_POINT);
+
+    // The dual isogeny multiplies the result by four
+    let result = multiscalar_mul(&scalars, &points).to_untwisted();
+
+    if result == ExtendedPoint::identity() {
+        Ok(())
+    } else {
+        Err(SignatureError::VerificationFailed)
+    }
+}
+
+/// Loads 128 bits as a coefficient of the linear combination
+fn coefficient(bytes: &[u8; COEFFICIENT_LENGTH]) -> Scalar {
+    let mut scalar_bytes = [0u8; 56];
+    scalar_bytes[..COEFFICIENT_LENGTH].copy_from_slice(bytes);
+    Scalar::from_bytes(scalar_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::edwards::affine::AffinePoint;
+    use crate::ed448::SigningKey;
+    use crate::field::FieldElement;
+
+    fn signing_keys(n: u8) -> Vec<SigningKey> {
+        (0..n).map(|i| SigningKey::from_seed(&[i; 57])).collect()
+    }
+
+    #[test]
+    fn test_valid_batch() {
+        let keys = signing_keys(5);
+        let messages: Vec<Vec<u8>> = (0..5).map(|i| vec![i; i as usize * 10]).collect();
+        let messages: Vec<&[u8]> = messages.iter().map(|m| &m[..]).collect();
+        let signatures: Vec<Signature> = keys
+            .iter()
+            .zip(&messages)
+            .map(|(key, message)| key.sign(message))
+            .collect();
+        let verifying_keys: Vec<VerifyingKey> = keys.iter().map(|k| k.verifying_key()).collect();
+
+        assert!(verify_batch(&messages, &signatures, &verifying_keys).is_ok());
+        assert!(verify_batch(&[], &[], &[]).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_batch() {
+        let keys = signing_keys(4);
+        let messages: [&[u8]; 4] = [b"a", b"b", b"c", b"d"];
+        let mut signatures: Vec<Signature> = keys
+            .iter()
+            .zip(messages.iter())
+            .map(|(key, message)| key.sign(message))
+            .collect();
+        let verifying_keys: Vec<VerifyingKey> = keys.iter().map(|k| k.verifying_key()).collect();
+
+        // Wrong message
+        let bad_messages: [&[u8]; 4] = [b"a", b"b", b"x", b"d"];
+        assert_eq!(
+            verify_batch(&bad_messages, &signatures, &verifying_keys),
+            Err(SignatureError::VerificationFailed)
+        );
+
+        // Keys swapped
+        let mut swapped_keys = verifying_keys.clone();
+        swapped_keys.swap(0, 1);
+        assert_eq!(
+            verify_batch(&messages, &signatures, &swapped_keys),
+            Err(SignatureError::VerificationFailed)
+        );
+
+        // Mismatched lengths
+        assert_eq!(
+            verify_batch(&messages[..3], &signatures, &verifying_keys),
+            Err(SignatureError::LengthMismatch)
+        );
+
+        // S is not reduced
+        signatures[3].s[55] = 0xff;
+        assert_eq!(
+            verify_batch(&messages, &signatures, &verifying_keys),
+            Err(SignatureError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_batch_agrees_with_single_verification_on_torsion() {
+        // Build a signature whose R has a component of order 4.
+        // The cofactored equation accepts it, in both single and batch verification.
+        let key = SigningKey::from_seed(&[7; 57]);
+        let message = b"torsion";
+
+        let torsion = AffinePoint {
+            x: FieldElement::one(),
+            y: FieldElement::zero(),
+        }
+        .to_extended();
+        let r = Scalar::from(123456789);
+        let R = (ExtendedPoint::generator().scalar_mul(&r) + torsion).compress();
+        let k = hash_to_scalar(
+            0,
+            &[],
+            &[R.as_bytes(), key.verifying_key().as_bytes(), message],
+        );
+        let s = r + k * key.scalar;
+        let signature = Signature {
+            R,
+            s: s.to_bytes_rfc_8032(),
+        };
+
+        let verifying_key = key.verifying_key();
+        assert!(verifying_key.verify(message, &signature).is_ok());
+
+        let other = SigningKey::from_seed(&[8; 57]);
+        let other_signature = other.sign(b"other");
+        assert!(verify_batch(
+            &[message, b"other"],
+            &[signature, other_signature],
+            &[verifying_key, other.verifying_key()]
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_cancelling_signatures() {
+        let keys = signing_keys(2);
+        let messages: [&[u8]; 2] = [b"first", b"second"];
+        let verifying_keys: Vec<VerifyingKey> = keys.iter().map(|k| k.verifying_key()).collect();
+
+        // Shift S_0 up and S_1 down by the same amount, so that both signatures are invalid
+        // but the sum of the two verification equations still holds
+        let delta = Scalar::from(0xdead_beef);
+        let mut signatures: Vec<Signature> = keys
+            .iter()
+            .zip(messages.iter())
+            .map(|(key, message)| key.sign(message))
+            .collect();
+        for (signature, shift) in signatures.iter_mut().zip([delta, Scalar::zero() - delta]) {
+            let s = Scalar::from_canonical_bytes(signature.s).unwrap() + shift;
+            signature.s = s.to_bytes_rfc_8032();
+        }
+
+        let mut naive_sum = ExtendedPoint::identity();
+        for ((message, signature), verifying_key) in
+            messages.iter().zip(&signatures).zip(&verifying_keys)
+        {
+            assert!(verifying_key.verify(message, signature).is_err());
+
+            let k = hash_to_scalar(
+                0,
+                &[],
+                &[signature.R.as_bytes(), verifying_key.as_bytes(), message],
+            );
+            let s = Scalar::from_canonical_bytes(signature.s).unwrap();
+            naive_sum = naive_sum + ExtendedPoint::generator().scalar_mul(&s)
+                - signature.R.decompress().unwrap()
+                - verifying_key.point.scalar_mul(&k);
+        }
+        assert_eq!(naive_sum, ExtendedPoint::identity());
+
+        assert_eq!(
+            verify_batch(&messages, &signatures, &verifying_keys),
+            Err(SignatureError::VerificationFailed)
+        );
+        #[cfg(feature = "rand")]
+        {
+            use rand_chacha::rand_core::SeedableRng;
+            let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+            assert_eq!(
+                verify_batch_with_rng(&messages, &signatures, &verifying_keys, &mut rng),
+                Err(SignatureError::VerificationFailed)
+            );
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_batch_with_rng() {
+        use rand_chacha::rand_core::SeedableRng;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+
+        let keys = signing_keys(3);
+        let messages: [&[u8]; 3] = [b"a", b"b", b"c"];
+        let signatures: Vec<Signature> = keys
+            .iter()
+            .zip(messages.iter())
+            .map(|(key, message)| key.sign(message))
+            .collect();
+        let verifying_keys: Vec<VerifyingKey> = keys.iter().map(|k| k.verifying_key()).collect();
+
+        assert!(verify_batch_with_rng(&messages, &signatures, &verifying_keys, &mut rng).is_ok());
+        assert!(verify_batch_with_rng(&[], &[], &[], &mut rng).is_ok());
+
+        let bad_messages: [&[u8]; 3] = [b"a", b"x", b"c"];
+        assert_eq!(
+            verify_batch_with_rng(&bad_messages, &signatures, &verifying_keys, &mut rng),
+            Err(SignatureError::VerificationFailed)
+        );
+        assert_eq!(
+            verify_batch_with_rng(&messages[..2], &signatures, &verifying_keys, &mut rng),
+            Err(SignatureError::LengthMismatch)
+        );
+    }
+}
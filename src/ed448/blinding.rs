@@ -0,0 +1,291 @@
+// Key blinding for Ed448
+//
+// This is a scheme specific to this crate, in the spirit of draft-irtf-cfrg-signature-key-blinding
+// but not an implementation of its Ed448 instantiation: the derivation and its labels differ,
+// so blinded keys do not match the ones of the draft.
+//
+// A long-term key pair (s, A = [s]B) and a secret blinding key skB are combined into a
+// blinded key pair (s * r, [r]A), where the blind r is derived from skB and a context string:
+//
+//      h = SHAKE256("Ed448-Goldilocks key blinding" || skB || ctx, 114)
+//      r = h[0..57] mod ℓ
+//
+// The blinded signing key uses the nonce prefix SHAKE256(h[57..114] || prefix, 57), so its
+// nonces are unrelated to the ones of the long-term key. The blinded key pair is an ordinary
+// Ed448 key pair, so blinded signatures verify with any RFC 8032 verifier, while the blinded
+// public keys for different contexts cannot be linked to each other without skB.
+
+use super::signing::sign_with_secret;
+use super::{check_context, hash_to_scalar, Signature, SignatureError, SigningKey, VerifyingKey};
+use crate::curve::edwards::ExtendedPoint;
+use crate::field::Scalar;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use std::fmt;
+
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The length of a blinding key, in bytes
+pub const BLINDING_KEY_LENGTH: usize = 57;
+
+/// Domain separator for deriving the blind, which names this scheme
+const BLINDING_DOMAIN: &[u8] = b"Ed448-Goldilocks key blinding";
+
+/// The secret which blinds a key pair
+///
+/// The blinding scheme is specific to this crate, it is not compatible with
+/// draft-irtf-cfrg-signature-key-blinding.
+///
+/// Anyone who holds the blinding key and a blinded public key can recover the long-term public key.
+#[derive(Clone)]
+pub struct BlindingKey([u8; BLINDING_KEY_LENGTH]);
+
+impl fmt::Debug for BlindingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlindingKey").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for BlindingKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl BlindingKey {
+    /// Creates a blinding key from 57 uniformly random bytes
+    pub fn from_bytes(bytes: &[u8; BLINDING_KEY_LENGTH]) -> BlindingKey {
+        BlindingKey(*bytes)
+    }
+
+    /// Generates a new blinding key
+    #[cfg(feature = "rand")]
+    pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> BlindingKey {
+        let mut bytes = [0u8; BLINDING_KEY_LENGTH];
+        rng.fill_bytes(&mut bytes);
+        BlindingKey(bytes)
+    }
+
+    /// Returns the bytes of this blinding key
+    pub fn as_bytes(&self) -> &[u8; BLINDING_KEY_LENGTH] {
+        &self.0
+    }
+
+    /// Derives the blind r and the nonce prefix of the blinding key for `context`
+    fn expand(&self, context: &[u8]) -> Result<(Scalar, [u8; 57]), SignatureError> {
+        let mut h = [0u8; 114];
+        Shake256::default()
+            .chain(BLINDING_DOMAIN)
+            .chain(self.0)
+            .chain(context)
+            .finalize_xof()
+            .read(&mut h);
+
+        let mut wide = [0u8; 114];
+        wide[..57].copy_from_slice(&h[..57]);
+        let blind = Scalar::from_bytes_mod_order_wide(&wide);
+        let prefix: [u8; 57] = std::array::from_fn(|i| h[57 + i]);
+
+        #[cfg(feature = "zeroize")]
+        {
+            h.zeroize();
+            wide.zeroize();
+        }
+
+        // A zero blind would map every key to the identity
+        if blind == Scalar::zero() {
+            return Err(SignatureError::InvalidBlindingKey);
+        }
+        Ok((blind, prefix))
+    }
+}
+
+impl VerifyingKey {
+    /// Blinds this public key with `blinding_key` under `context`
+    ///
+    /// The result is the public key of `SigningKey::blind` with the same arguments.
+    pub fn blind(
+        &self,
+        blinding_key: &BlindingKey,
+        context: &[u8],
+    ) -> Result<VerifyingKey, SignatureError> {
+        let (blind, _) = blinding_key.expand(context)?;
+        Ok(VerifyingKey::from_point(self.point.scalar_mul(&blind)))
+    }
+
+    /// Recovers the long-term public key from a public key blinded with `blinding_key` under `context`
+    pub fn unblind(
+        &self,
+        blinding_key: &BlindingKey,
+        context: &[u8],
+    ) -> Result<VerifyingKey, SignatureError> {
+        let (blind, _) = blinding_key.expand(context)?;
+        Ok(VerifyingKey::from_point(
+            self.point.scalar_mul(&blind.invert()),
+        ))
+    }
+}
+
+/// An Ed448 signing key which has been blinded, see `SigningKey::blind`
+#[derive(Clone)]
+pub struct BlindedSigningKey {
+    scalar: Scalar,
+    prefix: [u8; 57],
+    verifying_key: VerifyingKey,
+}
+
+impl fmt::Debug for BlindedSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlindedSigningKey")
+            .field("verifying_key", &self.verifying_key)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for BlindedSigningKey {
+    fn drop(&mut self) {
+        self.scalar.zeroize();
+        self.prefix.zeroize();
+    }
+}
+
+impl SigningKey {
+    /// Blinds this key pair with `blinding_key` under `context`
+    pub fn blind(
+        &self,
+        blinding_key: &BlindingKey,
+        context: &[u8],
+    ) -> Result<BlindedSigningKey, SignatureError> {
+        let (blind, blind_prefix) = blinding_key.expand(context)?;
+
+        let scalar = self.scalar * blind;
+
+        let mut prefix = [0u8; 57];
+        Shake256::default()
+            .chain(blind_prefix)
+            .chain(self.prefix)
+            .finalize_xof()
+            .read(&mut prefix);
+
+        Ok(BlindedSigningKey {
+            scalar,
+            prefix,
+            verifying_key: VerifyingKey::from_point(ExtendedPoint::generator().scalar_mul(&scalar)),
+        })
+    }
+}
+
+impl BlindedSigningKey {
+    /// Returns the blinded public key
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.verifying_key
+    }
+
+    /// Signs a message with an empty context string
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        self.sign_inner(&[], message)
+    }
+
+    /// Signs a message under a context string of at most 255 bytes
+    pub fn sign_with_context(
+        &self,
+        context: &[u8],
+        message: &[u8],
+    ) -> Result<Signature, SignatureError> {
+        check_context(context)?;
+        Ok(self.sign_inner(context, message))
+    }
+
+    fn sign_inner(&self, context: &[u8], message: &[u8]) -> Signature {
+        // r = SHAKE256(dom4(0, C) || prefix || M, 114)
+        let r = hash_to_scalar(0, context, &[&self.prefix, message]);
+        sign_with_secret(
+            &self.scalar,
+            &self.verifying_key.compressed,
+            &r,
+            0,
+            context,
+            message,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_blinded_signatures_verify() {
+        let signing_key = SigningKey::from_seed(&[1; 57]);
+        let verifying_key = signing_key.verifying_key();
+        let blinding_key = BlindingKey::from_bytes(&[2; 57]);
+
+        let blinded = signing_key.blind(&blinding_key, b"period 1").unwrap();
+        let blinded_public = verifying_key.blind(&blinding_key, b"period 1").unwrap();
+        assert_eq!(blinded.verifying_key(), blinded_public);
+        assert_ne!(blinded_public, verifying_key);
+
+        // A blinded signature is a plain RFC 8032 signature under the blinded key
+        let message = b"onion descriptor";
+        let signature = blinded.sign(message);
+        assert!(blinded_public.verify(message, &signature).is_ok());
+        assert!(blinded_public.verify_strict(message, &signature).is_ok());
+        let signature = blinded.sign_with_context(b"ctx", message).unwrap();
+        assert!(blinded_public
+            .verify_with_context(b"ctx", message, &signature)
+            .is_ok());
+
+        // but not under the long-term key
+        assert_eq!(
+            verifying_key.verify_with_context(b"ctx", message, &signature),
+            Err(SignatureError::VerificationFailed)
+        );
+
+        // The nonces are not shared with the long-term key
+        assert_ne!(blinded.sign(message).R, signing_key.sign(message).R);
+    }
+
+    #[test]
+    fn test_unblind() {
+        let verifying_key = SigningKey::from_seed(&[3; 57]).verifying_key();
+        let blinding_key = BlindingKey::from_bytes(&[4; 57]);
+
+        let blinded = verifying_key.blind(&blinding_key, b"period 7").unwrap();
+        assert_eq!(
+            blinded.unblind(&blinding_key, b"period 7").unwrap(),
+            verifying_key
+        );
+
+        // Unblinding with the wrong context or blinding key does not recover the key
+        assert_ne!(
+            blinded.unblind(&blinding_key, b"period 8").unwrap(),
+            verifying_key
+        );
+        let other = BlindingKey::from_bytes(&[5; 57]);
+        assert_ne!(blinded.unblind(&other, b"period 7").unwrap(), verifying_key);
+
+        // Different contexts give different keys
+        assert_ne!(
+            blinded,
+            verifying_key.blind(&blinding_key, b"period 8").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_blinded_key_is_pinned() {
+        // The derivation is specific to this crate, so pin it against accidental changes
+        let verifying_key = SigningKey::from_seed(&[6; 57]).verifying_key();
+        let blinding_key = BlindingKey::from_bytes(&[7; 57]);
+        let blinded = verifying_key.blind(&blinding_key, b"period 1").unwrap();
+        assert_eq!(
+            blinded.as_bytes(),
+            &hex!("0e284d3c568fc6082589fc7cd1e0fbfd378cb1cbeef61d76ad24e4893b13d92a0887dc3f3be04bdbadd6f3d7e326c4450ae25d61718a788780")
+        );
+    }
+}
@@ -0,0 +1,77 @@
+// Conversion of Ed448 keys to X448 keys
+//
+// RFC 7748 defines a 4-isogeny between Curve448 and Ed448-Goldilocks, which sends the Ed448
+// base point to the X448 base point u = 5. `ExtendedPoint::to_montgomery` is that isogeny,
+// so if A = [s]B is an Ed448 public key, its image is the X448 public key [s]5.
+//
+// On the secret side, X448 uses the same clamping as Ed448, so the clamped half of
+// SHAKE256(seed) is used directly as the X448 secret. This lets one identity key per device
+// be used for both signatures and key agreement.
+
+use super::signing::hash_seed;
+use super::{SigningKey, VerifyingKey};
+use crate::curve::montgomery::MontgomeryPoint;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+impl VerifyingKey {
+    /// Converts this key into the corresponding X448 public key
+    pub fn to_montgomery(&self) -> MontgomeryPoint {
+        self.point.to_montgomery()
+    }
+}
+
+impl SigningKey {
+    /// Converts this key into the corresponding X448 secret
+    ///
+    /// The seed is hashed and clamped exactly like Ed448 does, which is also the clamping of
+    /// RFC 7748, so the bytes can be used with any X448 implementation. Their public key is
+    /// `self.verifying_key().to_montgomery()`.
+    ///
+    /// N.B. The caller is responsible for zeroizing the returned secret.
+    pub fn to_x448_secret(&self) -> [u8; 56] {
+        #[cfg_attr(not(feature = "zeroize"), allow(unused_mut, unused_variables))]
+        let (mut secret, mut prefix) = hash_seed(self.as_bytes());
+        let x448_secret = std::array::from_fn(|i| secret[i]);
+
+        #[cfg(feature = "zeroize")]
+        {
+            secret.zeroize();
+            prefix.zeroize();
+        }
+
+        x448_secret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::Scalar;
+
+    #[test]
+    fn test_x448_conversion() {
+        let alice = SigningKey::from_seed(&[1; 57]);
+        let bob = SigningKey::from_seed(&[2; 57]);
+        let x448_scalar = |key: &SigningKey| Scalar::from_bytes(key.to_x448_secret());
+
+        // The converted secret and public key are a key pair
+        for key in [&alice, &bob] {
+            assert_eq!(
+                &MontgomeryPoint::generator() * &x448_scalar(key),
+                key.verifying_key().to_montgomery()
+            );
+        }
+
+        // DH(x448(a), X(B)) == DH(x448(b), X(A))
+        let alice_shared = &bob.verifying_key().to_montgomery() * &x448_scalar(&alice);
+        let bob_shared = &alice.verifying_key().to_montgomery() * &x448_scalar(&bob);
+        assert_eq!(alice_shared, bob_shared);
+
+        // The secret is already clamped
+        let secret = alice.to_x448_secret();
+        assert_eq!(secret[0] & 3, 0);
+        assert_eq!(secret[55] >> 7, 1);
+    }
+}
@@ -0,0 +1,110 @@
+// Ed448 signing keys which are given as an expanded secret (s, prefix) rather than as a seed
+//
+// RFC 8032 expands a seed into the secret scalar s and the nonce prefix. Keys which are derived
+// from other keys, such as the children of hierarchical deterministic derivation, only exist in
+// expanded form: there is no seed that hashes to them. Signing only needs s and the prefix, so
+// these keys sign exactly like a `SigningKey` and their signatures verify with any RFC 8032
+// verifier. The prefix must be secret and unique to the key, or nonces could repeat across keys.
+
+use super::signing::sign_with_secret;
+use super::{check_context, hash_to_scalar, Signature, SignatureError, VerifyingKey};
+use crate::curve::edwards::ExtendedPoint;
+use crate::field::Scalar;
+use std::fmt;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// An Ed448 signing key made of a secret scalar and a nonce prefix
+#[derive(Clone)]
+pub struct ExpandedSigningKey {
+    scalar: Scalar,
+    prefix: [u8; 57],
+    verifying_key: VerifyingKey,
+}
+
+impl fmt::Debug for ExpandedSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExpandedSigningKey")
+            .field("verifying_key", &self.verifying_key)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for ExpandedSigningKey {
+    fn drop(&mut self) {
+        self.scalar.zeroize();
+        self.prefix.zeroize();
+    }
+}
+
+impl ExpandedSigningKey {
+    /// Creates a signing key from the secret scalar s and the nonce prefix
+    ///
+    /// The public key is [s]B. The prefix must be secret, and must not be shared with another key.
+    pub fn from_scalar_and_prefix(scalar: &Scalar, prefix: &[u8; 57]) -> ExpandedSigningKey {
+        ExpandedSigningKey {
+            scalar: *scalar,
+            prefix: *prefix,
+            verifying_key: VerifyingKey::from_point(ExtendedPoint::generator().scalar_mul(scalar)),
+        }
+    }
+
+    /// Returns the public half of this key
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.verifying_key
+    }
+
+    /// Signs a message with an empty context string
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        self.sign_inner(&[], message)
+    }
+
+    /// Signs a message under a context string of at most 255 bytes
+    pub fn sign_with_context(
+        &self,
+        context: &[u8],
+        message: &[u8],
+    ) -> Result<Signature, SignatureError> {
+        check_context(context)?;
+        Ok(self.sign_inner(context, message))
+    }
+
+    fn sign_inner(&self, context: &[u8], message: &[u8]) -> Signature {
+        // r = SHAKE256(dom4(0, C) || prefix || M, 114)
+        let r = hash_to_scalar(0, context, &[&self.prefix, message]);
+        sign_with_secret(
+            &self.scalar,
+            &self.verifying_key.compressed,
+            &r,
+            0,
+            context,
+            message,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ed448::SigningKey;
+
+    #[test]
+    fn test_expanded_signing_key() {
+        // The expanded form of a seed signs like the seed itself
+        let signing_key = SigningKey::from_seed(&[9; 57]);
+        let expanded =
+            ExpandedSigningKey::from_scalar_and_prefix(&signing_key.scalar, &signing_key.prefix);
+        assert_eq!(expanded.verifying_key(), signing_key.verifying_key());
+        assert_eq!(expanded.sign(b"message"), signing_key.sign(b"message"));
+        assert_eq!(
+            expanded.sign_with_context(b"ctx", b"message"),
+            signing_key.sign_with_context(b"ctx", b"message")
+        );
+        assert_eq!(
+            expanded.sign_with_context(&[0; 256], b"message"),
+            Err(SignatureError::ContextTooLong)
+        );
+    }
+}
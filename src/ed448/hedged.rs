@@ -0,0 +1,163 @@
+// Hedged (randomized) Ed448 signing
+//
+// Deterministic nonces are a liability when an attacker can inject faults: signing the same
+// message twice and faulting one of the runs reveals the secret scalar. Hedged signing mixes
+// fresh randomness into the nonce derivation:
+//
+//      r = SHAKE256(dom4(F, C) || prefix || Z || M, 114)
+//
+// where Z is 64 bytes from the caller's RNG. If the RNG is broken, the nonce is still as
+// good as the deterministic one, and if the RNG is good, two runs never share a nonce.
+// The signature itself is an ordinary Ed448 signature, so any RFC 8032 verifier accepts it.
+
+use super::{check_context, shake256_with_dom4, Signature, SignatureError, SigningKey};
+use crate::constants::TWISTED_EDWARDS_BASE_POINT;
+use crate::curve::scalar_mul::variable_base;
+use crate::field::Scalar;
+use rand_core::{CryptoRng, RngCore};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The number of random bytes which are mixed into the nonce
+const NOISE_LENGTH: usize = 64;
+
+impl SigningKey {
+    /// Signs a message under a context string, mixing randomness from `rng` into the nonce
+    pub fn sign_hedged<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+        context: &[u8],
+        message: &[u8],
+    ) -> Result<Signature, SignatureError> {
+        check_context(context)?;
+        let r = self.hedged_nonce(rng, context, message);
+        Ok(self.sign_with_nonce(&r, 0, context, message))
+    }
+
+    /// Like `sign_hedged`, but checks the signature before releasing it
+    ///
+    /// The commitment R is recomputed with a separate scalar multiplication on the
+    /// twisted curve, and compared against the R in the signature. A fault during
+    /// either computation is reported as `SignatureError::FaultDetected`.
+    pub fn sign_hedged_checked<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+        context: &[u8],
+        message: &[u8],
+    ) -> Result<Signature, SignatureError> {
+        check_context(context)?;
+        let r = self.hedged_nonce(rng, context, message);
+        let signature = self.sign_with_nonce(&r, 0, context, message);
+        check_commitment(&signature, &r)?;
+        Ok(signature)
+    }
+
+    /// r = SHAKE256(dom4(0, context) || prefix || Z || message, 114)
+    fn hedged_nonce<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+        context: &[u8],
+        message: &[u8],
+    ) -> Scalar {
+        let mut noise = [0u8; NOISE_LENGTH];
+        rng.fill_bytes(&mut noise);
+
+        let mut hasher = shake256_with_dom4(0, context);
+        hasher.update(&self.prefix);
+        hasher.update(&noise);
+        hasher.update(message);
+
+        let mut output = [0u8; 114];
+        hasher.finalize_xof().read(&mut output);
+        let r = Scalar::from_bytes_mod_order_wide(&output);
+
+        #[cfg(feature = "zeroize")]
+        {
+            noise.zeroize();
+            output.zeroize();
+        }
+
+        r
+    }
+}
+
+/// Checks that the commitment in `signature` is [r]B, using `variable_base` directly
+fn check_commitment(signature: &Signature, r: &Scalar) -> Result<(), SignatureError> {
+    // The twisted basepoint is the image of the basepoint under the isogeny
+    let expected = variable_base(&TWISTED_EDWARDS_BASE_POINT, r);
+    match signature.R.decompress_strict() {
+        Some(R) if R.to_twisted() == expected => Ok(()),
+        _ => Err(SignatureError::FaultDetected),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::rand_core::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn test_hedged_signatures_verify() {
+        let mut rng = ChaCha20Rng::from_seed([5; 32]);
+        let signing_key = SigningKey::from_seed(&[9; 57]);
+        let verifying_key = signing_key.verifying_key();
+        let message = b"hedged";
+
+        let first = signing_key.sign_hedged(&mut rng, b"", message).unwrap();
+        let second = signing_key.sign_hedged(&mut rng, b"", message).unwrap();
+        let checked = signing_key
+            .sign_hedged_checked(&mut rng, b"ctx", message)
+            .unwrap();
+
+        // Fresh randomness gives fresh nonces, unlike deterministic signing
+        assert_ne!(first, second);
+        assert_ne!(first, signing_key.sign(message));
+
+        assert!(verifying_key.verify(message, &first).is_ok());
+        assert!(verifying_key.verify_strict(message, &second).is_ok());
+        assert!(verifying_key
+            .verify_with_context(b"ctx", message, &checked)
+            .is_ok());
+
+        assert_eq!(
+            signing_key.sign_hedged(&mut rng, &[0; 256], message),
+            Err(SignatureError::ContextTooLong)
+        );
+    }
+
+    #[test]
+    fn test_hedged_signing_is_deterministic_given_the_rng() {
+        let signing_key = SigningKey::from_seed(&[9; 57]);
+        let sign = |seed| {
+            let mut rng = ChaCha20Rng::from_seed(seed);
+            signing_key.sign_hedged(&mut rng, b"", b"m").unwrap()
+        };
+        assert_eq!(sign([1; 32]), sign([1; 32]));
+        assert_ne!(sign([1; 32]), sign([2; 32]));
+    }
+
+    #[test]
+    fn test_commitment_check_detects_faults() {
+        let signing_key = SigningKey::from_seed(&[9; 57]);
+        let r = Scalar::from(1000);
+        let signature = signing_key.sign_with_nonce(&r, 0, b"", b"m");
+        assert!(check_commitment(&signature, &r).is_ok());
+
+        // A nonce which differs from the one used for R, as a faulted computation would
+        assert_eq!(
+            check_commitment(&signature, &(r + Scalar::one())),
+            Err(SignatureError::FaultDetected)
+        );
+
+        // A corrupted encoding of R
+        let mut bytes = signature.to_bytes();
+        bytes[3] ^= 0x10;
+        assert_eq!(
+            check_commitment(&Signature::from_bytes(&bytes), &r),
+            Err(SignatureError::FaultDetected)
+        );
+    }
+}
@@ -0,0 +1,656 @@
+// MuSig2 multi-signatures for Ed448
+// See "MuSig2: Simple Two-Round Schnorr Multi-Signatures" by Nick, Ruffing and Seurin
+// (https://eprint.iacr.org/2020/1261)
+//
+// The keys A_1, ..., A_n are aggregated into
+//
+//      Ã = Σ [a_i]A_i,     a_i = H("coefficient" || L || A_i),     L = H("keys" || A_1 || ... || A_n)
+//
+// In the first round every signer publishes two nonce commitments (R_i1, R_i2) = ([r_i1]B, [r_i2]B).
+// In the second round every signer computes, from the aggregated commitments (R_1, R_2),
+//
+//      b = H("nonce" || Ã || R_1 || R_2 || len(C) || C || M)
+//      R = R_1 + [b]R_2
+//      k = SHAKE256(dom4(0, C) || R || Ã || M, 114)
+//      s_i = r_i1 + b * r_i2 + k * a_i * x_i
+//
+// and (R, Σ s_i) is an ordinary RFC 8032 signature under Ã. H is SHAKE256 with a domain separator,
+// squeezed to 114 bytes and reduced modulo ℓ.
+//
+// N.B. The secret scalars x_i are the expanded RFC 8032 scalars, so the signers keep using their
+// ordinary Ed448 signing keys. Only keys in the prime-order subgroup are accepted, otherwise the
+// aggregate could fail a cofactorless verifier.
+
+use super::{
+    check_context, hash_to_scalar, Signature, SignatureError, SigningKey, VerifyingKey,
+    MAX_CONTEXT_LENGTH,
+};
+use crate::curve::edwards::{CompressedEdwardsY, ExtendedPoint};
+use crate::field::Scalar;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use std::fmt;
+
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Domain separator for every hash in MuSig2, apart from the RFC 8032 challenge
+const MUSIG_DOMAIN: &[u8] = b"Ed448-Goldilocks MuSig2";
+
+/// The length of an encoded public nonce, in bytes
+pub const PUBLIC_NONCE_LENGTH: usize = 114;
+
+/// The length of an encoded partial signature, in bytes
+pub const PARTIAL_SIGNATURE_LENGTH: usize = 57;
+
+/// Computes H(tag || parts[0] || ... || parts[n]) as a scalar
+fn hash(tag: &[u8], parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Shake256::default().chain(MUSIG_DOMAIN).chain(tag);
+    for part in parts {
+        hasher.update(part);
+    }
+
+    let mut output = [0u8; 114];
+    hasher.finalize_xof().read(&mut output);
+    Scalar::from_bytes_mod_order_wide(&output)
+}
+
+/// Decodes a nonce commitment, which must be the canonical encoding of a point in the prime-order subgroup
+fn decode_commitment(bytes: &[u8; 57]) -> Option<ExtendedPoint> {
+    let point = CompressedEdwardsY(*bytes).decompress_strict()?;
+    if point.is_torsion_free() {
+        Some(point)
+    } else {
+        None
+    }
+}
+
+/// The aggregated public key of a set of signers, together with the coefficient of every signer
+#[derive(Clone, Debug)]
+pub struct KeyAggContext {
+    verifying_keys: Vec<VerifyingKey>,
+    coefficients: Vec<Scalar>,
+    aggregated_key: VerifyingKey,
+}
+
+impl KeyAggContext {
+    /// Aggregates the public keys of the signers
+    ///
+    /// The order of the keys matters, every signer must use the same list.
+    /// Each key must be in the prime-order subgroup and appear only once.
+    pub fn new(verifying_keys: &[VerifyingKey]) -> Result<KeyAggContext, SignatureError> {
+        if verifying_keys.is_empty() {
+            return Err(SignatureError::LengthMismatch);
+        }
+        if verifying_keys
+            .iter()
+            .any(|key| !key.point.is_torsion_free())
+        {
+            return Err(SignatureError::InvalidPublicKey);
+        }
+        // Every signer is identified by their key
+        for (i, key) in verifying_keys.iter().enumerate() {
+            if verifying_keys[..i].contains(key) {
+                return Err(SignatureError::InvalidPublicKey);
+            }
+        }
+
+        let encodings: Vec<&[u8]> = verifying_keys
+            .iter()
+            .map(|key| &key.compressed.as_bytes()[..])
+            .collect();
+        let L = hash(b"keys", &encodings).to_bytes_rfc_8032();
+
+        let coefficients: Vec<Scalar> = encodings
+            .iter()
+            .map(|key| hash(b"coefficient", &[&L, key]))
+            .collect();
+
+        let aggregated_point = verifying_keys
+            .iter()
+            .zip(&coefficients)
+            .fold(ExtendedPoint::identity(), |sum, (key, a)| {
+                sum + key.point.scalar_mul(a)
+            });
+        // The coefficients are random, so this only happens if the keys were chosen to cancel out
+        if aggregated_point == ExtendedPoint::identity() {
+            return Err(SignatureError::InvalidPublicKey);
+        }
+
+        Ok(KeyAggContext {
+            verifying_keys: verifying_keys.to_vec(),
+            coefficients,
+            aggregated_key: VerifyingKey::from_point(aggregated_point),
+        })
+    }
+
+    /// The aggregated public key, which verifies the final signature
+    pub fn aggregated_key(&self) -> VerifyingKey {
+        self.aggregated_key
+    }
+
+    /// The public keys of the signers, in the order they were aggregated in
+    pub fn verifying_keys(&self) -> &[VerifyingKey] {
+        &self.verifying_keys
+    }
+
+    /// Returns the coefficient a_i of `verifying_key`
+    fn coefficient(&self, verifying_key: &VerifyingKey) -> Result<Scalar, SignatureError> {
+        self.verifying_keys
+            .iter()
+            .position(|key| key == verifying_key)
+            .map(|i| self.coefficients[i])
+            .ok_or(SignatureError::InvalidPublicKey)
+    }
+
+    /// Computes the nonce coefficient b, the commitment R and the challenge k of a signing session
+    fn session(
+        &self,
+        aggregate_nonce: &AggregateNonce,
+        context: &[u8],
+        message: &[u8],
+    ) -> (Scalar, CompressedEdwardsY, Scalar) {
+        debug_assert!(context.len() <= MAX_CONTEXT_LENGTH);
+
+        let R_1 = aggregate_nonce.R_1.compress();
+        let R_2 = aggregate_nonce.R_2.compress();
+        let b = hash(
+            b"nonce",
+            &[
+                self.aggregated_key.as_bytes(),
+                R_1.as_bytes(),
+                R_2.as_bytes(),
+                &[context.len() as u8],
+                context,
+                message,
+            ],
+        );
+
+        let R = (aggregate_nonce.R_1 + aggregate_nonce.R_2.scalar_mul(&b)).compress();
+
+        // k = SHAKE256(dom4(0, C) || R || Ã || M, 114)
+        let k = hash_to_scalar(
+            0,
+            context,
+            &[R.as_bytes(), self.aggregated_key.as_bytes(), message],
+        );
+
+        (b, R, k)
+    }
+
+    /// Computes the partial signature of `signing_key` in the second round
+    ///
+    /// The secret nonce is consumed, so it cannot be used for a second signature.
+    pub fn partial_sign(
+        &self,
+        signing_key: &SigningKey,
+        secret_nonce: SecretNonce,
+        aggregate_nonce: &AggregateNonce,
+        context: &[u8],
+        message: &[u8],
+    ) -> Result<PartialSignature, SignatureError> {
+        check_context(context)?;
+        if secret_nonce.verifying_key != signing_key.verifying_key.compressed {
+            return Err(SignatureError::InvalidPublicKey);
+        }
+        let a = self.coefficient(&signing_key.verifying_key)?;
+
+        let (b, _, k) = self.session(aggregate_nonce, context, message);
+
+        // s_i = r_i1 + b * r_i2 + k * a_i * x_i
+        let s = secret_nonce.r_1 + b * secret_nonce.r_2 + k * a * signing_key.scalar;
+        Ok(PartialSignature(s))
+    }
+
+    /// Checks the partial signature of the signer with `verifying_key`, who committed to `public_nonce`
+    ///
+    /// This identifies the signer who is responsible when the aggregated signature is invalid.
+    pub fn verify_partial(
+        &self,
+        verifying_key: &VerifyingKey,
+        public_nonce: &PublicNonce,
+        aggregate_nonce: &AggregateNonce,
+        partial_signature: &PartialSignature,
+        context: &[u8],
+        message: &[u8],
+    ) -> Result<(), SignatureError> {
+        check_context(context)?;
+        let a = self.coefficient(verifying_key)?;
+
+        let (b, _, k) = self.session(aggregate_nonce, context, message);
+
+        // [s_i]B = R_i1 + [b]R_i2 + [k * a_i]A_i
+        let expected = public_nonce.R_1
+            + public_nonce.R_2.scalar_mul(&b)
+            + verifying_key.point.scalar_mul(&(k * a));
+        if ExtendedPoint::generator().scalar_mul(&partial_signature.0) == expected {
+            Ok(())
+        } else {
+            Err(SignatureError::VerificationFailed)
+        }
+    }
+
+    /// Combines the partial signatures of all signers into an Ed448 signature under the aggregated key
+    ///
+    /// The partial signatures are not checked here, so the result should be verified,
+    /// falling back to `verify_partial` to find the culprit if it is invalid.
+    pub fn aggregate_signatures(
+        &self,
+        aggregate_nonce: &AggregateNonce,
+        partial_signatures: &[PartialSignature],
+        context: &[u8],
+        message: &[u8],
+    ) -> Result<Signature, SignatureError> {
+        check_context(context)?;
+        if partial_signatures.len() != self.verifying_keys.len() {
+            return Err(SignatureError::LengthMismatch);
+        }
+
+        let (_, R, _) = self.session(aggregate_nonce, context, message);
+        let s = partial_signatures
+            .iter()
+            .fold(Scalar::zero(), |sum, partial| sum + partial.0);
+
+        Ok(Signature {
+            R,
+            s: s.to_bytes_rfc_8032(),
+        })
+    }
+}
+
+/// The secret nonces (r_1, r_2) of a signer for one signing session
+///
+/// It cannot be cloned and is consumed by `KeyAggContext::partial_sign`,
+/// since signing two messages with the same nonces reveals the secret key.
+pub struct SecretNonce {
+    r_1: Scalar,
+    r_2: Scalar,
+    verifying_key: CompressedEdwardsY,
+}
+
+impl fmt::Debug for SecretNonce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretNonce")
+            .field("verifying_key", &self.verifying_key)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretNonce {
+    fn drop(&mut self) {
+        self.r_1.zeroize();
+        self.r_2.zeroize();
+    }
+}
+
+impl SecretNonce {
+    /// Derives the nonces for a signing session from fresh random bytes, like NonceGen of BIP-327
+    ///
+    /// Besides the random bytes, the nonce prefix and the public key of the signer, the
+    /// aggregated key and the message, if it is already known, are hashed in. The random bytes
+    /// must never be used twice.
+    #[cfg(any(test, feature = "rand"))]
+    pub(crate) fn from_random(
+        signing_key: &SigningKey,
+        random: &[u8; 64],
+        key_agg: &KeyAggContext,
+        message: Option<&[u8]>,
+    ) -> SecretNonce {
+        let verifying_key = signing_key.verifying_key.compressed;
+        let aggregated_key = key_agg.aggregated_key.compressed;
+        // The message is prefixed with a flag and its length, as in BIP-327
+        let (message_flag, message) = match message {
+            Some(message) => ([1u8], message),
+            None => ([0u8], &[][..]),
+        };
+        let message_length = (message.len() as u64).to_le_bytes();
+        let r = |i: u8| {
+            hash(
+                b"secret nonce",
+                &[
+                    random,
+                    &signing_key.prefix,
+                    verifying_key.as_bytes(),
+                    aggregated_key.as_bytes(),
+                    &message_flag,
+                    &message_length,
+                    message,
+                    &[i],
+                ],
+            )
+        };
+
+        SecretNonce {
+            r_1: r(1),
+            r_2: r(2),
+            verifying_key,
+        }
+    }
+
+    /// Generates the nonces for a signing session of `key_agg`
+    ///
+    /// `message` can be left out when the nonces are generated before the message is known.
+    #[cfg(feature = "rand")]
+    pub fn generate<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        signing_key: &SigningKey,
+        key_agg: &KeyAggContext,
+        message: Option<&[u8]>,
+    ) -> SecretNonce {
+        #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+        let mut random = [0u8; 64];
+        rng.fill_bytes(&mut random);
+        let nonce = SecretNonce::from_random(signing_key, &random, key_agg, message);
+
+        #[cfg(feature = "zeroize")]
+        random.zeroize();
+
+        nonce
+    }
+
+    /// Returns the commitments to these nonces, which are sent to the other signers in the first round
+    pub fn public_nonce(&self) -> PublicNonce {
+        PublicNonce {
+            R_1: ExtendedPoint::generator().scalar_mul(&self.r_1),
+            R_2: ExtendedPoint::generator().scalar_mul(&self.r_2),
+        }
+    }
+}
+
+/// The commitments (R_1, R_2) = ([r_1]B, [r_2]B) of a signer
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PublicNonce {
+    R_1: ExtendedPoint,
+    R_2: ExtendedPoint,
+}
+
+impl PublicNonce {
+    /// Encodes the commitments as `R_1 || R_2`
+    pub fn to_bytes(&self) -> [u8; PUBLIC_NONCE_LENGTH] {
+        let R_1 = self.R_1.compress();
+        let R_2 = self.R_2.compress();
+        std::array::from_fn(|i| if i < 57 { R_1.0[i] } else { R_2.0[i - 57] })
+    }
+
+    /// Parses commitments encoded as `R_1 || R_2`
+    ///
+    /// Both points must be canonically encoded and in the prime-order subgroup.
+    pub fn from_bytes(bytes: &[u8; PUBLIC_NONCE_LENGTH]) -> Result<PublicNonce, SignatureError> {
+        let R_1 = decode_commitment(&std::array::from_fn(|i| bytes[i]));
+        let R_2 = decode_commitment(&std::array::from_fn(|i| bytes[57 + i]));
+        match (R_1, R_2) {
+            (Some(R_1), Some(R_2)) => Ok(PublicNonce { R_1, R_2 }),
+            _ => Err(SignatureError::InvalidSignature),
+        }
+    }
+}
+
+/// The sum of the public nonces of all signers
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AggregateNonce {
+    R_1: ExtendedPoint,
+    R_2: ExtendedPoint,
+}
+
+impl AggregateNonce {
+    /// Sums the public nonces of all signers
+    pub fn new(public_nonces: &[PublicNonce]) -> AggregateNonce {
+        let (R_1, R_2) = public_nonces.iter().fold(
+            (ExtendedPoint::identity(), ExtendedPoint::identity()),
+            |(R_1, R_2), nonce| (R_1 + nonce.R_1, R_2 + nonce.R_2),
+        );
+        AggregateNonce { R_1, R_2 }
+    }
+}
+
+/// The share s_i of a signer in the response of the aggregated signature
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PartialSignature(Scalar);
+
+impl PartialSignature {
+    /// Encodes the partial signature as a 57 byte little-endian scalar
+    pub fn to_bytes(&self) -> [u8; PARTIAL_SIGNATURE_LENGTH] {
+        self.0.to_bytes_rfc_8032()
+    }
+
+    /// Parses a partial signature, which must be reduced modulo ℓ
+    pub fn from_bytes(
+        bytes: &[u8; PARTIAL_SIGNATURE_LENGTH],
+    ) -> Result<PartialSignature, SignatureError> {
+        Scalar::from_canonical_bytes(*bytes)
+            .map(PartialSignature)
+            .ok_or(SignatureError::InvalidSignature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::edwards::affine::AffinePoint;
+    use crate::ed448::VerificationPolicy;
+    use crate::field::FieldElement;
+
+    fn signing_keys(n: u8) -> Vec<SigningKey> {
+        (0..n).map(|i| SigningKey::from_seed(&[i; 57])).collect()
+    }
+
+    /// Runs both rounds of MuSig2 and returns the public nonces, the aggregated nonce and the partial signatures
+    fn run_session(
+        keys: &[SigningKey],
+        key_agg: &KeyAggContext,
+        random: &[u8; 64],
+        context: &[u8],
+        message: &[u8],
+    ) -> (Vec<PublicNonce>, AggregateNonce, Vec<PartialSignature>) {
+        let secret_nonces: Vec<SecretNonce> = keys
+            .iter()
+            .map(|key| SecretNonce::from_random(key, random, key_agg, Some(message)))
+            .collect();
+        let public_nonces: Vec<PublicNonce> =
+            secret_nonces.iter().map(|n| n.public_nonce()).collect();
+        let aggregate_nonce = AggregateNonce::new(&public_nonces);
+
+        let partials = keys
+            .iter()
+            .zip(secret_nonces)
+            .map(|(key, nonce)| {
+                key_agg
+                    .partial_sign(key, nonce, &aggregate_nonce, context, message)
+                    .unwrap()
+            })
+            .collect();
+        (public_nonces, aggregate_nonce, partials)
+    }
+
+    #[test]
+    fn test_musig2_produces_rfc8032_signatures() {
+        for n in [1, 2, 5] {
+            let keys = signing_keys(n);
+            let verifying_keys: Vec<VerifyingKey> =
+                keys.iter().map(|key| key.verifying_key()).collect();
+            let key_agg = KeyAggContext::new(&verifying_keys).unwrap();
+
+            for (context, message) in [(&b""[..], &b"message"[..]), (b"foo", b"")] {
+                let (public_nonces, aggregate_nonce, partials) =
+                    run_session(&keys, &key_agg, &[7; 64], context, message);
+
+                for ((verifying_key, public_nonce), partial) in
+                    verifying_keys.iter().zip(&public_nonces).zip(&partials)
+                {
+                    assert!(key_agg
+                        .verify_partial(
+                            verifying_key,
+                            public_nonce,
+                            &aggregate_nonce,
+                            partial,
+                            context,
+                            message
+                        )
+                        .is_ok());
+                }
+
+                let signature = key_agg
+                    .aggregate_signatures(&aggregate_nonce, &partials, context, message)
+                    .unwrap();
+
+                // The reference verifier, with the strictest rules
+                let aggregated_key =
+                    VerifyingKey::from_bytes(&key_agg.aggregated_key().to_bytes()).unwrap();
+                assert!(aggregated_key
+                    .verify_with_policy(VerificationPolicy::Strict, context, message, &signature)
+                    .is_ok());
+                assert!(aggregated_key
+                    .verify_with_context(context, b"other", &signature)
+                    .is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_partial_signatures() {
+        let keys = signing_keys(3);
+        let verifying_keys: Vec<VerifyingKey> =
+            keys.iter().map(|key| key.verifying_key()).collect();
+        let key_agg = KeyAggContext::new(&verifying_keys).unwrap();
+        let message = b"message";
+
+        let (public_nonces, aggregate_nonce, mut partials) =
+            run_session(&keys, &key_agg, &[7; 64], b"", message);
+        partials[1] = PartialSignature(partials[1].0 + Scalar::one());
+
+        // The culprit is identified
+        for (i, verifying_key) in verifying_keys.iter().enumerate() {
+            let result = key_agg.verify_partial(
+                verifying_key,
+                &public_nonces[i],
+                &aggregate_nonce,
+                &partials[i],
+                b"",
+                message,
+            );
+            assert_eq!(result.is_ok(), i != 1);
+        }
+
+        let signature = key_agg
+            .aggregate_signatures(&aggregate_nonce, &partials, b"", message)
+            .unwrap();
+        assert!(key_agg
+            .aggregated_key()
+            .verify(message, &signature)
+            .is_err());
+
+        assert_eq!(
+            key_agg.aggregate_signatures(&aggregate_nonce, &partials[..2], b"", message),
+            Err(SignatureError::LengthMismatch)
+        );
+    }
+
+    #[test]
+    fn test_key_aggregation() {
+        let keys = signing_keys(3);
+        let verifying_keys: Vec<VerifyingKey> =
+            keys.iter().map(|key| key.verifying_key()).collect();
+
+        // The aggregated key depends on the order of the keys
+        let mut reversed = verifying_keys.clone();
+        reversed.reverse();
+        assert_ne!(
+            KeyAggContext::new(&verifying_keys)
+                .unwrap()
+                .aggregated_key(),
+            KeyAggContext::new(&reversed).unwrap().aggregated_key()
+        );
+
+        assert_eq!(
+            KeyAggContext::new(&[]).unwrap_err(),
+            SignatureError::LengthMismatch
+        );
+
+        // A key which is not in the prime-order subgroup
+        let torsion = AffinePoint {
+            x: FieldElement::one(),
+            y: FieldElement::zero(),
+        }
+        .to_extended();
+        let mixed_order = VerifyingKey::from_point(verifying_keys[0].point + torsion);
+        assert_eq!(
+            KeyAggContext::new(&[verifying_keys[1], mixed_order]).unwrap_err(),
+            SignatureError::InvalidPublicKey
+        );
+
+        // Duplicate keys
+        assert_eq!(
+            KeyAggContext::new(&[verifying_keys[0], verifying_keys[1], verifying_keys[0]])
+                .unwrap_err(),
+            SignatureError::InvalidPublicKey
+        );
+
+        // A signer who is not part of the aggregated key
+        let key_agg = KeyAggContext::new(&verifying_keys[..2]).unwrap();
+        let outsider = &keys[2];
+        let nonce = SecretNonce::from_random(outsider, &[7; 64], &key_agg, None);
+        let aggregate_nonce = AggregateNonce::new(&[nonce.public_nonce()]);
+        assert_eq!(
+            key_agg.partial_sign(outsider, nonce, &aggregate_nonce, b"", b"message"),
+            Err(SignatureError::InvalidPublicKey)
+        );
+
+        // Nonces are bound to the key they were derived for
+        let nonce = SecretNonce::from_random(&keys[0], &[7; 64], &key_agg, None);
+        assert_eq!(
+            key_agg.partial_sign(&keys[1], nonce, &aggregate_nonce, b"", b"message"),
+            Err(SignatureError::InvalidPublicKey)
+        );
+    }
+
+    #[test]
+    fn test_nonce_derivation() {
+        let keys = signing_keys(3);
+        let verifying_keys: Vec<VerifyingKey> =
+            keys.iter().map(|key| key.verifying_key()).collect();
+        let key_agg = KeyAggContext::new(&verifying_keys).unwrap();
+        let other_key_agg = KeyAggContext::new(&verifying_keys[..2]).unwrap();
+
+        // Repeated random bytes still give other nonces for another message or aggregated key
+        let nonce = |random, key_agg, message| {
+            SecretNonce::from_random(&keys[0], random, key_agg, message).public_nonce()
+        };
+        let public_nonce = nonce(&[7; 64], &key_agg, Some(&b"message"[..]));
+        for other in [
+            nonce(&[8; 64], &key_agg, Some(&b"message"[..])),
+            nonce(&[7; 64], &other_key_agg, Some(&b"message"[..])),
+            nonce(&[7; 64], &key_agg, Some(&b"other"[..])),
+            nonce(&[7; 64], &key_agg, Some(&b""[..])),
+            nonce(&[7; 64], &key_agg, None),
+        ] {
+            assert_ne!(other, public_nonce);
+        }
+        assert_eq!(
+            nonce(&[7; 64], &key_agg, Some(&b"message"[..])),
+            public_nonce
+        );
+    }
+
+    #[test]
+    fn test_encodings() {
+        let key = SigningKey::from_seed(&[1; 57]);
+        let key_agg = KeyAggContext::new(&[key.verifying_key()]).unwrap();
+        let public_nonce = SecretNonce::from_random(&key, &[7; 64], &key_agg, None).public_nonce();
+        let bytes = public_nonce.to_bytes();
+        assert_eq!(PublicNonce::from_bytes(&bytes).unwrap(), public_nonce);
+
+        let mut bad = bytes;
+        bad[56] ^= 0x01;
+        assert!(PublicNonce::from_bytes(&bad).is_err());
+
+        let partial = PartialSignature(Scalar::from(42));
+        assert_eq!(
+            PartialSignature::from_bytes(&partial.to_bytes()).unwrap(),
+            partial
+        );
+        assert!(PartialSignature::from_bytes(&[0xff; 57]).is_err());
+    }
+}
@@ -0,0 +1,294 @@
+// Ed448ph, the pre-hashed variant of Ed448 (HashEdDSA in RFC 8032)
+//
+// The message is first hashed with PH(M) = SHAKE256(M, 64) and the 64 byte digest is then
+// signed under `dom4(1, context)`. Since only the running SHAKE256 state needs to be kept
+// around, messages can be signed and verified without ever buffering them.
+
+use super::{
+    check_context, Signature, SignatureError, SigningKey, VerificationPolicy, VerifyingKey,
+};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use std::io;
+
+/// The length of the pre-hashed message PH(M), in bytes
+pub const PREHASH_LENGTH: usize = 64;
+
+/// Value of the `phflag` in `dom4` when the message is pre-hashed
+const PREHASHED: u8 = 1;
+
+/// Squeezes PH(M) out of a SHAKE256 state which has absorbed the message
+fn finalize_prehash(prehashed_message: Shake256) -> [u8; PREHASH_LENGTH] {
+    let mut digest = [0u8; PREHASH_LENGTH];
+    prehashed_message.finalize_xof().read(&mut digest);
+    digest
+}
+
+impl SigningKey {
+    /// Signs a message in Ed448ph mode
+    ///
+    /// `prehashed_message` is a SHAKE256 state which has absorbed the whole message.
+    pub fn sign_prehashed(
+        &self,
+        context: &[u8],
+        prehashed_message: Shake256,
+    ) -> Result<Signature, SignatureError> {
+        check_context(context)?;
+        let digest = finalize_prehash(prehashed_message);
+        Ok(self.sign_inner(PREHASHED, context, &digest))
+    }
+
+    /// Signs a message in Ed448ph mode, hashing the message in one go
+    pub fn sign_ph(&self, context: &[u8], message: &[u8]) -> Result<Signature, SignatureError> {
+        self.sign_prehashed(context, Shake256::default().chain(message))
+    }
+
+    /// Returns a signer which absorbs the message incrementally, see [`StreamingSigner`]
+    pub fn streaming_signer<'k>(
+        &'k self,
+        context: &[u8],
+    ) -> Result<StreamingSigner<'k>, SignatureError> {
+        check_context(context)?;
+        Ok(StreamingSigner {
+            signing_key: self,
+            context: context.to_vec(),
+            hasher: Shake256::default(),
+        })
+    }
+}
+
+impl VerifyingKey {
+    /// Verifies an Ed448ph signature
+    ///
+    /// `prehashed_message` is a SHAKE256 state which has absorbed the whole message.
+    pub fn verify_prehashed(
+        &self,
+        context: &[u8],
+        prehashed_message: Shake256,
+        signature: &Signature,
+    ) -> Result<(), SignatureError> {
+        check_context(context)?;
+        let digest = finalize_prehash(prehashed_message);
+        self.verify_inner(
+            VerificationPolicy::Permissive,
+            PREHASHED,
+            context,
+            &digest,
+            signature,
+        )
+    }
+
+    /// Verifies an Ed448ph signature, hashing the message in one go
+    pub fn verify_ph(
+        &self,
+        context: &[u8],
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<(), SignatureError> {
+        self.verify_prehashed(context, Shake256::default().chain(message), signature)
+    }
+
+    /// Returns a verifier which absorbs the message incrementally, see [`StreamingVerifier`]
+    pub fn streaming_verifier(&self, context: &[u8]) -> Result<StreamingVerifier, SignatureError> {
+        check_context(context)?;
+        Ok(StreamingVerifier {
+            verifying_key: *self,
+            context: context.to_vec(),
+            hasher: Shake256::default(),
+        })
+    }
+}
+
+/// Produces an Ed448ph signature over a message which is fed in chunks
+///
+/// This also implements `std::io::Write`, so a file can be signed with `std::io::copy`.
+#[derive(Clone)]
+pub struct StreamingSigner<'k> {
+    signing_key: &'k SigningKey,
+    context: Vec<u8>,
+    hasher: Shake256,
+}
+
+impl<'k> StreamingSigner<'k> {
+    /// Absorbs the next chunk of the message
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+    }
+
+    /// Signs everything that has been absorbed so far
+    pub fn finalize(self) -> Signature {
+        let digest = finalize_prehash(self.hasher);
+        self.signing_key
+            .sign_inner(PREHASHED, &self.context, &digest)
+    }
+}
+
+impl<'k> io::Write for StreamingSigner<'k> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Checks an Ed448ph signature over a message which is fed in chunks
+///
+/// This also implements `std::io::Write`, so a file can be checked with `std::io::copy`.
+#[derive(Clone)]
+pub struct StreamingVerifier {
+    verifying_key: VerifyingKey,
+    context: Vec<u8>,
+    hasher: Shake256,
+}
+
+impl StreamingVerifier {
+    /// Absorbs the next chunk of the message
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+    }
+
+    /// Verifies the signature over everything that has been absorbed so far
+    pub fn finalize(self, signature: &Signature) -> Result<(), SignatureError> {
+        let digest = finalize_prehash(self.hasher);
+        self.verifying_key.verify_inner(
+            VerificationPolicy::Permissive,
+            PREHASHED,
+            &self.context,
+            &digest,
+            signature,
+        )
+    }
+}
+
+impl io::Write for StreamingVerifier {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ed448::{PUBLIC_KEY_LENGTH, SECRET_KEY_LENGTH, SIGNATURE_LENGTH};
+    use hex_literal::hex;
+
+    struct TestVector {
+        secret_key: [u8; SECRET_KEY_LENGTH],
+        public_key: [u8; PUBLIC_KEY_LENGTH],
+        message: &'static [u8],
+        context: &'static [u8],
+        signature: [u8; SIGNATURE_LENGTH],
+    }
+
+    // RFC 8032 Section 7.5, Ed448ph
+    const TEST_VECTORS: [TestVector; 2] = [
+        // TEST abc
+        TestVector {
+            secret_key: hex!(
+                "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42ef7822e0d5104127dc05d6dbefde69e3"
+                "ab2cec7c867c6e2c49"
+            ),
+            public_key: hex!(
+                "259b71c19f83ef77a7abd26524cbdb3161b590a48f7d17de3ee0ba9c52beb743c09428a131d6b1b57303d90d8132c276"
+                "d5ed3d5d01c0f53880"
+            ),
+            message: &hex!("616263"),
+            context: &hex!(""),
+            signature: hex!(
+                "822f6901f7480f3d5f562c592994d9693602875614483256505600bbc281ae381f54d6bce2ea911574932f52a4e6cadd"
+                "78769375ec3ffd1b801a0d9b3f4030cd433964b6457ea39476511214f97469b57dd32dbc560a9a94d00bff07620464a3"
+                "ad203df7dc7ce360c3cd3696d9d9fab90f00"
+            ),
+        },
+        // TEST abc (with context)
+        TestVector {
+            secret_key: hex!(
+                "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42ef7822e0d5104127dc05d6dbefde69e3"
+                "ab2cec7c867c6e2c49"
+            ),
+            public_key: hex!(
+                "259b71c19f83ef77a7abd26524cbdb3161b590a48f7d17de3ee0ba9c52beb743c09428a131d6b1b57303d90d8132c276"
+                "d5ed3d5d01c0f53880"
+            ),
+            message: &hex!("616263"),
+            context: &hex!("666f6f"),
+            signature: hex!(
+                "c32299d46ec8ff02b54540982814dce9a05812f81962b649d528095916a2aa481065b1580423ef927ecf0af5888f90da"
+                "0f6a9a85ad5dc3f280d91224ba9911a3653d00e484e2ce232521481c8658df304bb7745a73514cdb9bf3e15784ab7128"
+                "4f8d0704a608c54a6b62d97beb511d132100"
+            ),
+        },
+    ];
+
+    #[test]
+    fn test_rfc8032_ed448ph_vectors() {
+        for vector in TEST_VECTORS.iter() {
+            let signing_key = SigningKey::from_seed(&vector.secret_key);
+            assert_eq!(signing_key.verifying_key().to_bytes(), vector.public_key);
+
+            let signature = signing_key.sign_ph(vector.context, vector.message).unwrap();
+            assert_eq!(signature.to_bytes(), vector.signature);
+
+            let verifying_key = VerifyingKey::from_bytes(&vector.public_key).unwrap();
+            let signature = Signature::from_bytes(&vector.signature);
+            assert!(verifying_key
+                .verify_ph(vector.context, vector.message, &signature)
+                .is_ok());
+
+            // An Ed448ph signature is not a valid Ed448 signature and vice versa
+            assert_eq!(
+                verifying_key.verify_with_context(vector.context, vector.message, &signature),
+                Err(SignatureError::VerificationFailed)
+            );
+            let pure = signing_key
+                .sign_with_context(vector.context, vector.message)
+                .unwrap();
+            assert_eq!(
+                verifying_key.verify_ph(vector.context, vector.message, &pure),
+                Err(SignatureError::VerificationFailed)
+            );
+        }
+    }
+
+    #[test]
+    fn test_streaming_matches_one_shot() {
+        let vector = &TEST_VECTORS[1];
+        let signing_key = SigningKey::from_seed(&vector.secret_key);
+
+        let mut signer = signing_key.streaming_signer(vector.context).unwrap();
+        for chunk in vector.message.chunks(1) {
+            signer.update(chunk);
+        }
+        let signature = signer.finalize();
+        assert_eq!(signature.to_bytes(), vector.signature);
+
+        let message = vec![0xa5u8; 100_000];
+        let mut signer = signing_key.streaming_signer(b"stream").unwrap();
+        std::io::copy(&mut &message[..], &mut signer).unwrap();
+        let signature = signer.finalize();
+        assert_eq!(signature, signing_key.sign_ph(b"stream", &message).unwrap());
+
+        let mut verifier = signing_key
+            .verifying_key()
+            .streaming_verifier(b"stream")
+            .unwrap();
+        for chunk in message.chunks(4096) {
+            verifier.update(chunk);
+        }
+        assert!(verifier.clone().finalize(&signature).is_ok());
+
+        verifier.update(b"trailing data");
+        assert_eq!(
+            verifier.finalize(&signature),
+            Err(SignatureError::VerificationFailed)
+        );
+    }
+}
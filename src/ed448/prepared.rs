@@ -0,0 +1,255 @@
+// Verifying keys which are prepared for many verifications
+//
+// Verification computes V = [S]B - [k]A and compares it with R. The scalar multiplications are
+// done on the Twisted-Goldilocks curve, with the scalars split as S = 4 S' + (S mod 4) and
+// k = 4 k' + (k mod 4), so that the result is exact and not only correct up to the cofactor:
+//
+//      V = φ̂([S']φ(B) + [k']φ(-A)) + [S mod 4]B + [k mod 4](-A)
+//
+// The odd multiples of φ(B) are computed once for the whole program, and the odd multiples
+// of φ(-A) are kept in the prepared key, so each verification only pays for the shared doublings.
+
+use super::{
+    check_context, hash_to_scalar, Signature, SignatureError, VerificationPolicy, VerifyingKey,
+    PUBLIC_KEY_LENGTH,
+};
+use crate::curve::edwards::{CompressedEdwardsY, DecodingPolicy, ExtendedPoint};
+use crate::curve::scalar_mul::double_base::{double_base_scalar_mul_with_table, PointNafTable};
+use crate::field::Scalar;
+use std::fmt;
+
+/// An Ed448 public key with precomputations to speed up repeated verification
+///
+/// Preparing a key costs about as much as one verification, so this is
+/// worthwhile for keys which check many signatures, such as release-signing keys.
+#[derive(Clone)]
+pub struct PreparedVerifyingKey {
+    verifying_key: VerifyingKey,
+    /// Whether the key passes the checks of `VerificationPolicy::Strict`
+    strictly_valid: bool,
+    /// The odd multiples of φ(-A)
+    neg_A_table: PointNafTable,
+}
+
+impl fmt::Debug for PreparedVerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PreparedVerifyingKey")
+            .field(&self.verifying_key.compressed)
+            .finish()
+    }
+}
+
+impl From<&VerifyingKey> for PreparedVerifyingKey {
+    fn from(verifying_key: &VerifyingKey) -> PreparedVerifyingKey {
+        let point = verifying_key.point;
+        let strictly_valid =
+            verifying_key.compressed.decompress_strict().is_some() && !point.is_small_order();
+
+        PreparedVerifyingKey {
+            verifying_key: *verifying_key,
+            strictly_valid,
+            neg_A_table: PointNafTable::from(&point.negate().to_twisted()),
+        }
+    }
+}
+
+impl PreparedVerifyingKey {
+    /// Decodes and prepares an encoded public key
+    pub fn from_compressed(
+        compressed: &CompressedEdwardsY,
+    ) -> Result<PreparedVerifyingKey, SignatureError> {
+        Self::from_bytes(compressed.as_bytes())
+    }
+
+    /// Decodes and prepares a 57 byte encoded public key
+    pub fn from_bytes(
+        bytes: &[u8; PUBLIC_KEY_LENGTH],
+    ) -> Result<PreparedVerifyingKey, SignatureError> {
+        let verifying_key = VerifyingKey::from_bytes(bytes)?;
+        Ok(PreparedVerifyingKey::from(&verifying_key))
+    }
+
+    /// Returns the key that was prepared
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.verifying_key
+    }
+
+    /// Same as `VerifyingKey::verify`
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> Result<(), SignatureError> {
+        self.verify_inner(VerificationPolicy::Permissive, 0, &[], message, signature)
+    }
+
+    /// Same as `VerifyingKey::verify_strict`
+    pub fn verify_strict(
+        &self,
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<(), SignatureError> {
+        self.verify_inner(VerificationPolicy::Strict, 0, &[], message, signature)
+    }
+
+    /// Same as `VerifyingKey::verify_with_context`
+    pub fn verify_with_context(
+        &self,
+        context: &[u8],
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<(), SignatureError> {
+        self.verify_with_policy(VerificationPolicy::Permissive, context, message, signature)
+    }
+
+    /// Same as `VerifyingKey::verify_with_policy`
+    pub fn verify_with_policy(
+        &self,
+        policy: VerificationPolicy,
+        context: &[u8],
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<(), SignatureError> {
+        check_context(context)?;
+        self.verify_inner(policy, 0, context, message, signature)
+    }
+
+    /// Checks a signature over `message` under `dom4(phflag, context)`
+    /// See RFC 8032 Section 5.2.7
+    ///
+    /// N.B. This does not run in constant time, the key, message and signature are public.
+    pub(crate) fn verify_inner(
+        &self,
+        policy: VerificationPolicy,
+        phflag: u8,
+        context: &[u8],
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<(), SignatureError> {
+        let strict = policy == VerificationPolicy::Strict;
+        let decoding = if strict {
+            DecodingPolicy::Strict
+        } else {
+            DecodingPolicy::Permissive
+        };
+
+        if strict && !self.strictly_valid {
+            return Err(SignatureError::InvalidPublicKey);
+        }
+
+        let R = signature
+            .R
+            .decompress_with_policy(decoding)
+            .ok_or(SignatureError::InvalidSignature)?;
+        if strict && R.is_small_order() {
+            return Err(SignatureError::InvalidSignature);
+        }
+        let s =
+            Scalar::from_canonical_bytes(signature.s).ok_or(SignatureError::InvalidSignature)?;
+
+        // k = SHAKE256(dom4(F, C) || R || A || PH(M), 114)
+        let k = hash_to_scalar(
+            phflag,
+            context,
+            &[
+                signature.R.as_bytes(),
+                self.verifying_key.compressed.as_bytes(),
+                message,
+            ],
+        );
+
+        let V = self.basepoint_minus_key(&s, &k);
+        let valid = if strict {
+            // [S]B = R + [k]A
+            V == R
+        } else {
+            // [4][S]B = [4]R + [4][k]A
+            (V - R).double().double() == ExtendedPoint::identity()
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(SignatureError::VerificationFailed)
+        }
+    }
+
+    /// Computes [s]B - [k]A exactly
+    fn basepoint_minus_key(&self, s: &Scalar, k: &Scalar) -> ExtendedPoint {
+        let mut s_div_four = *s;
+        s_div_four.div_by_four();
+        let mut k_div_four = *k;
+        k_div_four.div_by_four();
+
+        // The dual isogeny multiplies by four
+        let partial_result =
+            double_base_scalar_mul_with_table(&k_div_four, &self.neg_A_table, &s_div_four)
+                .to_untwisted();
+
+        let neg_A = self.verifying_key.point.negate();
+        partial_result + ExtendedPoint::generator().scalar_mod_four(s) + neg_A.scalar_mod_four(k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::edwards::affine::AffinePoint;
+    use crate::ed448::SigningKey;
+    use crate::field::FieldElement;
+
+    #[test]
+    fn test_basepoint_minus_key_is_exact() {
+        let torsion = AffinePoint {
+            x: FieldElement::one(),
+            y: FieldElement::zero(),
+        }
+        .to_extended();
+        let A = SigningKey::from_seed(&[3; 57]).verifying_key().to_edwards();
+
+        // Keys with and without a small-order component
+        for point in [A, A + torsion, torsion, ExtendedPoint::identity()] {
+            let verifying_key = VerifyingKey {
+                compressed: point.compress(),
+                point,
+            };
+            let prepared = PreparedVerifyingKey::from(&verifying_key);
+
+            for (s, k) in [
+                (Scalar::from(5), Scalar::from(7)),
+                (Scalar::zero() - Scalar::from(2), Scalar::from(123456789)),
+                (Scalar::zero(), Scalar::zero() - Scalar::one()),
+            ] {
+                let expected = ExtendedPoint::generator().scalar_mul(&s) - point.scalar_mul(&k);
+                assert_eq!(prepared.basepoint_minus_key(&s, &k), expected);
+                assert_eq!(verifying_key.basepoint_minus_key(&s, &k), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_prepared_key_agrees_with_verifying_key() {
+        let signing_key = SigningKey::from_seed(&[4; 57]);
+        let verifying_key = signing_key.verifying_key();
+        let prepared = PreparedVerifyingKey::from_compressed(&verifying_key.compressed).unwrap();
+        assert_eq!(prepared.verifying_key(), verifying_key);
+
+        for i in 0..10u8 {
+            let message = vec![i; i as usize];
+            let signature = signing_key
+                .sign_with_context(b"prepared", &message)
+                .unwrap();
+            assert!(prepared
+                .verify_with_context(b"prepared", &message, &signature)
+                .is_ok());
+            assert!(prepared
+                .verify_with_policy(
+                    VerificationPolicy::Strict,
+                    b"prepared",
+                    &message,
+                    &signature
+                )
+                .is_ok());
+            assert_eq!(
+                prepared.verify(&message, &signature),
+                Err(SignatureError::VerificationFailed)
+            );
+        }
+    }
+}
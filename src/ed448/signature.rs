@@ -0,0 +1,130 @@
+use super::SIGNATURE_LENGTH;
+use crate::curve::edwards::CompressedEdwardsY;
+use std::fmt;
+use subtle::{Choice, ConstantTimeEq};
+
+/// Errors which can occur when creating or checking an Ed448 signature
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SignatureError {
+    /// The public key does not encode a point on the curve
+    InvalidPublicKey,
+    /// The `R` component does not encode a point, or `S` is not reduced modulo ℓ
+    InvalidSignature,
+    /// The context string is longer than 255 bytes
+    ContextTooLong,
+    /// The signature is well formed, but does not verify for this key and message
+    VerificationFailed,
+    /// The number of messages, signatures and keys in a batch differ
+    LengthMismatch,
+    /// The signature failed the consistency check after signing and was not released
+    FaultDetected,
+    /// The blinding key derives a blind of zero
+    InvalidBlindingKey,
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureError::InvalidPublicKey => write!(f, "invalid Ed448 public key"),
+            SignatureError::InvalidSignature => write!(f, "malformed Ed448 signature"),
+            SignatureError::ContextTooLong => write!(f, "context string exceeds 255 bytes"),
+            SignatureError::VerificationFailed => write!(f, "Ed448 signature verification failed"),
+            SignatureError::LengthMismatch => {
+                write!(f, "number of messages, signatures and keys differ")
+            }
+            SignatureError::FaultDetected => write!(f, "fault detected while signing"),
+            SignatureError::InvalidBlindingKey => write!(f, "invalid blinding key"),
+        }
+    }
+}
+
+impl std::error::Error for SignatureError {}
+
+/// An Ed448 signature, which is the pair (R, S) encoded as 57 + 57 bytes
+///
+/// No checks are done when the signature is constructed, the components
+/// are validated during verification.
+#[derive(Copy, Clone)]
+pub struct Signature {
+    pub(crate) R: CompressedEdwardsY,
+    pub(crate) s: [u8; 57],
+}
+
+impl fmt::Debug for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signature")
+            .field("R", &&self.R.0[..])
+            .field("s", &&self.s[..])
+            .finish()
+    }
+}
+
+impl ConstantTimeEq for Signature {
+    fn ct_eq(&self, other: &Signature) -> Choice {
+        self.R.ct_eq(&other.R) & self.s.ct_eq(&other.s)
+    }
+}
+
+impl PartialEq for Signature {
+    fn eq(&self, other: &Signature) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+impl Eq for Signature {}
+
+impl Signature {
+    /// Parses a signature from the 114 byte encoding `R || S`
+    pub fn from_bytes(bytes: &[u8; SIGNATURE_LENGTH]) -> Signature {
+        let R: [u8; 57] = std::array::from_fn(|i| bytes[i]);
+        let s: [u8; 57] = std::array::from_fn(|i| bytes[i + 57]);
+        Signature {
+            R: CompressedEdwardsY(R),
+            s,
+        }
+    }
+
+    /// Encodes the signature as `R || S`
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_LENGTH] {
+        std::array::from_fn(|i| if i < 57 { self.R.0[i] } else { self.s[i - 57] })
+    }
+
+    /// The encoding of the commitment `R`
+    pub fn r_bytes(&self) -> &[u8; 57] {
+        self.R.as_bytes()
+    }
+
+    /// The encoding of the response `S`
+    pub fn s_bytes(&self) -> &[u8; 57] {
+        &self.s
+    }
+}
+
+impl From<[u8; SIGNATURE_LENGTH]> for Signature {
+    fn from(bytes: [u8; SIGNATURE_LENGTH]) -> Signature {
+        Signature::from_bytes(&bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for Signature {
+    type Error = SignatureError;
+
+    /// Parses a signature from a slice, which must be exactly 114 bytes long
+    fn try_from(bytes: &[u8]) -> Result<Signature, SignatureError> {
+        let bytes: &[u8; SIGNATURE_LENGTH] = bytes
+            .try_into()
+            .map_err(|_| SignatureError::InvalidSignature)?;
+        Ok(Signature::from_bytes(bytes))
+    }
+}
+
+impl From<Signature> for [u8; SIGNATURE_LENGTH] {
+    fn from(signature: Signature) -> [u8; SIGNATURE_LENGTH] {
+        signature.to_bytes()
+    }
+}
+
+impl From<&Signature> for [u8; SIGNATURE_LENGTH] {
+    fn from(signature: &Signature) -> [u8; SIGNATURE_LENGTH] {
+        signature.to_bytes()
+    }
+}
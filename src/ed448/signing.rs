@@ -0,0 +1,204 @@
+use super::{
+    check_context, hash_to_scalar, Signature, SignatureError, VerifyingKey, SECRET_KEY_LENGTH,
+};
+use crate::curve::edwards::{CompressedEdwardsY, ExtendedPoint};
+use crate::field::Scalar;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use std::fmt;
+
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// An Ed448 signing key
+///
+/// The 57 byte seed is expanded with SHAKE256 into the secret scalar `s`
+/// and the `prefix` which is used to derive deterministic nonces.
+#[derive(Clone)]
+pub struct SigningKey {
+    seed: [u8; SECRET_KEY_LENGTH],
+    pub(crate) scalar: Scalar,
+    pub(crate) prefix: [u8; 57],
+    pub(crate) verifying_key: VerifyingKey,
+}
+
+impl fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SigningKey")
+            .field("verifying_key", &self.verifying_key)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SigningKey {
+    fn drop(&mut self) {
+        self.seed.zeroize();
+        self.scalar.zeroize();
+        self.prefix.zeroize();
+    }
+}
+
+/// Hashes a seed with SHAKE256 and clamps the lower half
+/// See RFC 8032 Section 5.2.5
+///
+/// Returns the clamped secret, which is a 448-bit integer, and the nonce prefix.
+pub(crate) fn hash_seed(seed: &[u8; SECRET_KEY_LENGTH]) -> ([u8; 57], [u8; 57]) {
+    let mut h = [0u8; 114];
+    Shake256::default().chain(seed).finalize_xof().read(&mut h);
+
+    // The two least significant bits of the first octet are cleared, all eight
+    // bits of the last octet are cleared and the highest bit of the second to last octet is set.
+    let mut secret: [u8; 57] = std::array::from_fn(|i| h[i]);
+    secret[0] &= 0xfc;
+    secret[55] |= 0x80;
+    secret[56] = 0;
+
+    let prefix: [u8; 57] = std::array::from_fn(|i| h[57 + i]);
+
+    #[cfg(feature = "zeroize")]
+    h.zeroize();
+
+    (secret, prefix)
+}
+
+/// Expands a seed into the clamped secret scalar and the nonce prefix
+pub(crate) fn expand_seed(seed: &[u8; SECRET_KEY_LENGTH]) -> (Scalar, [u8; 57]) {
+    #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+    let (mut secret, prefix) = hash_seed(seed);
+
+    let mut wide = [0u8; 114];
+    wide[..57].copy_from_slice(&secret);
+    let scalar = Scalar::from_bytes_mod_order_wide(&wide);
+
+    #[cfg(feature = "zeroize")]
+    {
+        wide.zeroize();
+        secret.zeroize();
+    }
+
+    (scalar, prefix)
+}
+
+impl SigningKey {
+    /// Derives a signing key from a 57 byte secret seed
+    pub fn from_seed(seed: &[u8; SECRET_KEY_LENGTH]) -> SigningKey {
+        let (scalar, prefix) = expand_seed(seed);
+        let point = ExtendedPoint::generator().scalar_mul(&scalar);
+
+        SigningKey {
+            seed: *seed,
+            scalar,
+            prefix,
+            verifying_key: VerifyingKey {
+                compressed: point.compress(),
+                point,
+            },
+        }
+    }
+
+    /// Generates a new signing key from a random seed
+    #[cfg(feature = "rand")]
+    pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> SigningKey {
+        let mut seed = [0u8; SECRET_KEY_LENGTH];
+        rng.fill_bytes(&mut seed);
+        let signing_key = SigningKey::from_seed(&seed);
+
+        #[cfg(feature = "zeroize")]
+        seed.zeroize();
+
+        signing_key
+    }
+
+    /// Returns the secret seed that this key was derived from
+    pub fn to_bytes(&self) -> [u8; SECRET_KEY_LENGTH] {
+        self.seed
+    }
+
+    /// Returns a view of the secret seed that this key was derived from
+    pub fn as_bytes(&self) -> &[u8; SECRET_KEY_LENGTH] {
+        &self.seed
+    }
+
+    /// Returns the public half of this key
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.verifying_key
+    }
+
+    /// Signs a message with an empty context string (PureEdDSA, `Ed448` in RFC 8032)
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        self.sign_inner(0, &[], message)
+    }
+
+    /// Signs a message under a context string of at most 255 bytes
+    pub fn sign_with_context(
+        &self,
+        context: &[u8],
+        message: &[u8],
+    ) -> Result<Signature, SignatureError> {
+        check_context(context)?;
+        Ok(self.sign_inner(0, context, message))
+    }
+
+    /// Computes a signature over `message` under `dom4(phflag, context)`
+    /// See RFC 8032 Section 5.2.6
+    pub(crate) fn sign_inner(&self, phflag: u8, context: &[u8], message: &[u8]) -> Signature {
+        // r = SHAKE256(dom4(F, C) || prefix || PH(M), 114)
+        let r = hash_to_scalar(phflag, context, &[&self.prefix, message]);
+        self.sign_with_nonce(&r, phflag, context, message)
+    }
+
+    /// Completes a signature given the nonce `r`
+    pub(crate) fn sign_with_nonce(
+        &self,
+        r: &Scalar,
+        phflag: u8,
+        context: &[u8],
+        message: &[u8],
+    ) -> Signature {
+        sign_with_secret(
+            &self.scalar,
+            &self.verifying_key.compressed,
+            r,
+            phflag,
+            context,
+            message,
+        )
+    }
+}
+
+/// Computes a signature with the secret scalar `secret`, whose public key is `verifying_key`,
+/// and the nonce `r`
+pub(crate) fn sign_with_secret(
+    secret: &Scalar,
+    verifying_key: &CompressedEdwardsY,
+    r: &Scalar,
+    phflag: u8,
+    context: &[u8],
+    message: &[u8],
+) -> Signature {
+    let R = ExtendedPoint::generator().scalar_mul(r).compress();
+
+    // k = SHAKE256(dom4(F, C) || R || A || PH(M), 114)
+    let k = hash_to_scalar(
+        phflag,
+        context,
+        &[R.as_bytes(), verifying_key.as_bytes(), message],
+    );
+
+    // S = (r + k * s) mod ℓ
+    let s = *r + k * *secret;
+
+    Signature {
+        R,
+        s: s.to_bytes_rfc_8032(),
+    }
+}
+
+impl From<&[u8; SECRET_KEY_LENGTH]> for SigningKey {
+    fn from(seed: &[u8; SECRET_KEY_LENGTH]) -> SigningKey {
+        SigningKey::from_seed(seed)
+    }
+}
@@ -0,0 +1,148 @@
+// Implementations of the RustCrypto `signature` traits, so that the Ed448 keys can be used
+// by code which is generic over the signature scheme
+//
+// `Signer` and `Verifier` are PureEdDSA with an empty context, like `sign` and `verify`.
+// `DigestSigner` and `DigestVerifier` are Ed448ph with an empty context. The traits require
+// a fixed-output `Digest`, so the SHAKE256 state is wrapped in `PrehashDigest`.
+
+use super::{Signature, SignatureError, SigningKey, VerifyingKey, SIGNATURE_LENGTH};
+use sha3::digest::consts::U64;
+use sha3::digest::{
+    ExtendableOutput, FixedOutput, HashMarker, Output, OutputSizeUser, Update, XofReader,
+};
+use sha3::Shake256;
+use signature::{DigestSigner, DigestVerifier, Keypair, SignatureEncoding, Signer, Verifier};
+
+/// The pre-hash PH(M) = SHAKE256(M, 64) of Ed448ph, as a fixed-output `Digest`
+///
+/// Feed the message with `Digest::update` and pass the digest to
+/// [`DigestSigner::sign_digest`] or [`DigestVerifier::verify_digest`].
+#[derive(Clone, Default)]
+pub struct PrehashDigest(Shake256);
+
+impl From<Shake256> for PrehashDigest {
+    fn from(hasher: Shake256) -> PrehashDigest {
+        PrehashDigest(hasher)
+    }
+}
+
+impl Update for PrehashDigest {
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+}
+
+impl OutputSizeUser for PrehashDigest {
+    type OutputSize = U64;
+}
+
+impl FixedOutput for PrehashDigest {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        self.0.finalize_xof().read(out);
+    }
+}
+
+impl HashMarker for PrehashDigest {}
+
+impl From<SignatureError> for signature::Error {
+    fn from(error: SignatureError) -> signature::Error {
+        signature::Error::from_source(error)
+    }
+}
+
+impl SignatureEncoding for Signature {
+    type Repr = [u8; SIGNATURE_LENGTH];
+}
+
+impl Signer<Signature> for SigningKey {
+    fn try_sign(&self, message: &[u8]) -> Result<Signature, signature::Error> {
+        Ok(self.sign(message))
+    }
+}
+
+impl DigestSigner<PrehashDigest, Signature> for SigningKey {
+    fn try_sign_digest(&self, digest: PrehashDigest) -> Result<Signature, signature::Error> {
+        Ok(self.sign_prehashed(&[], digest.0)?)
+    }
+}
+
+impl Keypair for SigningKey {
+    type VerifyingKey = VerifyingKey;
+
+    fn verifying_key(&self) -> VerifyingKey {
+        self.verifying_key
+    }
+}
+
+impl Verifier<Signature> for VerifyingKey {
+    fn verify(&self, message: &[u8], signature: &Signature) -> Result<(), signature::Error> {
+        Ok(VerifyingKey::verify(self, message, signature)?)
+    }
+}
+
+impl DigestVerifier<PrehashDigest, Signature> for VerifyingKey {
+    fn verify_digest(
+        &self,
+        digest: PrehashDigest,
+        signature: &Signature,
+    ) -> Result<(), signature::Error> {
+        Ok(self.verify_prehashed(&[], digest.0, signature)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ed448::PREHASH_LENGTH;
+    use sha3::digest::Digest;
+
+    #[test]
+    fn test_signature_traits() {
+        let signing_key = SigningKey::from_seed(&[7; 57]);
+        let message = b"generic over the signature scheme";
+
+        let signature: Signature = Signer::sign(&signing_key, message);
+        assert_eq!(signature, signing_key.sign(message));
+
+        let verifier: &dyn Verifier<Signature> = &Keypair::verifying_key(&signing_key);
+        assert!(verifier.verify(message, &signature).is_ok());
+        assert!(verifier.verify(b"other message", &signature).is_err());
+
+        // Round trip through the byte encoding
+        let bytes = SignatureEncoding::to_bytes(&signature);
+        assert_eq!(Signature::try_from(&bytes[..]).unwrap(), signature);
+        assert_eq!(signature.encoded_len(), 114);
+        assert_eq!(
+            Signature::try_from(&bytes[..113]),
+            Err(SignatureError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_digest_traits() {
+        let signing_key = SigningKey::from_seed(&[7; 57]);
+        let verifying_key = signing_key.verifying_key();
+        let message = b"pre-hashed message";
+
+        let signature: Signature =
+            signing_key.sign_digest(PrehashDigest::default().chain_update(message));
+        assert_eq!(signature, signing_key.sign_ph(&[], message).unwrap());
+        assert!(verifying_key
+            .verify_digest(PrehashDigest::default().chain_update(message), &signature)
+            .is_ok());
+        assert!(verifying_key
+            .verify_digest(PrehashDigest::default().chain_update(b"other"), &signature)
+            .is_err());
+
+        // PH(M) is SHAKE256(M, 64)
+        let mut expected = [0u8; PREHASH_LENGTH];
+        Shake256::default()
+            .chain(message)
+            .finalize_xof()
+            .read(&mut expected);
+        assert_eq!(
+            PrehashDigest::default().chain_update(message).finalize()[..],
+            expected
+        );
+    }
+}
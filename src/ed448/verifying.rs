@@ -0,0 +1,370 @@
+use super::{check_context, hash_to_scalar, Signature, SignatureError, PUBLIC_KEY_LENGTH};
+use crate::curve::edwards::{CompressedEdwardsY, DecodingPolicy, ExtendedPoint};
+use crate::curve::scalar_mul::double_base_scalar_mul;
+use crate::field::Scalar;
+use std::fmt;
+use subtle::{Choice, ConstantTimeEq};
+
+/// The set of rules a signature has to follow to be accepted
+///
+/// RFC 8032 leaves some freedom in how a signature is checked, so two correct
+/// implementations can disagree on the validity of a maliciously crafted signature.
+/// Systems where every party must reach the same verdict should pick a policy explicitly.
+/// In both policies `S` must be fully reduced, i.e. less than ℓ.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VerificationPolicy {
+    /// Rejects everything that an honest signer would never produce
+    ///
+    /// * `A` and `R` are decoded with [`DecodingPolicy::Strict`](crate::curve::edwards::DecodingPolicy::Strict)
+    /// * `A` and `R` must not be of small order
+    /// * the cofactorless equation `[S]B = R + [k]A` must hold, with k reduced modulo ℓ
+    Strict,
+    /// Accepts everything that can be interpreted as a valid signature, like ZIP-215 does for Ed25519
+    ///
+    /// * `A` and `R` are decoded with [`DecodingPolicy::Permissive`](crate::curve::edwards::DecodingPolicy::Permissive), the encodings are hashed as given
+    /// * `A` and `R` may be of small order
+    /// * the cofactored equation `[4][S]B = [4]R + [4][k]A` must hold
+    ///
+    /// This is the policy used by `verify` and by batch verification, so they always agree.
+    Permissive,
+}
+
+/// An Ed448 public key
+///
+/// Both the encoding and the decoded point are kept around, since the
+/// encoding is hashed and the point is used during verification.
+#[derive(Copy, Clone)]
+pub struct VerifyingKey {
+    pub(crate) compressed: CompressedEdwardsY,
+    pub(crate) point: ExtendedPoint,
+}
+
+impl fmt::Debug for VerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VerifyingKey")
+            .field(&self.compressed)
+            .finish()
+    }
+}
+
+impl ConstantTimeEq for VerifyingKey {
+    fn ct_eq(&self, other: &VerifyingKey) -> Choice {
+        self.compressed.ct_eq(&other.compressed)
+    }
+}
+
+impl PartialEq for VerifyingKey {
+    fn eq(&self, other: &VerifyingKey) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+impl Eq for VerifyingKey {}
+
+impl VerifyingKey {
+    /// Parses a 57 byte encoded public key
+    pub fn from_bytes(bytes: &[u8; PUBLIC_KEY_LENGTH]) -> Result<VerifyingKey, SignatureError> {
+        let compressed = CompressedEdwardsY(*bytes);
+        let point = compressed
+            .decompress()
+            .ok_or(SignatureError::InvalidPublicKey)?;
+        Ok(VerifyingKey { compressed, point })
+    }
+
+    /// Creates a public key from a point, which is compressed once
+    pub(crate) fn from_point(point: ExtendedPoint) -> VerifyingKey {
+        VerifyingKey {
+            compressed: point.compress(),
+            point,
+        }
+    }
+
+    /// Returns the encoding of this public key
+    pub fn to_bytes(&self) -> [u8; PUBLIC_KEY_LENGTH] {
+        self.compressed.0
+    }
+
+    /// Returns a view of the encoding of this public key
+    pub fn as_bytes(&self) -> &[u8; PUBLIC_KEY_LENGTH] {
+        self.compressed.as_bytes()
+    }
+
+    /// Returns the public key as a point on Ed448-Goldilocks
+    pub fn to_edwards(&self) -> ExtendedPoint {
+        self.point
+    }
+
+    /// Verifies a signature over a message with an empty context string
+    /// See [`VerificationPolicy::Permissive`]
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> Result<(), SignatureError> {
+        self.verify_inner(VerificationPolicy::Permissive, 0, &[], message, signature)
+    }
+
+    /// Verifies a signature over a message with an empty context string
+    /// See [`VerificationPolicy::Strict`]
+    pub fn verify_strict(
+        &self,
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<(), SignatureError> {
+        self.verify_inner(VerificationPolicy::Strict, 0, &[], message, signature)
+    }
+
+    /// Verifies a signature over a message under a context string of at most 255 bytes
+    pub fn verify_with_context(
+        &self,
+        context: &[u8],
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<(), SignatureError> {
+        self.verify_with_policy(VerificationPolicy::Permissive, context, message, signature)
+    }
+
+    /// Verifies a signature over a message under a context string, following `policy`
+    pub fn verify_with_policy(
+        &self,
+        policy: VerificationPolicy,
+        context: &[u8],
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<(), SignatureError> {
+        check_context(context)?;
+        self.verify_inner(policy, 0, context, message, signature)
+    }
+
+    /// Checks a signature over `message` under `dom4(phflag, context)`
+    /// See RFC 8032 Section 5.2.7
+    ///
+    /// N.B. This does not run in constant time, the key, message and signature are public.
+    pub(crate) fn verify_inner(
+        &self,
+        policy: VerificationPolicy,
+        phflag: u8,
+        context: &[u8],
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<(), SignatureError> {
+        let strict = policy == VerificationPolicy::Strict;
+        let decoding = if strict {
+            DecodingPolicy::Strict
+        } else {
+            DecodingPolicy::Permissive
+        };
+
+        // The key was decoded permissively when it was parsed, so it only needs to be re-checked here
+        if strict && (self.compressed.decompress_strict().is_none() || self.point.is_small_order())
+        {
+            return Err(SignatureError::InvalidPublicKey);
+        }
+
+        let R = signature
+            .R
+            .decompress_with_policy(decoding)
+            .ok_or(SignatureError::InvalidSignature)?;
+        if strict && R.is_small_order() {
+            return Err(SignatureError::InvalidSignature);
+        }
+        let s =
+            Scalar::from_canonical_bytes(signature.s).ok_or(SignatureError::InvalidSignature)?;
+
+        // k = SHAKE256(dom4(F, C) || R || A || PH(M), 114)
+        let k = hash_to_scalar(
+            phflag,
+            context,
+            &[signature.R.as_bytes(), self.compressed.as_bytes(), message],
+        );
+
+        let V = self.basepoint_minus_key(&s, &k);
+        let valid = if strict {
+            // [S]B = R + [k]A
+            V == R
+        } else {
+            // [4][S]B = [4]R + [4][k]A
+            (V - R).double().double() == ExtendedPoint::identity()
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(SignatureError::VerificationFailed)
+        }
+    }
+
+    /// Computes [s]B - [k]A exactly, like `PreparedVerifyingKey` but without keeping a table
+    pub(crate) fn basepoint_minus_key(&self, s: &Scalar, k: &Scalar) -> ExtendedPoint {
+        let mut s_div_four = *s;
+        s_div_four.div_by_four();
+        let mut k_div_four = *k;
+        k_div_four.div_by_four();
+
+        // The dual isogeny multiplies by four
+        let neg_A = self.point.negate();
+        let partial_result =
+            double_base_scalar_mul(&k_div_four, &neg_A.to_twisted(), &s_div_four).to_untwisted();
+
+        partial_result + ExtendedPoint::generator().scalar_mod_four(s) + neg_A.scalar_mod_four(k)
+    }
+}
+
+impl TryFrom<&[u8; PUBLIC_KEY_LENGTH]> for VerifyingKey {
+    type Error = SignatureError;
+
+    fn try_from(bytes: &[u8; PUBLIC_KEY_LENGTH]) -> Result<VerifyingKey, SignatureError> {
+        VerifyingKey::from_bytes(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::BASEPOINT_ORDER;
+    use crate::curve::edwards::affine::AffinePoint;
+    use crate::ed448::SigningKey;
+    use crate::field::FieldElement;
+
+    /// Signs with secret scalar `a` and nonce `r`, hashing the encodings of `A` and `R` as given
+    fn sign_raw(
+        a: &Scalar,
+        A: &CompressedEdwardsY,
+        r: &Scalar,
+        R: CompressedEdwardsY,
+        message: &[u8],
+    ) -> Signature {
+        let k = hash_to_scalar(0, &[], &[R.as_bytes(), A.as_bytes(), message]);
+        let s = *r + k * *a;
+        Signature {
+            R,
+            s: s.to_bytes_rfc_8032(),
+        }
+    }
+
+    /// Sets one of the unused bits of the last byte
+    fn non_canonical(compressed: CompressedEdwardsY) -> CompressedEdwardsY {
+        let mut bytes = compressed.0;
+        bytes[56] |= 0x01;
+        CompressedEdwardsY(bytes)
+    }
+
+    #[test]
+    fn test_verification_policy_corpus() {
+        use VerificationPolicy::{Permissive, Strict};
+
+        let signing_key = SigningKey::from_seed(&[42; 57]);
+        let a = signing_key.scalar;
+        let A = signing_key.verifying_key();
+        let r = Scalar::from(0xdeadbeef);
+        let rB = ExtendedPoint::generator().scalar_mul(&r);
+        let torsion = AffinePoint {
+            x: FieldElement::one(),
+            y: FieldElement::zero(),
+        }
+        .to_extended();
+        let message: &[u8] = b"edge cases";
+
+        // The key with the encoding of the identity, for which any S = r gives a valid signature
+        let identity =
+            VerifyingKey::from_bytes(ExtendedPoint::identity().compress().as_bytes()).unwrap();
+        // A with a non-canonical encoding
+        let non_canonical_A =
+            VerifyingKey::from_bytes(non_canonical(A.compressed).as_bytes()).unwrap();
+
+        // S + ℓ, which is still less than 2^456
+        let unreduced = {
+            let signature = signing_key.sign(message);
+            let order = BASEPOINT_ORDER.to_bytes_rfc_8032();
+            let mut carry = 0u16;
+            let mut s = [0u8; 57];
+            for i in 0..57 {
+                let sum = signature.s[i] as u16 + order[i] as u16 + carry;
+                s[i] = sum as u8;
+                carry = sum >> 8;
+            }
+            Signature { s, ..signature }
+        };
+
+        // (key, signature, expected under Strict, expected under Permissive)
+        let corpus = [
+            // An honest signature
+            (A, signing_key.sign(message), Ok(()), Ok(())),
+            // A small-order key
+            (
+                identity,
+                sign_raw(
+                    &Scalar::zero(),
+                    &identity.compressed,
+                    &r,
+                    rB.compress(),
+                    message,
+                ),
+                Err(SignatureError::InvalidPublicKey),
+                Ok(()),
+            ),
+            // A non-canonical encoding of the key
+            (
+                non_canonical_A,
+                sign_raw(&a, &non_canonical_A.compressed, &r, rB.compress(), message),
+                Err(SignatureError::InvalidPublicKey),
+                Ok(()),
+            ),
+            // A non-canonical encoding of R
+            (
+                A,
+                sign_raw(&a, &A.compressed, &r, non_canonical(rB.compress()), message),
+                Err(SignatureError::InvalidSignature),
+                Ok(()),
+            ),
+            // R is the identity
+            (
+                A,
+                sign_raw(
+                    &a,
+                    &A.compressed,
+                    &Scalar::zero(),
+                    ExtendedPoint::identity().compress(),
+                    message,
+                ),
+                Err(SignatureError::InvalidSignature),
+                Ok(()),
+            ),
+            // R is of small order
+            (
+                A,
+                sign_raw(
+                    &a,
+                    &A.compressed,
+                    &Scalar::zero(),
+                    torsion.compress(),
+                    message,
+                ),
+                Err(SignatureError::InvalidSignature),
+                Ok(()),
+            ),
+            // R has a small-order component, which only the cofactored equation ignores
+            (
+                A,
+                sign_raw(&a, &A.compressed, &r, (rB + torsion).compress(), message),
+                Err(SignatureError::VerificationFailed),
+                Ok(()),
+            ),
+            // S is not reduced
+            (
+                A,
+                unreduced,
+                Err(SignatureError::InvalidSignature),
+                Err(SignatureError::InvalidSignature),
+            ),
+        ];
+
+        for (key, signature, strict, permissive) in corpus.iter() {
+            assert_eq!(key.verify_strict(message, signature), *strict);
+            assert_eq!(
+                key.verify_with_policy(Strict, &[], message, signature),
+                *strict
+            );
+            assert_eq!(key.verify(message, signature), *permissive);
+            assert_eq!(
+                key.verify_with_policy(Permissive, &[], message, signature),
+                *permissive
+            );
+        }
+    }
+}
@@ -71,6 +71,10 @@ pub const DECAF_FACTOR: FieldElement56 = FieldElement56(fiat_p448_tight_field_el
     0x22d962fbeb24f7,
 ]));
 
+/// 156326, the coefficient A of Curve448: v^2 = u^3 + A u^2 + u
+pub const MONTGOMERY_A: FieldElement56 =
+    FieldElement56(fiat_p448_tight_field_element([156326, 0, 0, 0, 0, 0, 0, 0]));
+
 /// 39082 used in the doubling procedure in montgomery ladder
 pub const A_PLUS_TWO_OVER_FOUR: FieldElement56 =
     FieldElement56(fiat_p448_tight_field_element([39082, 0, 0, 0, 0, 0, 0, 0]));
@@ -33,6 +33,12 @@ impl FieldElement {
     pub(crate) fn is_zero(&self) -> Choice {
         self.ct_eq(&FieldElement::zero())
     }
+    /// Checks if a field element is a square, i.e. if its Legendre symbol is 0 or 1
+    pub(crate) fn is_square(&self) -> Choice {
+        // The residue flag is x^((p-1)/2) == 1, which is false for zero
+        let (_, is_residue) = self.inverse_square_root();
+        is_residue | self.is_zero()
+    }
     /// Inverts a field element
     /// Previous chain length: 462, new length 460
     pub fn invert(&self) -> FieldElement {
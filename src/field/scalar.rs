@@ -2,7 +2,9 @@ use std::ops::{Add, Index, IndexMut, Mul, Sub};
 
 #[cfg(feature = "rand")]
 use rand_core::{CryptoRng, RngCore};
-use subtle::{Choice, ConstantTimeEq};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 use crate::constants;
 
@@ -10,6 +12,7 @@ use crate::constants;
 /// size = 4q = 2^446 - 0x8335dc163bb124b65129c96fde933d8d723a70aadc873d6d54a7bb0d
 /// We can therefore use 14 saturated 32-bit limbs
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "zeroize", derive(Zeroize))]
 pub struct Scalar(pub(crate) [u32; 14]);
 
 pub(crate) const MODULUS: Scalar = constants::BASEPOINT_ORDER;
@@ -37,6 +40,14 @@ impl PartialEq for Scalar {
 }
 impl Eq for Scalar {}
 
+impl ConditionallySelectable for Scalar {
+    fn conditional_select(a: &Scalar, b: &Scalar, choice: Choice) -> Scalar {
+        Scalar(std::array::from_fn(|i| {
+            u32::conditional_select(&a.0[i], &b.0[i], choice)
+        }))
+    }
+}
+
 impl From<u32> for Scalar {
     fn from(a: u32) -> Scalar {
         Scalar([a, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0])
@@ -131,6 +142,66 @@ impl Scalar {
 
         output
     }
+
+    // This method was modified from Curve25519-Dalek codebase. [scalar.rs]
+    /// Computes the width-`w` non-adjacent form of this scalar, for 2 <= w <= 8
+    ///
+    /// Every non-zero digit is odd and less than 2^(w-1) in absolute value, and any `w`
+    /// consecutive digits contain at most one non-zero digit.
+    /// The scalar must be reduced, so that the recoding fits into 448 digits.
+    ///
+    /// N.B. This does not run in constant time, only use it for public scalars.
+    pub(crate) fn non_adjacent_form(&self, w: usize) -> [i8; 448] {
+        debug_assert!((2..=8).contains(&w));
+
+        let mut naf = [0i8; 448];
+
+        // Load the scalar into 64-bit words, with an extra zero word to read past the end
+        let bytes = self.to_bytes();
+        let mut x = [0u64; 8];
+        for (i, chunk) in bytes.chunks(8).enumerate() {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(chunk);
+            x[i] = u64::from_le_bytes(word);
+        }
+
+        let width = 1u64 << w;
+        let window_mask = width - 1;
+
+        let mut pos = 0;
+        let mut carry = 0;
+        while pos < 448 {
+            // Construct a buffer of bits of the scalar, starting at bit `pos`
+            let u64_idx = pos / 64;
+            let bit_idx = pos % 64;
+            let bit_buf = if bit_idx < 64 - w {
+                x[u64_idx] >> bit_idx
+            } else {
+                (x[u64_idx] >> bit_idx) | (x[u64_idx + 1] << (64 - bit_idx))
+            };
+
+            // Add the carry into the current window
+            let window = carry + (bit_buf & window_mask);
+
+            if window & 1 == 0 {
+                // If the window value is even, move on to the next bit
+                pos += 1;
+                continue;
+            }
+
+            if window < width / 2 {
+                carry = 0;
+                naf[pos] = window as i8;
+            } else {
+                carry = 1;
+                naf[pos] = (window as i8).wrapping_sub(width as i8);
+            }
+
+            pos += w;
+        }
+
+        naf
+    }
     // XXX: Better if this method returns an array of 448 items
     pub fn bits(&self) -> Vec<bool> {
         let mut bits: Vec<bool> = Vec::with_capacity(14 * 32);
@@ -437,6 +508,41 @@ mod test {
     use hex_literal::hex;
     use super::*;
 
+    #[test]
+    fn test_non_adjacent_form() {
+        let scalar = Scalar([
+            0x6ee372b7, 0xe128ae78, 0x1533427c, 0xad0b7015, 0x307f665e, 0xde8026c1, 0xb64629d1,
+            0xab454c66, 0x3fe5bf1a, 0x083f8304, 0x3c003777, 0xdef437f6, 0xee2e1b73, 0x05ca185a,
+        ]);
+        let largest = Scalar::zero() - Scalar::one();
+
+        for s in [scalar, largest, Scalar::zero(), Scalar::from(255)] {
+            for w in 2..=8 {
+                let naf = s.non_adjacent_form(w);
+
+                // Recompute the scalar with Horner's rule
+                let mut recomputed = Scalar::zero();
+                for digit in naf.iter().rev() {
+                    recomputed = recomputed + recomputed;
+                    let abs = Scalar::from(digit.unsigned_abs() as u32);
+                    recomputed = if *digit < 0 {
+                        recomputed - abs
+                    } else {
+                        recomputed + abs
+                    };
+                }
+                assert_eq!(recomputed, s);
+
+                for (i, digit) in naf.iter().enumerate() {
+                    if *digit != 0 {
+                        assert_eq!(digit & 1, 1);
+                        assert!((digit.unsigned_abs() as u32) < 1 << (w - 1));
+                        assert!(naf[i + 1..].iter().take(w - 1).all(|d| *d == 0));
+                    }
+                }
+            }
+        }
+    }
     #[test]
     fn test_basic_add() {
         let five = Scalar::from(5);
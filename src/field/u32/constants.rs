@@ -39,6 +39,10 @@ pub const DECAF_FACTOR: FieldElement28 = FieldElement28([
     0x05572736, 0x042ef0f4, 0x00ce5296, 0x07bf6aa2, 0x0ed26033, 0x0f4fd6ed, 0x0a839a66, 0x0968c14b,
     0x04a2d780, 0x0b8d54b6, 0x01a7b8a5, 0x06aa0a1f, 0x0d722fa2, 0x0683bf68, 0x0beb24f7, 0x022d962f,
 ]);
+/// 156326, the coefficient A of Curve448: v^2 = u^3 + A u^2 + u
+pub const MONTGOMERY_A: FieldElement28 =
+    FieldElement28([156326, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
 /// 39082 used in the doubling procedure in montgomery ladder
 pub const A_PLUS_TWO_OVER_FOUR: FieldElement28 =
     FieldElement28([39082, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
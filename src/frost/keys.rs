@@ -0,0 +1,311 @@
+use super::{FrostError, Identifier};
+use crate::curve::edwards::ExtendedPoint;
+use crate::ed448::VerifyingKey;
+use crate::field::Scalar;
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The commitments [a_0]B, ..., [a_{t-1}]B to the coefficients of the dealer's polynomial
+///
+/// Every participant checks their share against it, and [a_0]B is the group public key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifiableSecretSharingCommitment(Vec<ExtendedPoint>);
+
+impl VerifiableSecretSharingCommitment {
+    /// The commitments to the coefficients, starting with the constant term
+    pub fn points(&self) -> &[ExtendedPoint] {
+        &self.0
+    }
+
+    /// The group public key
+    pub fn verifying_key(&self) -> VerifyingKey {
+        VerifyingKey::from_point(self.0[0])
+    }
+
+    /// The number of signers needed to sign
+    pub fn min_signers(&self) -> u16 {
+        self.0.len() as u16
+    }
+
+    /// Computes the public share of `identifier`, which is Σ [i^j]C_j
+    fn evaluate(&self, identifier: &Identifier) -> ExtendedPoint {
+        self.0
+            .iter()
+            .rev()
+            .fold(ExtendedPoint::identity(), |sum, commitment| {
+                sum.scalar_mul(&identifier.0) + commitment
+            })
+    }
+}
+
+/// The share of the group secret key that the dealer sends to one participant
+#[derive(Clone)]
+pub struct SecretShare {
+    identifier: Identifier,
+    value: Scalar,
+    commitment: VerifiableSecretSharingCommitment,
+}
+
+impl fmt::Debug for SecretShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretShare")
+            .field("identifier", &self.identifier)
+            .field("commitment", &self.commitment)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretShare {
+    fn drop(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl SecretShare {
+    /// The identifier of the participant this share belongs to
+    pub fn identifier(&self) -> Identifier {
+        self.identifier
+    }
+
+    /// The share of the group secret key
+    pub fn value(&self) -> &Scalar {
+        &self.value
+    }
+
+    /// The commitment of the dealer that the share can be checked against
+    pub fn commitment(&self) -> &VerifiableSecretSharingCommitment {
+        &self.commitment
+    }
+}
+
+/// The key material that a participant needs to sign
+#[derive(Clone)]
+pub struct KeyPackage {
+    identifier: Identifier,
+    signing_share: Scalar,
+    verifying_share: ExtendedPoint,
+    verifying_key: VerifyingKey,
+    min_signers: u16,
+}
+
+impl fmt::Debug for KeyPackage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyPackage")
+            .field("identifier", &self.identifier)
+            .field("verifying_key", &self.verifying_key)
+            .field("min_signers", &self.min_signers)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for KeyPackage {
+    fn drop(&mut self) {
+        self.signing_share.zeroize();
+    }
+}
+
+impl TryFrom<&SecretShare> for KeyPackage {
+    type Error = FrostError;
+
+    /// Checks the share against the dealer's commitment, see vss_verify in RFC 9591 Appendix C.2
+    fn try_from(share: &SecretShare) -> Result<KeyPackage, FrostError> {
+        let verifying_share = ExtendedPoint::generator().scalar_mul(&share.value);
+        if verifying_share != share.commitment.evaluate(&share.identifier) {
+            return Err(FrostError::InvalidSecretShare);
+        }
+
+        Ok(KeyPackage {
+            identifier: share.identifier,
+            signing_share: share.value,
+            verifying_share,
+            verifying_key: share.commitment.verifying_key(),
+            min_signers: share.commitment.min_signers(),
+        })
+    }
+}
+
+impl KeyPackage {
+    /// The identifier of this participant
+    pub fn identifier(&self) -> Identifier {
+        self.identifier
+    }
+
+    /// The share of the group secret key
+    pub fn signing_share(&self) -> &Scalar {
+        &self.signing_share
+    }
+
+    /// The public key of the share, which is used to check signature shares
+    pub fn verifying_share(&self) -> ExtendedPoint {
+        self.verifying_share
+    }
+
+    /// The group public key
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.verifying_key
+    }
+
+    /// The number of signers needed to sign
+    pub fn min_signers(&self) -> u16 {
+        self.min_signers
+    }
+}
+
+/// The public keys of the group and of every share, which the coordinator needs to aggregate
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublicKeyPackage {
+    verifying_shares: BTreeMap<Identifier, ExtendedPoint>,
+    verifying_key: VerifyingKey,
+}
+
+impl PublicKeyPackage {
+    /// Creates a public key package from the public keys of the shares and the group public key
+    pub fn new(
+        verifying_shares: BTreeMap<Identifier, ExtendedPoint>,
+        verifying_key: VerifyingKey,
+    ) -> PublicKeyPackage {
+        PublicKeyPackage {
+            verifying_shares,
+            verifying_key,
+        }
+    }
+
+    /// The public keys of the shares
+    pub fn verifying_shares(&self) -> &BTreeMap<Identifier, ExtendedPoint> {
+        &self.verifying_shares
+    }
+
+    /// The group public key
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.verifying_key
+    }
+}
+
+/// Splits `secret` into `max_signers` shares, any `coefficients.len() + 1` of which can sign
+/// See secret_share_shard in RFC 9591 Appendix C.1
+///
+/// The coefficients of the polynomial must be uniformly random and kept secret.
+/// The participants are given the identifiers 1 to `max_signers`.
+pub fn split(
+    secret: &Scalar,
+    coefficients: &[Scalar],
+    max_signers: u16,
+) -> Result<(Vec<SecretShare>, PublicKeyPackage), FrostError> {
+    let min_signers = coefficients.len() + 1;
+    if min_signers < 2 || min_signers > max_signers as usize {
+        return Err(FrostError::InvalidThreshold);
+    }
+
+    let mut polynomial = Vec::with_capacity(min_signers);
+    polynomial.push(*secret);
+    polynomial.extend_from_slice(coefficients);
+
+    let commitment = VerifiableSecretSharingCommitment(
+        polynomial
+            .iter()
+            .map(|a| ExtendedPoint::generator().scalar_mul(a))
+            .collect(),
+    );
+
+    let shares: Vec<SecretShare> = (1..=max_signers)
+        .map(|i| {
+            let identifier = Identifier::new(i)?;
+            // Horner's method
+            let value = polynomial
+                .iter()
+                .rev()
+                .fold(Scalar::zero(), |sum, a| sum * identifier.0 + *a);
+            Ok(SecretShare {
+                identifier,
+                value,
+                commitment: commitment.clone(),
+            })
+        })
+        .collect::<Result<_, FrostError>>()?;
+
+    let verifying_shares = shares
+        .iter()
+        .map(|share| {
+            (
+                share.identifier,
+                ExtendedPoint::generator().scalar_mul(&share.value),
+            )
+        })
+        .collect();
+
+    #[cfg(feature = "zeroize")]
+    polynomial.iter_mut().for_each(Zeroize::zeroize);
+
+    Ok((
+        shares,
+        PublicKeyPackage::new(verifying_shares, commitment.verifying_key()),
+    ))
+}
+
+/// Generates a group key as a trusted dealer, and splits it into `max_signers` shares,
+/// any `min_signers` of which can sign
+/// See trusted_dealer_keygen in RFC 9591 Appendix C
+#[cfg(feature = "rand")]
+pub fn generate_with_dealer<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    max_signers: u16,
+    min_signers: u16,
+) -> Result<(Vec<SecretShare>, PublicKeyPackage), FrostError> {
+    if min_signers < 2 {
+        return Err(FrostError::InvalidThreshold);
+    }
+    let secret = Scalar::random(rng);
+    let coefficients: Vec<Scalar> = (1..min_signers).map(|_| Scalar::random(rng)).collect();
+    split(&secret, &coefficients, max_signers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let secret = Scalar::from(42);
+        let (shares, public_key_package) =
+            split(&secret, &[Scalar::from(3), Scalar::from(5)], 4).unwrap();
+        assert_eq!(shares.len(), 4);
+        assert_eq!(
+            public_key_package.verifying_key().to_edwards(),
+            ExtendedPoint::generator().scalar_mul(&secret)
+        );
+
+        // f(x) = 42 + 3x + 5x^2
+        assert_eq!(*shares[1].value(), Scalar::from(42 + 6 + 20));
+
+        // A tampered share is detected
+        let mut share = shares[2].clone();
+        share.value = share.value + Scalar::one();
+        assert_eq!(
+            KeyPackage::try_from(&share).unwrap_err(),
+            FrostError::InvalidSecretShare
+        );
+
+        let key_package = KeyPackage::try_from(&shares[2]).unwrap();
+        assert_eq!(key_package.min_signers(), 3);
+        assert_eq!(
+            key_package.verifying_share(),
+            public_key_package.verifying_shares()[&key_package.identifier()]
+        );
+
+        assert_eq!(
+            split(&secret, &[], 3).unwrap_err(),
+            FrostError::InvalidThreshold
+        );
+        assert_eq!(
+            split(&secret, &[Scalar::one(); 3], 3).unwrap_err(),
+            FrostError::InvalidThreshold
+        );
+    }
+}
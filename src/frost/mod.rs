@@ -0,0 +1,603 @@
+// This is the module for FROST threshold signatures, with the FROST(Ed448, SHAKE256) ciphersuite
+// of RFC 9591 (https://www.rfc-editor.org/rfc/rfc9591)
+//
+// A trusted dealer splits the group secret key into n shares with Shamir secret sharing, and any
+// t of the participants can then sign together in two rounds:
+//
+// 1. Every signer commits to a hiding and a binding nonce (`round1`)
+// 2. A coordinator collects the commitments and the message into a `SigningPackage`, every signer
+//    answers with a signature share (`round2`) and the coordinator aggregates the shares
+//
+// The result is an ordinary RFC 8032 Ed448 signature (with an empty context) under the group
+// public key, which verifies with `ed448::VerifyingKey`.
+
+mod keys;
+pub mod round1;
+pub mod round2;
+
+#[cfg(feature = "rand")]
+pub use keys::generate_with_dealer;
+pub use keys::{
+    split, KeyPackage, PublicKeyPackage, SecretShare, VerifiableSecretSharingCommitment,
+};
+
+use crate::curve::edwards::{CompressedEdwardsY, ExtendedPoint};
+use crate::ed448::{hash_to_scalar, hash_wide, Signature, VerifyingKey};
+use crate::field::Scalar;
+use round1::SigningCommitments;
+use round2::SignatureShare;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The context string of the ciphersuite, which prefixes every hash apart from the challenge
+const CONTEXT_STRING: &[u8] = b"FROST-ED448-SHAKE256-v1";
+
+/// The length of an encoded identifier, in bytes
+pub const IDENTIFIER_LENGTH: usize = 57;
+
+/// Errors which can occur during key generation, signing or aggregation
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrostError {
+    /// The identifier is zero or not a canonical scalar
+    InvalidIdentifier,
+    /// The threshold is less than two, or larger than the number of participants
+    InvalidThreshold,
+    /// The secret share does not match the commitment of the dealer
+    InvalidSecretShare,
+    /// A point is not the canonical encoding of an element of the prime-order subgroup,
+    /// or it is the identity
+    InvalidElement,
+    /// A scalar is not reduced modulo ℓ
+    InvalidScalar,
+    /// The signing package has fewer commitments than the threshold
+    NotEnoughSigners,
+    /// The signing package does not contain the commitment of this signer, or it contains a different one
+    MissingCommitment,
+    /// The signature shares do not belong to the signers in the signing package
+    UnknownSigner,
+    /// The signature share of `culprit` is invalid
+    InvalidSignatureShare {
+        /// The signer who sent the invalid share
+        culprit: Identifier,
+    },
+    /// The aggregated signature does not verify
+    InvalidSignature,
+}
+
+impl fmt::Display for FrostError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrostError::InvalidIdentifier => write!(f, "invalid identifier"),
+            FrostError::InvalidThreshold => write!(f, "invalid threshold"),
+            FrostError::InvalidSecretShare => {
+                write!(f, "secret share does not match the dealer's commitment")
+            }
+            FrostError::InvalidElement => write!(f, "invalid group element"),
+            FrostError::InvalidScalar => write!(f, "invalid scalar"),
+            FrostError::NotEnoughSigners => write!(f, "fewer signers than the threshold"),
+            FrostError::MissingCommitment => write!(f, "signer's commitment is missing"),
+            FrostError::UnknownSigner => write!(f, "signature share from an unknown signer"),
+            FrostError::InvalidSignatureShare { culprit } => {
+                write!(f, "invalid signature share from signer {:?}", culprit)
+            }
+            FrostError::InvalidSignature => write!(f, "aggregated signature is invalid"),
+        }
+    }
+}
+
+impl std::error::Error for FrostError {}
+
+/// The identifier of a participant, which is a non-zero scalar
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Identifier(Scalar);
+
+impl Identifier {
+    /// Creates the identifier `i`, which must not be zero
+    pub fn new(i: u16) -> Result<Identifier, FrostError> {
+        if i == 0 {
+            return Err(FrostError::InvalidIdentifier);
+        }
+        Ok(Identifier(Scalar::from(i as u32)))
+    }
+
+    /// Encodes the identifier as a 57 byte little-endian scalar
+    pub fn to_bytes(&self) -> [u8; IDENTIFIER_LENGTH] {
+        self.0.to_bytes_rfc_8032()
+    }
+
+    /// Parses an identifier, which must be a canonical non-zero scalar
+    pub fn from_bytes(bytes: &[u8; IDENTIFIER_LENGTH]) -> Result<Identifier, FrostError> {
+        let scalar = Scalar::from_canonical_bytes(*bytes).ok_or(FrostError::InvalidIdentifier)?;
+        if scalar == Scalar::zero() {
+            return Err(FrostError::InvalidIdentifier);
+        }
+        Ok(Identifier(scalar))
+    }
+}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Identifier) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Identifiers are ordered by their value, which is the order of the commitment list
+impl Ord for Identifier {
+    fn cmp(&self, other: &Identifier) -> Ordering {
+        let mut a = self.to_bytes();
+        let mut b = other.to_bytes();
+        a.reverse();
+        b.reverse();
+        a.cmp(&b)
+    }
+}
+
+/// Everything the signers need to agree on for the second round: the commitments and the message
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SigningPackage {
+    commitments: BTreeMap<Identifier, SigningCommitments>,
+    message: Vec<u8>,
+}
+
+impl SigningPackage {
+    /// Creates a signing package from the commitments of the participating signers
+    pub fn new(
+        commitments: BTreeMap<Identifier, SigningCommitments>,
+        message: &[u8],
+    ) -> SigningPackage {
+        SigningPackage {
+            commitments,
+            message: message.to_vec(),
+        }
+    }
+
+    /// The commitments of the participating signers, sorted by identifier
+    pub fn commitments(&self) -> &BTreeMap<Identifier, SigningCommitments> {
+        &self.commitments
+    }
+
+    /// The message to sign
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// The participating signers
+    fn signers(&self) -> Vec<Identifier> {
+        self.commitments.keys().copied().collect()
+    }
+
+    /// encode_group_commitment_list, see RFC 9591 Section 4.3
+    fn encode_commitments(&self) -> Vec<u8> {
+        let mut encoded = Vec::with_capacity(self.commitments.len() * 3 * 57);
+        for (identifier, commitments) in self.commitments.iter() {
+            encoded.extend_from_slice(&identifier.to_bytes());
+            encoded.extend_from_slice(&commitments.to_bytes());
+        }
+        encoded
+    }
+
+    /// compute_binding_factors, see RFC 9591 Section 4.4
+    fn binding_factors(&self, verifying_key: &VerifyingKey) -> BTreeMap<Identifier, Scalar> {
+        let message_hash = h4(&self.message);
+        let commitments_hash = h5(&self.encode_commitments());
+
+        self.commitments
+            .keys()
+            .map(|identifier| {
+                let binding_factor = h1(&[
+                    verifying_key.as_bytes(),
+                    &message_hash,
+                    &commitments_hash,
+                    &identifier.to_bytes(),
+                ]);
+                (*identifier, binding_factor)
+            })
+            .collect()
+    }
+
+    /// Computes the binding factors, the group commitment R and the challenge of this signing session
+    /// See RFC 9591 Sections 4.4, 4.5 and 4.6
+    fn session(
+        &self,
+        verifying_key: &VerifyingKey,
+    ) -> (BTreeMap<Identifier, Scalar>, CompressedEdwardsY, Scalar) {
+        let binding_factors = self.binding_factors(verifying_key);
+
+        let group_commitment = self.commitments.iter().fold(
+            ExtendedPoint::identity(),
+            |sum, (identifier, commitments)| {
+                sum + commitments.hiding
+                    + commitments.binding.scalar_mul(&binding_factors[identifier])
+            },
+        );
+        let R = group_commitment.compress();
+
+        let challenge = h2(&[R.as_bytes(), verifying_key.as_bytes(), &self.message]);
+
+        (binding_factors, R, challenge)
+    }
+}
+
+/// Aggregates the signature shares of the signers into an Ed448 signature
+/// See RFC 9591 Section 5.3
+///
+/// If the signature does not verify, every share is checked to find the signer who cheated.
+pub fn aggregate(
+    signing_package: &SigningPackage,
+    signature_shares: &BTreeMap<Identifier, SignatureShare>,
+    public_key_package: &PublicKeyPackage,
+) -> Result<Signature, FrostError> {
+    if signature_shares.len() != signing_package.commitments.len()
+        || signature_shares
+            .keys()
+            .any(|identifier| !signing_package.commitments.contains_key(identifier))
+    {
+        return Err(FrostError::UnknownSigner);
+    }
+
+    let verifying_key = public_key_package.verifying_key();
+    let (_, R, _) = signing_package.session(&verifying_key);
+
+    let z = signature_shares
+        .values()
+        .fold(Scalar::zero(), |sum, share| sum + share.0);
+    let signature = Signature {
+        R,
+        s: z.to_bytes_rfc_8032(),
+    };
+
+    if verifying_key
+        .verify_strict(&signing_package.message, &signature)
+        .is_ok()
+    {
+        return Ok(signature);
+    }
+
+    // Find the culprit, see RFC 9591 Section 5.4
+    for (identifier, share) in signature_shares.iter() {
+        let verifying_share = public_key_package
+            .verifying_shares()
+            .get(identifier)
+            .ok_or(FrostError::UnknownSigner)?;
+        round2::verify_signature_share(
+            identifier,
+            verifying_share,
+            share,
+            signing_package,
+            &verifying_key,
+        )?;
+    }
+    Err(FrostError::InvalidSignature)
+}
+
+/// Computes the Lagrange coefficient of `identifier` for interpolating at zero from `signers`
+/// See derive_interpolating_value in RFC 9591 Section 4.2
+fn lagrange_coefficient(identifier: &Identifier, signers: &[Identifier]) -> Scalar {
+    let mut numerator = Scalar::one();
+    let mut denominator = Scalar::one();
+    for signer in signers.iter().filter(|signer| *signer != identifier) {
+        numerator = numerator * signer.0;
+        denominator = denominator * (signer.0 - identifier.0);
+    }
+    numerator * denominator.invert()
+}
+
+/// Encodes an element, see SerializeElement in RFC 9591 Section 6.3
+fn encode_element(point: &ExtendedPoint) -> [u8; 57] {
+    point.compress().0
+}
+
+/// Decodes an element, see DeserializeElement in RFC 9591 Section 6.3
+///
+/// The encoding must be canonical, and the point must be a non-identity element of the prime-order subgroup.
+fn decode_element(bytes: &[u8; 57]) -> Result<ExtendedPoint, FrostError> {
+    let point = CompressedEdwardsY(*bytes)
+        .decompress_strict()
+        .ok_or(FrostError::InvalidElement)?;
+    if point == ExtendedPoint::identity() || !point.is_torsion_free() {
+        return Err(FrostError::InvalidElement);
+    }
+    Ok(point)
+}
+
+/// Decodes a scalar, see DeserializeScalar in RFC 9591 Section 6.3
+fn decode_scalar(bytes: &[u8; 57]) -> Result<Scalar, FrostError> {
+    Scalar::from_canonical_bytes(*bytes).ok_or(FrostError::InvalidScalar)
+}
+
+/// H1, which derives the binding factors
+fn h1(parts: &[&[u8]]) -> Scalar {
+    let mut input: Vec<&[u8]> = vec![CONTEXT_STRING, b"rho"];
+    input.extend_from_slice(parts);
+    Scalar::from_bytes_mod_order_wide(&hash_wide(&input))
+}
+
+/// H2, which is the RFC 8032 challenge with an empty context
+fn h2(parts: &[&[u8]]) -> Scalar {
+    hash_to_scalar(0, &[], parts)
+}
+
+/// H3, which derives the nonces
+fn h3(parts: &[&[u8]]) -> Scalar {
+    let mut input: Vec<&[u8]> = vec![CONTEXT_STRING, b"nonce"];
+    input.extend_from_slice(parts);
+    Scalar::from_bytes_mod_order_wide(&hash_wide(&input))
+}
+
+/// H4, which hashes the message
+fn h4(message: &[u8]) -> [u8; 114] {
+    hash_wide(&[CONTEXT_STRING, b"msg", message])
+}
+
+/// H5, which hashes the encoded commitment list
+fn h5(commitments: &[u8]) -> [u8; 114] {
+    hash_wide(&[CONTEXT_STRING, b"com", commitments])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frost::round1::SigningNonces;
+    use hex_literal::hex;
+
+    // RFC 9591 Appendix E.2, FROST(Ed448, SHAKE256)
+    const GROUP_SECRET_KEY: [u8; 57] = hex!("6298e1eef3c379392caaed061ed8a31033c9e9e3420726f23b404158a401cd9df24632adfe6b418dc942d8a091817dd8bd70e1c72ba52f3c00");
+    const GROUP_PUBLIC_KEY: [u8; 57] = hex!("3832f82fda00ff5365b0376df705675b63d2a93c24c6e81d40801ba265632be10f443f95968fadb70d10786827f30dc001c8d0f9b7c1d1b000");
+    const MESSAGE: &[u8] = &hex!("74657374");
+    const SHARE_POLYNOMIAL_COEFFICIENT: [u8; 57] = hex!("dbd7a514f7a731976620f0436bd135fe8dddc3fadd6e0d13dbd58a1981e587d377d48e0b7ce4e0092967c5e85884d0275a7a740b6abdcd0500");
+    const PARTICIPANT_SHARES: [[u8; 57]; 3] = [
+        hex!("4a2b2f5858a932ad3d3b18bd16e76ced3070d72fd79ae4402df201f525e754716a1bc1b87a502297f2a99d89ea054e0018eb55d39562fd0100"),
+        hex!("2503d56c4f516444a45b080182b8a2ebbe4d9b2ab509f25308c88c0ea7ccdc44e2ef4fc4f63403a11b116372438a1e287265cadeff1fcb0700"),
+        hex!("00db7a8146f995db0a7cf844ed89d8e94c2b5f259378ff66e39d172828b264185ac4decf7219e4aa4478285b9c0eef4fccdf3eea69dd980d00"),
+    ];
+
+    struct RoundVector {
+        identifier: u16,
+        hiding_nonce_randomness: [u8; 32],
+        binding_nonce_randomness: [u8; 32],
+        hiding_nonce: [u8; 57],
+        binding_nonce: [u8; 57],
+        hiding_nonce_commitment: [u8; 57],
+        binding_nonce_commitment: [u8; 57],
+        binding_factor: [u8; 57],
+        sig_share: [u8; 57],
+    }
+
+    const ROUND_VECTORS: [RoundVector; 2] = [
+        RoundVector {
+            identifier: 1,
+            hiding_nonce_randomness: hex!("3d9e765ab0f06bc3131acc2f7642223255fd5295f3c04bef5c644c0bae4f85be"),
+            binding_nonce_randomness: hex!("86160f8224ea273128b0ea5af828a9c0b0985cd9b52a3b8b668ae9d3205a6672"),
+            hiding_nonce: hex!("d9610abd59ac2e9b33a3919acf28f5c895918389c69a26a3fcfe6a70edab4685e9ea3d3641d8bfe1634616fafd457b2ef6503c51f191991300"),
+            binding_nonce: hex!("1caf737f1e73b5ab7f155c239ea70fb5783cd8da454298984a325cb4eb968b0e625936cbb9623e57919b3cc01be40f7f45b6c37fd947772b00"),
+            hiding_nonce_commitment: hex!("2afe1a6d56ee111e7e4b84060c07044643434362edbce8f79f568236884c1c16883e9a4d3f42029f324619b32587d501609941bab590682000"),
+            binding_nonce_commitment: hex!("605d2b3b5af4159ca3fa23e4463a3573d4144b43c7b01322d621817c80fcd84f874ea79655648ca65f128a4cfbec236c0c89f3898b27529180"),
+            binding_factor: hex!("02a61d347e83fc93eaab248c409eaea947cb6d2166b02d19c0d3760031c064b5f2f9354f301e274ac3a6ee000c91136dedfb4f98ffc50a3700"),
+            sig_share: hex!("0dad0c550bade2576c6cbe1d7b57a55b00e57a6e3683c8f84fbfa48751745bb0b2b89624e7d1b443b1ff62ffde9ee0c15e5df678c4ed1c0400"),
+        },
+        RoundVector {
+            identifier: 3,
+            hiding_nonce_randomness: hex!("8cba373b6c3a3601d5cfa3d37cbc93f310d253a2fcf886695bb59ac703d8a100"),
+            binding_nonce_randomness: hex!("1a84c88d88fbb99a4417157487b45367eb91d7dd2ba55566d6784f39f750a2f7"),
+            hiding_nonce: hex!("3a8f5b86cf6e80d3a2d99ac5628a1229eaa9dd205b3b3bdd3bc8d2270c749d08836379c8b50d964f492ba8785f3deb571e0b4e7db887be1400"),
+            binding_nonce: hex!("4b529518068f9b261d56cffced7f753b9b22db6a79c8c495584ec67edc561da644b70f9af42e633e14bc145b3ea61c5d2bf30090c3700b2f00"),
+            hiding_nonce_commitment: hex!("3f06fbce6ca0f92331e97946e27c649e9fdb96f1ba1061189495bcd6d019e7915de818c3901b9d5d0e2de062ddeca7a40bbd84c9ffbc983900"),
+            binding_nonce_commitment: hex!("ef2f07a309be3c8936c505b385dee51f319ebb9bf26520ed5579c9b3ede6467968d969fc3c7b34d704b86547e8ae11dcdae9fdc0794e642380"),
+            binding_factor: hex!("14e0cfb824bd2003099f3ebb2fe00e95355eab38098a5619acc62a8faa2092ce25cdc061a7b51b6ed41e291ed311a2909d2ce8c3acadfc1200"),
+            sig_share: hex!("0baf31c64cf452fe3a520f14c5e2b898d6869341fdd8ea26044086a11a4372cd31ba2ee9cf7e316706067a53d05e184ebb95cae16aac7f3700"),
+        },
+    ];
+
+    const SIGNATURE: [u8; 114] = hex!("60e3d4d641dd19054c1eced47f02f4a5e377126fb75f68cbd1122d948f93e26069f6740506da0366eb4225746faf894ecd196a1f56d1e1d280185c3e1b58a13556a7becd31403a5ef4d66b0eb0335cb31f54ff2a296cb7cd7de472c50db750e6aab705dd52affdf80f1af3c05a2f9a9c3b00");
+
+    #[test]
+    fn test_rfc9591_vectors() {
+        let secret = Scalar::from_canonical_bytes(GROUP_SECRET_KEY).unwrap();
+        let coefficient = Scalar::from_canonical_bytes(SHARE_POLYNOMIAL_COEFFICIENT).unwrap();
+        let (shares, public_key_package) = split(&secret, &[coefficient], 3).unwrap();
+        assert_eq!(
+            public_key_package.verifying_key().to_bytes(),
+            GROUP_PUBLIC_KEY
+        );
+
+        let key_packages: Vec<KeyPackage> = shares
+            .iter()
+            .zip(PARTICIPANT_SHARES.iter())
+            .map(|(share, expected)| {
+                assert_eq!(share.value().to_bytes_rfc_8032(), *expected);
+                KeyPackage::try_from(share).unwrap()
+            })
+            .collect();
+
+        // Round one
+        let mut commitments = BTreeMap::new();
+        let mut nonces = Vec::new();
+        for vector in ROUND_VECTORS.iter() {
+            let key_package = &key_packages[vector.identifier as usize - 1];
+            let signing_nonces = SigningNonces::from_randomness(
+                key_package.signing_share(),
+                &vector.hiding_nonce_randomness,
+                &vector.binding_nonce_randomness,
+            );
+            assert_eq!(
+                signing_nonces.hiding.to_bytes_rfc_8032(),
+                vector.hiding_nonce
+            );
+            assert_eq!(
+                signing_nonces.binding.to_bytes_rfc_8032(),
+                vector.binding_nonce
+            );
+
+            let signing_commitments = signing_nonces.commitments();
+            assert_eq!(
+                encode_element(&signing_commitments.hiding),
+                vector.hiding_nonce_commitment
+            );
+            assert_eq!(
+                encode_element(&signing_commitments.binding),
+                vector.binding_nonce_commitment
+            );
+
+            commitments.insert(key_package.identifier(), signing_commitments);
+            nonces.push(signing_nonces);
+        }
+
+        let signing_package = SigningPackage::new(commitments, MESSAGE);
+        let binding_factors = signing_package.binding_factors(&public_key_package.verifying_key());
+        for vector in ROUND_VECTORS.iter() {
+            let identifier = Identifier::new(vector.identifier).unwrap();
+            assert_eq!(
+                binding_factors[&identifier].to_bytes_rfc_8032(),
+                vector.binding_factor
+            );
+        }
+
+        // Round two
+        let mut signature_shares = BTreeMap::new();
+        for (vector, signing_nonces) in ROUND_VECTORS.iter().zip(nonces) {
+            let key_package = &key_packages[vector.identifier as usize - 1];
+            let share = round2::sign(&signing_package, signing_nonces, key_package).unwrap();
+            assert_eq!(share.to_bytes(), vector.sig_share);
+
+            let identifier = key_package.identifier();
+            assert!(round2::verify_signature_share(
+                &identifier,
+                &public_key_package.verifying_shares()[&identifier],
+                &share,
+                &signing_package,
+                &public_key_package.verifying_key(),
+            )
+            .is_ok());
+            signature_shares.insert(identifier, share);
+        }
+
+        let signature =
+            aggregate(&signing_package, &signature_shares, &public_key_package).unwrap();
+        assert_eq!(signature.to_bytes(), SIGNATURE);
+
+        // The output is a plain Ed448 signature
+        let verifying_key = VerifyingKey::from_bytes(&GROUP_PUBLIC_KEY).unwrap();
+        assert!(verifying_key.verify_strict(MESSAGE, &signature).is_ok());
+    }
+
+    #[test]
+    fn test_threshold_signing() {
+        let secret = Scalar::from(0x1234_5678);
+        let coefficients = [Scalar::from(11), Scalar::from(12)];
+        let (shares, public_key_package) = split(&secret, &coefficients, 5).unwrap();
+        let key_packages: Vec<KeyPackage> = shares
+            .iter()
+            .map(|share| KeyPackage::try_from(share).unwrap())
+            .collect();
+        let message = b"threshold";
+
+        // Any three of the five participants can sign
+        for signers in [[0, 1, 2], [0, 2, 4], [1, 3, 4]] {
+            let nonces: Vec<SigningNonces> = signers
+                .iter()
+                .map(|&i| {
+                    SigningNonces::from_randomness(
+                        key_packages[i].signing_share(),
+                        &[i as u8; 32],
+                        &[0xff; 32],
+                    )
+                })
+                .collect();
+            let commitments = signers
+                .iter()
+                .zip(&nonces)
+                .map(|(&i, nonces)| (key_packages[i].identifier(), nonces.commitments()))
+                .collect();
+            let signing_package = SigningPackage::new(commitments, message);
+
+            let mut signature_shares: BTreeMap<Identifier, SignatureShare> = signers
+                .iter()
+                .zip(nonces)
+                .map(|(&i, nonces)| {
+                    let share = round2::sign(&signing_package, nonces, &key_packages[i]).unwrap();
+                    (key_packages[i].identifier(), share)
+                })
+                .collect();
+
+            let signature =
+                aggregate(&signing_package, &signature_shares, &public_key_package).unwrap();
+            assert!(public_key_package
+                .verifying_key()
+                .verify_strict(message, &signature)
+                .is_ok());
+
+            // A bad share is attributed to the signer who sent it
+            let culprit = key_packages[signers[1]].identifier();
+            let share = signature_shares.get_mut(&culprit).unwrap();
+            share.0 = share.0 + Scalar::one();
+            assert_eq!(
+                aggregate(&signing_package, &signature_shares, &public_key_package),
+                Err(FrostError::InvalidSignatureShare { culprit })
+            );
+        }
+    }
+
+    #[test]
+    fn test_signing_errors() {
+        let (shares, _) = split(&Scalar::from(5), &[Scalar::from(7)], 3).unwrap();
+        let key_packages: Vec<KeyPackage> = shares
+            .iter()
+            .map(|share| KeyPackage::try_from(share).unwrap())
+            .collect();
+
+        let nonces: Vec<SigningNonces> = key_packages
+            .iter()
+            .map(|key| SigningNonces::from_randomness(key.signing_share(), &[1; 32], &[2; 32]))
+            .collect();
+        let commitments: Vec<SigningCommitments> =
+            nonces.iter().map(|nonces| nonces.commitments()).collect();
+
+        // Fewer signers than the threshold
+        let package = SigningPackage::new(
+            BTreeMap::from([(key_packages[0].identifier(), commitments[0])]),
+            b"",
+        );
+        let mut nonces = nonces.into_iter();
+        assert_eq!(
+            round2::sign(&package, nonces.next().unwrap(), &key_packages[0]),
+            Err(FrostError::NotEnoughSigners)
+        );
+
+        // The signer is not part of the signing package
+        let package = SigningPackage::new(
+            BTreeMap::from([
+                (key_packages[0].identifier(), commitments[0]),
+                (key_packages[1].identifier(), commitments[1]),
+            ]),
+            b"",
+        );
+        assert_eq!(
+            round2::sign(&package, nonces.next().unwrap(), &key_packages[2]),
+            Err(FrostError::MissingCommitment)
+        );
+
+        // The nonces do not match the commitment in the signing package
+        assert_eq!(
+            round2::sign(&package, nonces.next().unwrap(), &key_packages[1]),
+            Err(FrostError::MissingCommitment)
+        );
+    }
+
+    #[test]
+    fn test_identifiers() {
+        assert_eq!(Identifier::new(0), Err(FrostError::InvalidIdentifier));
+        let one = Identifier::new(1).unwrap();
+        let big = Identifier::new(0x100).unwrap();
+        assert!(one < big);
+        assert_eq!(Identifier::from_bytes(&big.to_bytes()).unwrap(), big);
+        assert_eq!(
+            Identifier::from_bytes(&[0; 57]),
+            Err(FrostError::InvalidIdentifier)
+        );
+        assert_eq!(
+            Identifier::from_bytes(&[0xff; 57]),
+            Err(FrostError::InvalidIdentifier)
+        );
+    }
+}
@@ -0,0 +1,140 @@
+// Round one of FROST: every signer commits to a pair of nonces
+// See RFC 9591 Section 5.1
+
+use super::{decode_element, encode_element, h3, FrostError};
+use crate::curve::edwards::ExtendedPoint;
+use crate::field::Scalar;
+use std::fmt;
+
+#[cfg(feature = "rand")]
+use super::KeyPackage;
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The length of encoded signing commitments, in bytes
+pub const SIGNING_COMMITMENTS_LENGTH: usize = 114;
+
+/// Derives a nonce from 32 random bytes and the signing share
+/// See nonce_generate in RFC 9591 Section 4.1
+fn generate_nonce(signing_share: &Scalar, randomness: &[u8; 32]) -> Scalar {
+    h3(&[randomness, &signing_share.to_bytes_rfc_8032()])
+}
+
+/// The hiding and binding nonces of a signer for one signing session
+///
+/// It cannot be cloned and is consumed by `round2::sign`,
+/// since signing two messages with the same nonces reveals the signing share.
+pub struct SigningNonces {
+    pub(crate) hiding: Scalar,
+    pub(crate) binding: Scalar,
+    commitments: SigningCommitments,
+}
+
+impl fmt::Debug for SigningNonces {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SigningNonces")
+            .field("commitments", &self.commitments)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SigningNonces {
+    fn drop(&mut self) {
+        self.hiding.zeroize();
+        self.binding.zeroize();
+    }
+}
+
+impl SigningNonces {
+    /// Derives the nonces from the signing share and 32 bytes of fresh randomness for each nonce
+    ///
+    /// The randomness must never be reused, this is only public to reproduce test vectors.
+    pub fn from_randomness(
+        signing_share: &Scalar,
+        hiding_randomness: &[u8; 32],
+        binding_randomness: &[u8; 32],
+    ) -> SigningNonces {
+        let hiding = generate_nonce(signing_share, hiding_randomness);
+        let binding = generate_nonce(signing_share, binding_randomness);
+        SigningNonces {
+            hiding,
+            binding,
+            commitments: SigningCommitments {
+                hiding: ExtendedPoint::generator().scalar_mul(&hiding),
+                binding: ExtendedPoint::generator().scalar_mul(&binding),
+            },
+        }
+    }
+
+    /// Generates the nonces of the signer with `key_package` for a new signing session
+    #[cfg(feature = "rand")]
+    pub fn generate<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        key_package: &KeyPackage,
+    ) -> SigningNonces {
+        let mut hiding_randomness = [0u8; 32];
+        let mut binding_randomness = [0u8; 32];
+        rng.fill_bytes(&mut hiding_randomness);
+        rng.fill_bytes(&mut binding_randomness);
+        SigningNonces::from_randomness(
+            key_package.signing_share(),
+            &hiding_randomness,
+            &binding_randomness,
+        )
+    }
+
+    /// The commitments to the nonces, which are sent to the coordinator
+    pub fn commitments(&self) -> SigningCommitments {
+        self.commitments
+    }
+}
+
+/// The commitments ([hiding]B, [binding]B) of a signer
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SigningCommitments {
+    pub(crate) hiding: ExtendedPoint,
+    pub(crate) binding: ExtendedPoint,
+}
+
+impl SigningCommitments {
+    /// Encodes the commitments as `hiding || binding`
+    pub fn to_bytes(&self) -> [u8; SIGNING_COMMITMENTS_LENGTH] {
+        let hiding = encode_element(&self.hiding);
+        let binding = encode_element(&self.binding);
+        std::array::from_fn(|i| if i < 57 { hiding[i] } else { binding[i - 57] })
+    }
+
+    /// Parses commitments encoded as `hiding || binding`
+    pub fn from_bytes(
+        bytes: &[u8; SIGNING_COMMITMENTS_LENGTH],
+    ) -> Result<SigningCommitments, FrostError> {
+        Ok(SigningCommitments {
+            hiding: decode_element(&std::array::from_fn(|i| bytes[i]))?,
+            binding: decode_element(&std::array::from_fn(|i| bytes[57 + i]))?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commitment_encoding() {
+        let nonces = SigningNonces::from_randomness(&Scalar::from(3), &[1; 32], &[2; 32]);
+        let commitments = nonces.commitments();
+        let bytes = commitments.to_bytes();
+        assert_eq!(SigningCommitments::from_bytes(&bytes).unwrap(), commitments);
+
+        // The identity is rejected
+        let mut identity = bytes;
+        identity[..57].copy_from_slice(&encode_element(&ExtendedPoint::identity()));
+        assert_eq!(
+            SigningCommitments::from_bytes(&identity),
+            Err(FrostError::InvalidElement)
+        );
+    }
+}
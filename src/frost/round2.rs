@@ -0,0 +1,85 @@
+// Round two of FROST: every signer answers the signing package with a signature share
+// See RFC 9591 Section 5.2
+
+use super::round1::SigningNonces;
+use super::{
+    decode_scalar, lagrange_coefficient, FrostError, Identifier, KeyPackage, SigningPackage,
+};
+use crate::curve::edwards::ExtendedPoint;
+use crate::ed448::VerifyingKey;
+use crate::field::Scalar;
+
+/// The length of an encoded signature share, in bytes
+pub const SIGNATURE_SHARE_LENGTH: usize = 57;
+
+/// The share of a signer in the response of the signature
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SignatureShare(pub(crate) Scalar);
+
+impl SignatureShare {
+    /// Encodes the share as a 57 byte little-endian scalar
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_SHARE_LENGTH] {
+        self.0.to_bytes_rfc_8032()
+    }
+
+    /// Parses a share, which must be reduced modulo ℓ
+    pub fn from_bytes(bytes: &[u8; SIGNATURE_SHARE_LENGTH]) -> Result<SignatureShare, FrostError> {
+        decode_scalar(bytes).map(SignatureShare)
+    }
+}
+
+/// Computes the signature share of the signer with `key_package`
+///
+/// The nonces are consumed, so they cannot be used for a second signature.
+pub fn sign(
+    signing_package: &SigningPackage,
+    signing_nonces: SigningNonces,
+    key_package: &KeyPackage,
+) -> Result<SignatureShare, FrostError> {
+    if signing_package.commitments.len() < key_package.min_signers() as usize {
+        return Err(FrostError::NotEnoughSigners);
+    }
+    let identifier = key_package.identifier();
+    if signing_package.commitments.get(&identifier) != Some(&signing_nonces.commitments()) {
+        return Err(FrostError::MissingCommitment);
+    }
+
+    let (binding_factors, _, challenge) = signing_package.session(&key_package.verifying_key());
+    let lambda = lagrange_coefficient(&identifier, &signing_package.signers());
+
+    // z_i = d_i + e_i * ρ_i + λ_i * s_i * c
+    let share = signing_nonces.hiding
+        + signing_nonces.binding * binding_factors[&identifier]
+        + lambda * *key_package.signing_share() * challenge;
+    Ok(SignatureShare(share))
+}
+
+/// Checks the signature share of the signer with `identifier`, whose share has the public key `verifying_share`
+/// See verify_signature_share in RFC 9591 Section 5.4
+pub fn verify_signature_share(
+    identifier: &Identifier,
+    verifying_share: &ExtendedPoint,
+    signature_share: &SignatureShare,
+    signing_package: &SigningPackage,
+    verifying_key: &VerifyingKey,
+) -> Result<(), FrostError> {
+    let commitments = signing_package
+        .commitments
+        .get(identifier)
+        .ok_or(FrostError::UnknownSigner)?;
+
+    let (binding_factors, _, challenge) = signing_package.session(verifying_key);
+    let lambda = lagrange_coefficient(identifier, &signing_package.signers());
+
+    // [z_i]B = D_i + [ρ_i]E_i + [c * λ_i]Y_i
+    let expected = commitments.hiding
+        + commitments.binding.scalar_mul(&binding_factors[identifier])
+        + verifying_share.scalar_mul(&(challenge * lambda));
+    if ExtendedPoint::generator().scalar_mul(&signature_share.0) == expected {
+        Ok(())
+    } else {
+        Err(FrostError::InvalidSignatureShare {
+            culprit: *identifier,
+        })
+    }
+}
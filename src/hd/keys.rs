@@ -0,0 +1,587 @@
+use super::{ChainCode, ChildIndex, DerivationPath, Ed448, HdError, HdGroup};
+use super::{CHAIN_CODE_LENGTH, MIN_SEED_LENGTH};
+use crate::ed448::{ExpandedSigningKey, VerifyingKey};
+use crate::field::Scalar;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use std::fmt;
+use std::marker::PhantomData;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Length of the metadata which precedes the key in a serialized extended key:
+/// version (4) || depth (1) || parent fingerprint (4) || child index (4) || chain code (32)
+const HEADER_LENGTH: usize = 4 + 1 + 4 + 4 + CHAIN_CODE_LENGTH;
+
+/// Length of an encoded secret scalar
+const SECRET_LENGTH: usize = 57;
+
+/// Computes the tweak and the chain code of a child
+/// I = SHAKE256(domain || label || data, 114 + 32)
+fn derive<G: HdGroup>(label: &[u8], data: &[&[u8]]) -> (Scalar, ChainCode) {
+    let mut hasher = Shake256::default();
+    hasher.update(G::DOMAIN);
+    hasher.update(label);
+    for part in data {
+        hasher.update(part);
+    }
+    let mut reader = hasher.finalize_xof();
+
+    let mut wide = [0u8; 114];
+    reader.read(&mut wide);
+    let mut chain_code = [0u8; CHAIN_CODE_LENGTH];
+    reader.read(&mut chain_code);
+
+    let tweak = Scalar::from_bytes_mod_order_wide(&wide);
+
+    #[cfg(feature = "zeroize")]
+    wide.zeroize();
+
+    (tweak, ChainCode(chain_code))
+}
+
+/// Computes the tweak and the chain code of the child at `index`,
+/// where `data` is either the hardened or the non-hardened input
+fn derive_child<G: HdGroup>(
+    chain_code: &ChainCode,
+    data: &[&[u8]],
+    index: ChildIndex,
+) -> (Scalar, ChainCode) {
+    let index_bytes = index.0.to_be_bytes();
+    let mut input: Vec<&[u8]> = vec![chain_code.as_bytes()];
+    input.extend_from_slice(data);
+    input.push(&index_bytes);
+    derive::<G>(b"child", &input)
+}
+
+/// Computes the child secret, parent + tweak, which must not be zero
+fn child_secret(parent: &Scalar, tweak: &Scalar) -> Result<Scalar, HdError> {
+    let secret = *parent + *tweak;
+    if secret == Scalar::zero() {
+        return Err(HdError::InvalidChild);
+    }
+    Ok(secret)
+}
+
+/// Computes the child public key, parent + [tweak]G, which must not be the identity
+///
+/// This is the public key of `child_secret`, so both derivations reject the same children.
+fn child_point<G: HdGroup>(parent: &G::Point, tweak: &Scalar) -> Result<G::Point, HdError> {
+    let point = G::add(parent, &G::mul_base(tweak));
+    if G::is_identity(&point) {
+        return Err(HdError::InvalidChild);
+    }
+    Ok(point)
+}
+
+/// The first four bytes of the hash of a public key, which identify the parent of a key
+fn fingerprint<G: HdGroup>(point: &G::Point) -> [u8; 4] {
+    let mut fingerprint = [0u8; 4];
+    Shake256::default()
+        .chain(G::DOMAIN)
+        .chain(b"fingerprint")
+        .chain(G::encode(point))
+        .finalize_xof()
+        .read(&mut fingerprint);
+    fingerprint
+}
+
+/// Parses the metadata of a serialized extended key
+fn parse_header(
+    bytes: &[u8],
+    version: [u8; 4],
+) -> Result<(u8, [u8; 4], ChildIndex, ChainCode), HdError> {
+    if bytes.len() < HEADER_LENGTH || bytes[..4] != version {
+        return Err(HdError::InvalidEncoding);
+    }
+    let depth = bytes[4];
+    let parent_fingerprint: [u8; 4] = std::array::from_fn(|i| bytes[5 + i]);
+    let child_index = ChildIndex(u32::from_be_bytes(std::array::from_fn(|i| bytes[9 + i])));
+    let chain_code = ChainCode(std::array::from_fn(|i| bytes[13 + i]));
+
+    // The master key has no parent
+    if depth == 0 && (parent_fingerprint != [0; 4] || child_index.0 != 0) {
+        return Err(HdError::InvalidEncoding);
+    }
+
+    Ok((depth, parent_fingerprint, child_index, chain_code))
+}
+
+/// Serializes the metadata of an extended key
+fn write_header(
+    version: [u8; 4],
+    depth: u8,
+    parent_fingerprint: &[u8; 4],
+    child_index: ChildIndex,
+    chain_code: &ChainCode,
+) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LENGTH + SECRET_LENGTH);
+    bytes.extend_from_slice(&version);
+    bytes.push(depth);
+    bytes.extend_from_slice(parent_fingerprint);
+    bytes.extend_from_slice(&child_index.0.to_be_bytes());
+    bytes.extend_from_slice(chain_code.as_bytes());
+    bytes
+}
+
+/// A secret key together with its chain code and position in the tree
+#[derive(Clone)]
+pub struct ExtendedSecretKey<G: HdGroup> {
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_index: ChildIndex,
+    chain_code: ChainCode,
+    secret: Scalar,
+    group: PhantomData<G>,
+}
+
+impl<G: HdGroup> fmt::Debug for ExtendedSecretKey<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedSecretKey")
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &self.parent_fingerprint)
+            .field("child_index", &self.child_index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G: HdGroup> PartialEq for ExtendedSecretKey<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.depth == other.depth
+            && self.parent_fingerprint == other.parent_fingerprint
+            && self.child_index == other.child_index
+            && self.chain_code == other.chain_code
+            && self.secret == other.secret
+    }
+}
+impl<G: HdGroup> Eq for ExtendedSecretKey<G> {}
+
+#[cfg(feature = "zeroize")]
+impl<G: HdGroup> Drop for ExtendedSecretKey<G> {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+        self.chain_code.0.zeroize();
+    }
+}
+
+impl<G: HdGroup> ExtendedSecretKey<G> {
+    /// Derives the master key from a seed of at least `MIN_SEED_LENGTH` bytes
+    pub fn from_seed(seed: &[u8]) -> Result<ExtendedSecretKey<G>, HdError> {
+        if seed.len() < MIN_SEED_LENGTH {
+            return Err(HdError::SeedTooShort);
+        }
+
+        let (secret, chain_code) = derive::<G>(b"master", &[seed]);
+        if secret == Scalar::zero() {
+            return Err(HdError::InvalidChild);
+        }
+
+        Ok(ExtendedSecretKey {
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_index: ChildIndex(0),
+            chain_code,
+            secret,
+            group: PhantomData,
+        })
+    }
+
+    /// Derives the child at `index`, which may be hardened
+    pub fn derive_child(&self, index: ChildIndex) -> Result<ExtendedSecretKey<G>, HdError> {
+        let depth = self.depth.checked_add(1).ok_or(HdError::MaximumDepth)?;
+        let point = G::mul_base(&self.secret);
+
+        let (tweak, chain_code) = if index.is_hardened() {
+            #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+            let mut secret_bytes = self.secret.to_bytes_rfc_8032();
+            let derived = derive_child::<G>(&self.chain_code, &[&[0x00], &secret_bytes], index);
+
+            #[cfg(feature = "zeroize")]
+            secret_bytes.zeroize();
+
+            derived
+        } else {
+            derive_child::<G>(&self.chain_code, &[&[0x01], &G::encode(&point)], index)
+        };
+
+        let secret = child_secret(&self.secret, &tweak)?;
+
+        Ok(ExtendedSecretKey {
+            depth,
+            parent_fingerprint: fingerprint::<G>(&point),
+            child_index: index,
+            chain_code,
+            secret,
+            group: PhantomData,
+        })
+    }
+
+    /// Derives the descendant at the end of `path`, relative to this key
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<ExtendedSecretKey<G>, HdError> {
+        let mut key = self.clone();
+        for index in path.indices() {
+            key = key.derive_child(*index)?;
+        }
+        Ok(key)
+    }
+
+    /// Returns the extended public key with the same position in the tree
+    pub fn public_key(&self) -> ExtendedPublicKey<G> {
+        ExtendedPublicKey {
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_index: self.child_index,
+            chain_code: self.chain_code,
+            point: G::mul_base(&self.secret),
+        }
+    }
+
+    /// The secret scalar
+    pub fn secret(&self) -> Scalar {
+        self.secret
+    }
+
+    /// The chain code
+    pub fn chain_code(&self) -> ChainCode {
+        self.chain_code
+    }
+
+    /// The number of derivations from the master key to this key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// The index that this key was derived with, zero for the master key
+    pub fn child_index(&self) -> ChildIndex {
+        self.child_index
+    }
+
+    /// The fingerprint of the parent key, zero for the master key
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// The fingerprint of this key
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint::<G>(&G::mul_base(&self.secret))
+    }
+
+    /// Serializes the extended key as
+    /// version || depth || parent fingerprint || child index || chain code || secret
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = write_header(
+            G::SECRET_VERSION,
+            self.depth,
+            &self.parent_fingerprint,
+            self.child_index,
+            &self.chain_code,
+        );
+        bytes.extend_from_slice(&self.secret.to_bytes_rfc_8032());
+        bytes
+    }
+
+    /// Parses an extended key which was serialized with `to_bytes`
+    pub fn from_bytes(bytes: &[u8]) -> Result<ExtendedSecretKey<G>, HdError> {
+        let (depth, parent_fingerprint, child_index, chain_code) =
+            parse_header(bytes, G::SECRET_VERSION)?;
+        let secret: [u8; SECRET_LENGTH] = bytes[HEADER_LENGTH..]
+            .try_into()
+            .map_err(|_| HdError::InvalidEncoding)?;
+        let secret = Scalar::from_canonical_bytes(secret).ok_or(HdError::InvalidEncoding)?;
+        if secret == Scalar::zero() {
+            return Err(HdError::InvalidEncoding);
+        }
+
+        Ok(ExtendedSecretKey {
+            depth,
+            parent_fingerprint,
+            child_index,
+            chain_code,
+            secret,
+            group: PhantomData,
+        })
+    }
+}
+
+impl ExtendedSecretKey<Ed448> {
+    /// Returns the Ed448 signing key of this key
+    ///
+    /// The secret scalar signs directly, with the nonce prefix
+    /// SHAKE256(domain || "prefix" || chain code || secret, 57).
+    pub fn signing_key(&self) -> ExpandedSigningKey {
+        #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+        let mut secret_bytes = self.secret.to_bytes_rfc_8032();
+        let mut prefix = [0u8; 57];
+        Shake256::default()
+            .chain(Ed448::DOMAIN)
+            .chain(b"prefix")
+            .chain(self.chain_code.as_bytes())
+            .chain(secret_bytes)
+            .finalize_xof()
+            .read(&mut prefix);
+        let signing_key = ExpandedSigningKey::from_scalar_and_prefix(&self.secret, &prefix);
+
+        #[cfg(feature = "zeroize")]
+        {
+            secret_bytes.zeroize();
+            prefix.zeroize();
+        }
+
+        signing_key
+    }
+}
+
+/// A public key together with its chain code and position in the tree
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ExtendedPublicKey<G: HdGroup> {
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_index: ChildIndex,
+    chain_code: ChainCode,
+    point: G::Point,
+}
+
+impl<G: HdGroup> ExtendedPublicKey<G> {
+    /// Derives the non-hardened child at `index`
+    pub fn derive_child(&self, index: ChildIndex) -> Result<ExtendedPublicKey<G>, HdError> {
+        if index.is_hardened() {
+            return Err(HdError::HardenedPublicDerivation);
+        }
+        let depth = self.depth.checked_add(1).ok_or(HdError::MaximumDepth)?;
+
+        let (tweak, chain_code) =
+            derive_child::<G>(&self.chain_code, &[&[0x01], &G::encode(&self.point)], index);
+        let point = child_point::<G>(&self.point, &tweak)?;
+
+        Ok(ExtendedPublicKey {
+            depth,
+            parent_fingerprint: fingerprint::<G>(&self.point),
+            child_index: index,
+            chain_code,
+            point,
+        })
+    }
+
+    /// Derives the descendant at the end of `path`, relative to this key
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<ExtendedPublicKey<G>, HdError> {
+        let mut key = *self;
+        for index in path.indices() {
+            key = key.derive_child(*index)?;
+        }
+        Ok(key)
+    }
+
+    /// The public key
+    pub fn point(&self) -> G::Point {
+        self.point
+    }
+
+    /// The chain code
+    pub fn chain_code(&self) -> ChainCode {
+        self.chain_code
+    }
+
+    /// The number of derivations from the master key to this key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// The index that this key was derived with, zero for the master key
+    pub fn child_index(&self) -> ChildIndex {
+        self.child_index
+    }
+
+    /// The fingerprint of the parent key, zero for the master key
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// The fingerprint of this key
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint::<G>(&self.point)
+    }
+
+    /// Serializes the extended key as
+    /// version || depth || parent fingerprint || child index || chain code || public key
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = write_header(
+            G::PUBLIC_VERSION,
+            self.depth,
+            &self.parent_fingerprint,
+            self.child_index,
+            &self.chain_code,
+        );
+        bytes.extend_from_slice(&G::encode(&self.point));
+        bytes
+    }
+
+    /// Parses an extended key which was serialized with `to_bytes`
+    pub fn from_bytes(bytes: &[u8]) -> Result<ExtendedPublicKey<G>, HdError> {
+        let (depth, parent_fingerprint, child_index, chain_code) =
+            parse_header(bytes, G::PUBLIC_VERSION)?;
+        let point = &bytes[HEADER_LENGTH..];
+        if point.len() != G::POINT_LENGTH {
+            return Err(HdError::InvalidEncoding);
+        }
+        let point = G::decode(point).ok_or(HdError::InvalidEncoding)?;
+
+        Ok(ExtendedPublicKey {
+            depth,
+            parent_fingerprint,
+            child_index,
+            chain_code,
+            point,
+        })
+    }
+}
+
+impl ExtendedPublicKey<Ed448> {
+    /// Returns the Ed448 public key, which verifies the signatures of `ExtendedSecretKey::signing_key`
+    pub fn verifying_key(&self) -> VerifyingKey {
+        VerifyingKey::from_point(self.point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hd::{Decaf448, Ed448};
+    use proptest::prelude::*;
+
+    fn check_public_and_private_derivation_agree<G: HdGroup>(seed: &[u8], path: &[u32]) {
+        let master = ExtendedSecretKey::<G>::from_seed(seed).unwrap();
+        let path = DerivationPath::from(
+            path.iter()
+                .map(|i| ChildIndex::normal(*i).unwrap())
+                .collect::<Vec<_>>(),
+        );
+
+        let private = master.derive_path(&path).unwrap().public_key();
+        let public = master.public_key().derive_path(&path).unwrap();
+        assert_eq!(private, public);
+    }
+
+    fn check_serialization<G: HdGroup>(seed: &[u8], path: &DerivationPath) {
+        let key = ExtendedSecretKey::<G>::from_seed(seed)
+            .unwrap()
+            .derive_path(path)
+            .unwrap();
+        let bytes = key.to_bytes();
+        assert_eq!(ExtendedSecretKey::<G>::from_bytes(&bytes).unwrap(), key);
+
+        let public = key.public_key();
+        let bytes = public.to_bytes();
+        assert_eq!(ExtendedPublicKey::<G>::from_bytes(&bytes).unwrap(), public);
+
+        // Secret and public keys, and keys of different groups, cannot be confused
+        assert!(ExtendedSecretKey::<G>::from_bytes(&bytes).is_err());
+        assert!(ExtendedPublicKey::<G>::from_bytes(&key.to_bytes()).is_err());
+        assert!(ExtendedPublicKey::<G>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    fn check_invalid_children_agree<G: HdGroup>() {
+        let parent = Scalar::from(1234u32);
+        let point = G::mul_base(&parent);
+        // A zero tweak is a valid child, a tweak of -parent is not
+        for tweak in [Scalar::zero(), Scalar::one(), Scalar::zero() - parent] {
+            let secret = child_secret(&parent, &tweak);
+            let public = child_point::<G>(&point, &tweak);
+            assert_eq!(secret.is_ok(), public.is_ok());
+            if let (Ok(secret), Ok(public)) = (secret, public) {
+                assert_eq!(G::mul_base(&secret), public);
+            }
+        }
+        assert_eq!(
+            child_point::<G>(&point, &(Scalar::zero() - parent)),
+            Err(HdError::InvalidChild)
+        );
+        assert_eq!(child_point::<G>(&point, &Scalar::zero()), Ok(point));
+    }
+
+    #[test]
+    fn test_invalid_children_agree() {
+        check_invalid_children_agree::<Ed448>();
+        check_invalid_children_agree::<Decaf448>();
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn prop_public_and_private_derivation_agree(
+            seed in prop::collection::vec(any::<u8>(), 16..64),
+            path in prop::collection::vec(0..ChildIndex::HARDENED_OFFSET, 0..4),
+        ) {
+            check_public_and_private_derivation_agree::<Ed448>(&seed, &path);
+            check_public_and_private_derivation_agree::<Decaf448>(&seed, &path);
+        }
+
+        #[test]
+        fn prop_serialization_round_trips(
+            seed in prop::collection::vec(any::<u8>(), 16..64),
+            path in prop::collection::vec(any::<u32>(), 0..4),
+        ) {
+            let path = DerivationPath::from(path.into_iter().map(ChildIndex).collect::<Vec<_>>());
+            check_serialization::<Ed448>(&seed, &path);
+            check_serialization::<Decaf448>(&seed, &path);
+        }
+    }
+
+    #[test]
+    fn test_derivation() {
+        let seed = [7u8; 32];
+        let master = ExtendedSecretKey::<Ed448>::from_seed(&seed).unwrap();
+        assert_eq!(master.depth(), 0);
+        assert_eq!(
+            ExtendedSecretKey::<Ed448>::from_seed(&seed[..15]),
+            Err(HdError::SeedTooShort)
+        );
+
+        let hardened = ChildIndex::hardened(0).unwrap();
+        let normal = ChildIndex::normal(0).unwrap();
+        assert_ne!(hardened, normal);
+
+        let child = master.derive_child(hardened).unwrap();
+        assert_eq!(child.depth(), 1);
+        assert_eq!(child.parent_fingerprint(), master.fingerprint());
+        assert_ne!(
+            child.secret(),
+            master.derive_child(normal).unwrap().secret()
+        );
+
+        // Hardened children cannot be derived from the public key
+        assert_eq!(
+            master.public_key().derive_child(hardened),
+            Err(HdError::HardenedPublicDerivation)
+        );
+
+        // The derived keys are Ed448 keys in the prime-order subgroup
+        let path: DerivationPath = "m/1'/2/3".parse().unwrap();
+        let key = master.derive_path(&path).unwrap();
+        assert!(key.public_key().point().is_torsion_free());
+
+        // A derived child signs, and verifies against the child derived from the public key
+        let path: DerivationPath = "m/5/6".parse().unwrap();
+        let signing_key = master.derive_path(&path).unwrap().signing_key();
+        let verifying_key = master
+            .public_key()
+            .derive_path(&path)
+            .unwrap()
+            .verifying_key();
+        assert_eq!(signing_key.verifying_key(), verifying_key);
+        let signature = signing_key.sign(b"message");
+        assert!(verifying_key.verify_strict(b"message", &signature).is_ok());
+        assert!(verifying_key.verify(b"other", &signature).is_err());
+        // Siblings have other nonces
+        let sibling = master
+            .derive_child(ChildIndex::normal(7).unwrap())
+            .unwrap()
+            .signing_key();
+        assert_ne!(sibling.sign(b"message").r_bytes(), signature.r_bytes());
+
+        // The same seed gives unrelated keys in the two groups
+        let decaf = ExtendedSecretKey::<Decaf448>::from_seed(&seed).unwrap();
+        assert_ne!(decaf.secret(), master.secret());
+    }
+}
@@ -0,0 +1,189 @@
+// This is the module for hierarchical deterministic (HD) key derivation, in the style of BIP32
+// https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+//
+// Secret keys are scalars and public keys are points, either on Ed448-Goldilocks or in the
+// Decaf448 group. HMAC-SHA512 from BIP32 is replaced by SHAKE256 with a domain separator
+// which names the group, so the two groups never share derived keys. For the child with index i:
+//
+//      I = SHAKE256(domain || chain code || data || i, 114 + 32)
+//      child secret = parent secret + I[0..114] mod ℓ
+//      child chain code = I[114..146]
+//
+// where data is 0x00 || parent secret for hardened children and 0x01 || parent public key
+// otherwise. Non-hardened children can therefore also be derived from the parent public key,
+// as child public = parent public + [I[0..114]]G.
+//
+// N.B. An Ed448 child secret is a scalar, not an RFC 8032 seed. It signs as an
+// `ed448::ExpandedSigningKey`, with a nonce prefix derived from the chain code and the secret.
+
+mod keys;
+mod path;
+
+pub use keys::{ExtendedPublicKey, ExtendedSecretKey};
+pub use path::{ChildIndex, DerivationPath};
+
+use crate::curve::edwards::{CompressedEdwardsY, ExtendedPoint};
+use crate::decaf::{CompressedDecaf, DecafPoint};
+use crate::field::Scalar;
+use std::fmt;
+
+/// The length of a chain code, in bytes
+pub const CHAIN_CODE_LENGTH: usize = 32;
+
+/// The shortest seed that a master key can be derived from
+pub const MIN_SEED_LENGTH: usize = 16;
+
+/// Errors which can occur when deriving, parsing or serializing extended keys
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HdError {
+    /// The seed is shorter than `MIN_SEED_LENGTH` bytes
+    SeedTooShort,
+    /// A hardened child cannot be derived from a public key
+    HardenedPublicDerivation,
+    /// The derived key is zero; the next index should be used instead
+    InvalidChild,
+    /// The key is already at the maximum depth of 255
+    MaximumDepth,
+    /// The derivation path is not of the form `m/0'/1/2h`
+    InvalidPath,
+    /// The serialized extended key is malformed
+    InvalidEncoding,
+}
+
+impl fmt::Display for HdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HdError::SeedTooShort => write!(f, "seed is shorter than {} bytes", MIN_SEED_LENGTH),
+            HdError::HardenedPublicDerivation => {
+                write!(f, "hardened children cannot be derived from a public key")
+            }
+            HdError::InvalidChild => write!(f, "derived key is invalid"),
+            HdError::MaximumDepth => write!(f, "maximum derivation depth reached"),
+            HdError::InvalidPath => write!(f, "invalid derivation path"),
+            HdError::InvalidEncoding => write!(f, "invalid extended key encoding"),
+        }
+    }
+}
+
+impl std::error::Error for HdError {}
+
+/// The chain code, which is the extra entropy that makes derivation from a public key possible
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ChainCode(pub [u8; CHAIN_CODE_LENGTH]);
+
+impl ChainCode {
+    /// View the chain code as a byte slice
+    pub fn as_bytes(&self) -> &[u8; CHAIN_CODE_LENGTH] {
+        &self.0
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A prime-order group that keys can be derived in
+///
+/// This is implemented by [`Ed448`] and [`Decaf448`], and cannot be implemented outside of this crate.
+pub trait HdGroup: sealed::Sealed + Copy + fmt::Debug + PartialEq + Eq {
+    /// The type of the public keys
+    type Point: Copy + fmt::Debug + PartialEq;
+
+    /// Domain separator for the derivation in this group
+    const DOMAIN: &'static [u8];
+    /// Length of an encoded public key
+    const POINT_LENGTH: usize;
+    /// Version bytes which start a serialized extended secret key
+    const SECRET_VERSION: [u8; 4];
+    /// Version bytes which start a serialized extended public key
+    const PUBLIC_VERSION: [u8; 4];
+
+    /// Computes [s]G for the generator G of the group
+    fn mul_base(s: &Scalar) -> Self::Point;
+    /// Adds two points
+    fn add(a: &Self::Point, b: &Self::Point) -> Self::Point;
+    /// Returns true if the point is the identity
+    fn is_identity(point: &Self::Point) -> bool;
+    /// Encodes a point into `POINT_LENGTH` bytes
+    fn encode(point: &Self::Point) -> Vec<u8>;
+    /// Decodes a point from `POINT_LENGTH` bytes
+    fn decode(bytes: &[u8]) -> Option<Self::Point>;
+}
+
+/// Key derivation on Ed448-Goldilocks, public keys are Ed448 public keys
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Ed448;
+
+impl sealed::Sealed for Ed448 {}
+
+impl HdGroup for Ed448 {
+    type Point = ExtendedPoint;
+
+    const DOMAIN: &'static [u8] = b"Ed448-Goldilocks HD Ed448";
+    const POINT_LENGTH: usize = 57;
+    const SECRET_VERSION: [u8; 4] = *b"ed4s";
+    const PUBLIC_VERSION: [u8; 4] = *b"ed4p";
+
+    fn mul_base(s: &Scalar) -> ExtendedPoint {
+        ExtendedPoint::generator().scalar_mul(s)
+    }
+
+    fn add(a: &ExtendedPoint, b: &ExtendedPoint) -> ExtendedPoint {
+        a + b
+    }
+
+    fn is_identity(point: &ExtendedPoint) -> bool {
+        *point == ExtendedPoint::identity()
+    }
+
+    fn encode(point: &ExtendedPoint) -> Vec<u8> {
+        point.compress().0.to_vec()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<ExtendedPoint> {
+        let bytes: [u8; 57] = bytes.try_into().ok()?;
+        // Only accept the canonical encoding of a point in the prime-order subgroup
+        let point = CompressedEdwardsY(bytes).decompress_strict()?;
+        if point.is_torsion_free() {
+            Some(point)
+        } else {
+            None
+        }
+    }
+}
+
+/// Key derivation in the Decaf448 group
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Decaf448;
+
+impl sealed::Sealed for Decaf448 {}
+
+impl HdGroup for Decaf448 {
+    type Point = DecafPoint;
+
+    const DOMAIN: &'static [u8] = b"Ed448-Goldilocks HD Decaf448";
+    const POINT_LENGTH: usize = 56;
+    const SECRET_VERSION: [u8; 4] = *b"dc4s";
+    const PUBLIC_VERSION: [u8; 4] = *b"dc4p";
+
+    fn mul_base(s: &Scalar) -> DecafPoint {
+        &DecafPoint::generator() * s
+    }
+
+    fn add(a: &DecafPoint, b: &DecafPoint) -> DecafPoint {
+        a + b
+    }
+
+    fn is_identity(point: &DecafPoint) -> bool {
+        *point == DecafPoint::identity()
+    }
+
+    fn encode(point: &DecafPoint) -> Vec<u8> {
+        point.compress().0.to_vec()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<DecafPoint> {
+        let bytes: [u8; 56] = bytes.try_into().ok()?;
+        CompressedDecaf(bytes).decompress()
+    }
+}
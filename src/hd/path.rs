@@ -0,0 +1,166 @@
+use super::HdError;
+use std::fmt;
+use std::str::FromStr;
+
+/// The index of a child key, hardened indices have the top bit set
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ChildIndex(pub u32);
+
+impl ChildIndex {
+    /// The first hardened index
+    pub const HARDENED_OFFSET: u32 = 1 << 31;
+
+    /// The `i`th non-hardened child, `i` must be less than 2^31
+    pub fn normal(i: u32) -> Option<ChildIndex> {
+        if i < Self::HARDENED_OFFSET {
+            Some(ChildIndex(i))
+        } else {
+            None
+        }
+    }
+
+    /// The `i`th hardened child, `i` must be less than 2^31
+    pub fn hardened(i: u32) -> Option<ChildIndex> {
+        if i < Self::HARDENED_OFFSET {
+            Some(ChildIndex(i | Self::HARDENED_OFFSET))
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if the child can only be derived from the parent secret key
+    pub fn is_hardened(&self) -> bool {
+        self.0 >= Self::HARDENED_OFFSET
+    }
+}
+
+impl fmt::Display for ChildIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_hardened() {
+            write!(f, "{}'", self.0 - Self::HARDENED_OFFSET)
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+impl FromStr for ChildIndex {
+    type Err = HdError;
+
+    /// Parses `i` or, for hardened indices, `i'` or `ih`
+    fn from_str(s: &str) -> Result<ChildIndex, HdError> {
+        let (digits, hardened) = match s.strip_suffix(['\'', 'h']) {
+            Some(digits) => (digits, true),
+            None => (s, false),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(HdError::InvalidPath);
+        }
+        let i: u32 = digits.parse().map_err(|_| HdError::InvalidPath)?;
+
+        let index = if hardened {
+            ChildIndex::hardened(i)
+        } else {
+            ChildIndex::normal(i)
+        };
+        index.ok_or(HdError::InvalidPath)
+    }
+}
+
+/// A path from the master key to a descendant, such as `m/44'/0'/1/7`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Vec<ChildIndex>);
+
+impl DerivationPath {
+    /// The path which only contains the master key
+    pub fn master() -> DerivationPath {
+        DerivationPath(Vec::new())
+    }
+
+    /// Returns the path to the given child of the last key on this path
+    pub fn child(&self, index: ChildIndex) -> DerivationPath {
+        let mut path = self.0.clone();
+        path.push(index);
+        DerivationPath(path)
+    }
+
+    /// The indices along the path, starting below the master key
+    pub fn indices(&self) -> &[ChildIndex] {
+        &self.0
+    }
+}
+
+impl From<Vec<ChildIndex>> for DerivationPath {
+    fn from(indices: Vec<ChildIndex>) -> DerivationPath {
+        DerivationPath(indices)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for index in self.0.iter() {
+            write!(f, "/{}", index)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = HdError;
+
+    fn from_str(s: &str) -> Result<DerivationPath, HdError> {
+        let mut components = s.split('/');
+        if components.next() != Some("m") {
+            return Err(HdError::InvalidPath);
+        }
+        let indices = components
+            .map(ChildIndex::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(DerivationPath(indices))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_paths() {
+        let path: DerivationPath = "m/44'/0h/1/2147483647".parse().unwrap();
+        assert_eq!(
+            path.indices(),
+            &[
+                ChildIndex::hardened(44).unwrap(),
+                ChildIndex::hardened(0).unwrap(),
+                ChildIndex::normal(1).unwrap(),
+                ChildIndex::normal(2147483647).unwrap(),
+            ]
+        );
+        assert_eq!(path.to_string(), "m/44'/0'/1/2147483647");
+        assert_eq!(
+            "m".parse::<DerivationPath>().unwrap(),
+            DerivationPath::master()
+        );
+
+        for invalid in [
+            "",
+            "/1",
+            "m/",
+            "m//1",
+            "m/x",
+            "m/-1",
+            "m/+1",
+            "m/1''",
+            "m/2147483648",
+            "n/1",
+        ] {
+            assert_eq!(
+                invalid.parse::<DerivationPath>(),
+                Err(HdError::InvalidPath),
+                "{}",
+                invalid
+            );
+        }
+    }
+}
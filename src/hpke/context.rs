@@ -0,0 +1,245 @@
+// The key schedule and the encryption contexts, see RFC 9180 sections 5.1 to 5.3
+
+use super::{labeled_expand, labeled_extract, Aead, HpkeError, Psk, KDF_ID, KEM_ID};
+use aes_gcm::aead::generic_array::GenericArray;
+use aes_gcm::aead::{KeyInit, Payload};
+use aes_gcm::{Aes128Gcm, Aes256Gcm};
+use chacha20poly1305::ChaCha20Poly1305;
+use hkdf::Hkdf;
+use sha2::Sha512;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The output length of HKDF-SHA512, Nh
+const KDF_LENGTH: usize = 64;
+
+/// The nonce length of all three AEADs, Nn
+const NONCE_LENGTH: usize = 12;
+
+/// The longest key of the three AEADs, Nk of AES-256-GCM and ChaCha20Poly1305
+const MAX_KEY_LENGTH: usize = 32;
+
+/// The state shared by the sender and recipient contexts
+struct Context {
+    aead: Aead,
+    key: [u8; MAX_KEY_LENGTH],
+    base_nonce: [u8; NONCE_LENGTH],
+    seq: u64,
+    exporter_secret: [u8; KDF_LENGTH],
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Context {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.base_nonce.zeroize();
+        self.exporter_secret.zeroize();
+    }
+}
+
+/// Derives the context from the shared secret of the KEM, see KeySchedule in RFC 9180 section 5.1
+fn key_schedule(
+    mode: u8,
+    aead: Aead,
+    shared_secret: &[u8],
+    info: &[u8],
+    psk: Option<&Psk>,
+) -> Result<Context, HpkeError> {
+    let (psk, psk_id) = match psk {
+        Some(psk) if psk.psk.is_empty() || psk.psk_id.is_empty() => {
+            return Err(HpkeError::InvalidPsk)
+        }
+        Some(psk) => (psk.psk, psk.psk_id),
+        None => (&[][..], &[][..]),
+    };
+
+    let suite_id = suite_id(aead);
+    let (psk_id_hash, _) = labeled_extract(&suite_id, &[], b"psk_id_hash", psk_id);
+    let (info_hash, _) = labeled_extract(&suite_id, &[], b"info_hash", info);
+    let mut key_schedule_context = [0u8; 1 + 2 * KDF_LENGTH];
+    key_schedule_context[0] = mode;
+    key_schedule_context[1..1 + KDF_LENGTH].copy_from_slice(&psk_id_hash);
+    key_schedule_context[1 + KDF_LENGTH..].copy_from_slice(&info_hash);
+
+    let (_, secret) = labeled_extract(&suite_id, shared_secret, b"secret", psk);
+
+    let mut context = Context {
+        aead,
+        key: [0u8; MAX_KEY_LENGTH],
+        base_nonce: [0u8; NONCE_LENGTH],
+        seq: 0,
+        exporter_secret: [0u8; KDF_LENGTH],
+    };
+    if aead != Aead::ExportOnly {
+        let key_length = aead.key_length();
+        labeled_expand(
+            &secret,
+            &suite_id,
+            b"key",
+            &key_schedule_context,
+            &mut context.key[..key_length],
+        )?;
+        labeled_expand(
+            &secret,
+            &suite_id,
+            b"base_nonce",
+            &key_schedule_context,
+            &mut context.base_nonce,
+        )?;
+    }
+    labeled_expand(
+        &secret,
+        &suite_id,
+        b"exp",
+        &key_schedule_context,
+        &mut context.exporter_secret,
+    )?;
+    Ok(context)
+}
+
+/// The suite_id of HPKE: "HPKE" || I2OSP(kem_id, 2) || I2OSP(kdf_id, 2) || I2OSP(aead_id, 2)
+fn suite_id(aead: Aead) -> [u8; 10] {
+    let mut suite_id = *b"HPKE\0\0\0\0\0\0";
+    suite_id[4..6].copy_from_slice(&KEM_ID.to_be_bytes());
+    suite_id[6..8].copy_from_slice(&KDF_ID.to_be_bytes());
+    suite_id[8..].copy_from_slice(&aead.id().to_be_bytes());
+    suite_id
+}
+
+/// Encrypts or decrypts with the AEAD `A`
+fn crypt<A: KeyInit + aes_gcm::aead::Aead>(
+    key: &[u8],
+    nonce: &[u8; NONCE_LENGTH],
+    payload: Payload,
+    encrypt: bool,
+) -> Result<Vec<u8>, aes_gcm::aead::Error> {
+    let cipher = A::new_from_slice(key).expect("the key length matches the AEAD");
+    if encrypt {
+        cipher.encrypt(GenericArray::from_slice(nonce), payload)
+    } else {
+        cipher.decrypt(GenericArray::from_slice(nonce), payload)
+    }
+}
+
+impl Context {
+    /// Returns the nonce for the current sequence number, the base nonce XORed with the sequence number
+    fn compute_nonce(&self) -> [u8; NONCE_LENGTH] {
+        let mut nonce = self.base_nonce;
+        for (byte, seq) in nonce[NONCE_LENGTH - 8..]
+            .iter_mut()
+            .zip(self.seq.to_be_bytes())
+        {
+            *byte ^= seq;
+        }
+        nonce
+    }
+
+    /// Encrypts or decrypts with the current nonce, and increments the sequence number on success
+    fn crypt(&mut self, aad: &[u8], msg: &[u8], encrypt: bool) -> Result<Vec<u8>, HpkeError> {
+        if self.aead == Aead::ExportOnly {
+            return Err(HpkeError::ExportOnly);
+        }
+        // The sequence number is 64 bits, so it never reaches the limit of 2^96 - 1 of RFC 9180
+        let next_seq = self
+            .seq
+            .checked_add(1)
+            .ok_or(HpkeError::MessageLimitReached)?;
+
+        let nonce = self.compute_nonce();
+        let key = &self.key[..self.aead.key_length()];
+        let payload = Payload { msg, aad };
+        let output = match self.aead {
+            Aead::Aes128Gcm => crypt::<Aes128Gcm>(key, &nonce, payload, encrypt),
+            Aead::Aes256Gcm => crypt::<Aes256Gcm>(key, &nonce, payload, encrypt),
+            Aead::ChaCha20Poly1305 => crypt::<ChaCha20Poly1305>(key, &nonce, payload, encrypt),
+            Aead::ExportOnly => unreachable!(),
+        };
+        let output = output.map_err(|_| {
+            if encrypt {
+                HpkeError::SealFailed
+            } else {
+                HpkeError::OpenFailed
+            }
+        })?;
+
+        self.seq = next_seq;
+        Ok(output)
+    }
+
+    /// Derives `length` bytes from the exporter secret and `exporter_context`
+    fn export(&self, exporter_context: &[u8], length: usize) -> Result<Vec<u8>, HpkeError> {
+        let exporter = Hkdf::<Sha512>::from_prk(&self.exporter_secret)
+            .expect("the exporter secret has the length of the hash");
+        let mut output = vec![0u8; length];
+        labeled_expand(
+            &exporter,
+            &suite_id(self.aead),
+            b"sec",
+            exporter_context,
+            &mut output,
+        )?;
+        Ok(output)
+    }
+}
+
+/// The context of the sender, which encrypts messages to the recipient
+pub struct SenderContext(Context);
+
+/// The context of the recipient, which decrypts messages from the sender
+pub struct RecipientContext(Context);
+
+impl SenderContext {
+    pub(crate) fn new(
+        mode: u8,
+        aead: Aead,
+        shared_secret: &[u8],
+        info: &[u8],
+        psk: Option<&Psk>,
+    ) -> Result<SenderContext, HpkeError> {
+        key_schedule(mode, aead, shared_secret, info, psk).map(SenderContext)
+    }
+
+    /// Encrypts the next message, authenticating `aad` along with it
+    pub fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        self.0.crypt(aad, plaintext, true)
+    }
+
+    /// Derives `length` bytes of secret from the context, at most 255 * 64 bytes
+    pub fn export(&self, exporter_context: &[u8], length: usize) -> Result<Vec<u8>, HpkeError> {
+        self.0.export(exporter_context, length)
+    }
+}
+
+impl RecipientContext {
+    pub(crate) fn new(
+        mode: u8,
+        aead: Aead,
+        shared_secret: &[u8],
+        info: &[u8],
+        psk: Option<&Psk>,
+    ) -> Result<RecipientContext, HpkeError> {
+        key_schedule(mode, aead, shared_secret, info, psk).map(RecipientContext)
+    }
+
+    /// Decrypts the next message, which must have been sealed with the same `aad`
+    pub fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        self.0.crypt(aad, ciphertext, false)
+    }
+
+    /// Derives `length` bytes of secret from the context, at most 255 * 64 bytes
+    pub fn export(&self, exporter_context: &[u8], length: usize) -> Result<Vec<u8>, HpkeError> {
+        self.0.export(exporter_context, length)
+    }
+}
+
+#[cfg(test)]
+impl SenderContext {
+    pub(crate) fn secrets(&self) -> (&[u8], &[u8], &[u8]) {
+        let context = &self.0;
+        (
+            &context.key[..context.aead.key_length()],
+            &context.base_nonce,
+            &context.exporter_secret,
+        )
+    }
+}
@@ -0,0 +1,128 @@
+// DHKEM(X448, HKDF-SHA512), see RFC 9180 section 4.1
+
+use super::{labeled_expand, labeled_extract, HpkeError, KEM_ID};
+use crate::x448::{PublicKey, StaticSecret, X448_KEY_LENGTH};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The length of the shared secret of the KEM, Nsecret
+pub(crate) const KEM_SECRET_LENGTH: usize = 64;
+
+/// The suite_id of the KEM: "KEM" || I2OSP(kem_id, 2)
+const KEM_SUITE_ID: [u8; 5] = [b'K', b'E', b'M', (KEM_ID >> 8) as u8, KEM_ID as u8];
+
+/// Derives a key pair from the input keying material `ikm`, see DeriveKeyPair in RFC 9180 section 7.1.3
+///
+/// `ikm` should have at least 56 bytes of entropy. The secret key is the output of the KDF
+/// as it is, it is clamped whenever it is used.
+pub fn derive_key_pair(ikm: &[u8]) -> (StaticSecret, PublicKey) {
+    let (_, dkp_prk) = labeled_extract(&KEM_SUITE_ID, &[], b"dkp_prk", ikm);
+
+    #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+    let mut bytes = [0u8; X448_KEY_LENGTH];
+    labeled_expand(&dkp_prk, &KEM_SUITE_ID, b"sk", &[], &mut bytes)
+        .expect("56 bytes is a valid length for HKDF-SHA512");
+    let secret = StaticSecret::from(bytes);
+
+    #[cfg(feature = "zeroize")]
+    bytes.zeroize();
+
+    let public = PublicKey::from(&secret);
+    (secret, public)
+}
+
+/// Computes the shared secret from the Diffie-Hellman outputs and the KEM context
+fn extract_and_expand(dh: &[u8], kem_context: &[u8]) -> [u8; KEM_SECRET_LENGTH] {
+    let (_, eae_prk) = labeled_extract(&KEM_SUITE_ID, &[], b"eae_prk", dh);
+    let mut shared_secret = [0u8; KEM_SECRET_LENGTH];
+    labeled_expand(
+        &eae_prk,
+        &KEM_SUITE_ID,
+        b"shared_secret",
+        kem_context,
+        &mut shared_secret,
+    )
+    .expect("64 bytes is a valid length for HKDF-SHA512");
+    shared_secret
+}
+
+/// Computes DH(sk, pk) into `out`, rejecting the all-zero output of small order public keys
+fn diffie_hellman(sk: &StaticSecret, pk: &PublicKey, out: &mut [u8]) -> Result<(), HpkeError> {
+    let shared = sk.diffie_hellman(pk).ok_or(HpkeError::InvalidPublicKey)?;
+    out.copy_from_slice(shared.as_bytes());
+    Ok(())
+}
+
+/// Encap and AuthEncap with the ephemeral secret `sk_e`, returning the shared secret and enc
+///
+/// The sender is authenticated if `sk_s` is given.
+pub(crate) fn encap(
+    sk_e: &StaticSecret,
+    pk_r: &PublicKey,
+    sk_s: Option<&StaticSecret>,
+) -> Result<([u8; KEM_SECRET_LENGTH], [u8; X448_KEY_LENGTH]), HpkeError> {
+    let enc = PublicKey::from(sk_e).to_bytes();
+
+    let mut dh = [0u8; 2 * X448_KEY_LENGTH];
+    let mut kem_context = [0u8; 3 * X448_KEY_LENGTH];
+    kem_context[..X448_KEY_LENGTH].copy_from_slice(&enc);
+    kem_context[X448_KEY_LENGTH..2 * X448_KEY_LENGTH].copy_from_slice(pk_r.as_bytes());
+
+    let result = diffie_hellman(sk_e, pk_r, &mut dh[..X448_KEY_LENGTH]).and_then(|_| {
+        let length = match sk_s {
+            Some(sk_s) => {
+                diffie_hellman(sk_s, pk_r, &mut dh[X448_KEY_LENGTH..])?;
+                kem_context[2 * X448_KEY_LENGTH..]
+                    .copy_from_slice(PublicKey::from(sk_s).as_bytes());
+                2 * X448_KEY_LENGTH
+            }
+            None => X448_KEY_LENGTH,
+        };
+        Ok(extract_and_expand(
+            &dh[..length],
+            &kem_context[..length + X448_KEY_LENGTH],
+        ))
+    });
+
+    #[cfg(feature = "zeroize")]
+    dh.zeroize();
+
+    result.map(|shared_secret| (shared_secret, enc))
+}
+
+/// Decap and AuthDecap, returning the shared secret
+///
+/// The sender is authenticated if `pk_s` is given.
+pub(crate) fn decap(
+    enc: &[u8; X448_KEY_LENGTH],
+    sk_r: &StaticSecret,
+    pk_s: Option<&PublicKey>,
+) -> Result<[u8; KEM_SECRET_LENGTH], HpkeError> {
+    let pk_e = PublicKey::from(*enc);
+
+    let mut dh = [0u8; 2 * X448_KEY_LENGTH];
+    let mut kem_context = [0u8; 3 * X448_KEY_LENGTH];
+    kem_context[..X448_KEY_LENGTH].copy_from_slice(enc);
+    kem_context[X448_KEY_LENGTH..2 * X448_KEY_LENGTH]
+        .copy_from_slice(PublicKey::from(sk_r).as_bytes());
+
+    let result = diffie_hellman(sk_r, &pk_e, &mut dh[..X448_KEY_LENGTH]).and_then(|_| {
+        let length = match pk_s {
+            Some(pk_s) => {
+                diffie_hellman(sk_r, pk_s, &mut dh[X448_KEY_LENGTH..])?;
+                kem_context[2 * X448_KEY_LENGTH..].copy_from_slice(pk_s.as_bytes());
+                2 * X448_KEY_LENGTH
+            }
+            None => X448_KEY_LENGTH,
+        };
+        Ok(extract_and_expand(
+            &dh[..length],
+            &kem_context[..length + X448_KEY_LENGTH],
+        ))
+    });
+
+    #[cfg(feature = "zeroize")]
+    dh.zeroize();
+
+    result
+}
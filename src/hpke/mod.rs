@@ -0,0 +1,776 @@
+// This is the module for HPKE, as specified in RFC 9180, with the KEM DHKEM(X448, HKDF-SHA512)
+// https://www.rfc-editor.org/rfc/rfc9180
+//
+// The KDF of the key schedule is HKDF-SHA512 as well, and the AEAD is one of AES-128-GCM,
+// AES-256-GCM and ChaCha20Poly1305, or none in the export-only mode. The Diffie-Hellman function
+// is X448 from the `x448` module, which rejects the all-zero output of small order public keys.
+//
+// All four modes are supported: the sender can prove the possession of a pre-shared key (PSK),
+// of a static X448 key (Auth), or both (AuthPsk). The encapsulated key `enc` is the ephemeral
+// X448 public key of the sender.
+
+mod context;
+mod kem;
+
+pub use context::{RecipientContext, SenderContext};
+pub use kem::derive_key_pair;
+
+use crate::x448::{PublicKey, StaticSecret, X448_KEY_LENGTH};
+use hkdf::{Hkdf, HkdfExtract};
+use rand_core::{CryptoRng, RngCore};
+use sha2::digest::Output;
+use sha2::Sha512;
+use std::fmt;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The identifier of DHKEM(X448, HKDF-SHA512)
+pub const KEM_ID: u16 = 0x0021;
+
+/// The identifier of HKDF-SHA512
+pub const KDF_ID: u16 = 0x0003;
+
+/// The length of the encapsulated key, Nenc
+pub const ENCAPPED_KEY_LENGTH: usize = X448_KEY_LENGTH;
+
+/// The AEAD which encrypts the messages
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Aead {
+    /// AES-128-GCM
+    Aes128Gcm,
+    /// AES-256-GCM
+    Aes256Gcm,
+    /// ChaCha20Poly1305
+    ChaCha20Poly1305,
+    /// No encryption, the context can only be used to export secrets
+    ExportOnly,
+}
+
+impl Aead {
+    /// Returns the identifier of the AEAD in RFC 9180
+    pub fn id(&self) -> u16 {
+        match self {
+            Aead::Aes128Gcm => 0x0001,
+            Aead::Aes256Gcm => 0x0002,
+            Aead::ChaCha20Poly1305 => 0x0003,
+            Aead::ExportOnly => 0xffff,
+        }
+    }
+
+    /// Returns the length of the key, Nk
+    fn key_length(&self) -> usize {
+        match self {
+            Aead::Aes128Gcm => 16,
+            Aead::Aes256Gcm | Aead::ChaCha20Poly1305 => 32,
+            Aead::ExportOnly => 0,
+        }
+    }
+}
+
+/// A pre-shared key and its identifier, which must both be non-empty
+///
+/// The PSK should have at least 32 bytes of entropy.
+#[derive(Copy, Clone, Debug)]
+pub struct Psk<'a> {
+    pub psk: &'a [u8],
+    pub psk_id: &'a [u8],
+}
+
+/// The mode of the sender, with the PSK and static secret key it authenticates with
+#[derive(Copy, Clone, Debug)]
+pub enum SenderMode<'a> {
+    Base,
+    Psk(Psk<'a>),
+    Auth(&'a StaticSecret),
+    AuthPsk(&'a StaticSecret, Psk<'a>),
+}
+
+/// The mode of the recipient, with the PSK and static public key of the sender
+#[derive(Copy, Clone, Debug)]
+pub enum RecipientMode<'a> {
+    Base,
+    Psk(Psk<'a>),
+    Auth(&'a PublicKey),
+    AuthPsk(&'a PublicKey, Psk<'a>),
+}
+
+impl SenderMode<'_> {
+    fn id(&self) -> u8 {
+        match self {
+            SenderMode::Base => 0,
+            SenderMode::Psk(_) => 1,
+            SenderMode::Auth(_) => 2,
+            SenderMode::AuthPsk(_, _) => 3,
+        }
+    }
+}
+
+impl RecipientMode<'_> {
+    fn id(&self) -> u8 {
+        match self {
+            RecipientMode::Base => 0,
+            RecipientMode::Psk(_) => 1,
+            RecipientMode::Auth(_) => 2,
+            RecipientMode::AuthPsk(_, _) => 3,
+        }
+    }
+}
+
+/// Errors which can occur when setting up a context, encrypting, decrypting or exporting
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HpkeError {
+    /// The PSK or its identifier is empty
+    InvalidPsk,
+    /// A public key is of small order, so the Diffie-Hellman output is all zero
+    InvalidPublicKey,
+    /// The message is too long for the AEAD
+    SealFailed,
+    /// The ciphertext is not authentic, or the keys, PSK, info or aad do not match
+    OpenFailed,
+    /// The sequence number of the context is exhausted
+    MessageLimitReached,
+    /// The context was set up without an AEAD and can only export secrets
+    ExportOnly,
+    /// The exported secret would be longer than 255 * 64 bytes
+    InvalidExportLength,
+}
+
+impl fmt::Display for HpkeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HpkeError::InvalidPsk => write!(f, "empty PSK or PSK identifier"),
+            HpkeError::InvalidPublicKey => write!(f, "public key of small order"),
+            HpkeError::SealFailed => write!(f, "encryption failed"),
+            HpkeError::OpenFailed => write!(f, "decryption failed"),
+            HpkeError::MessageLimitReached => write!(f, "message limit reached"),
+            HpkeError::ExportOnly => write!(f, "the context is export-only"),
+            HpkeError::InvalidExportLength => write!(f, "exported secret is too long"),
+        }
+    }
+}
+
+impl std::error::Error for HpkeError {}
+
+/// LabeledExtract(salt, label, ikm) = Extract(salt, "HPKE-v1" || suite_id || label || ikm)
+///
+/// Returns the pseudorandom key, both as bytes and ready for expansion.
+fn labeled_extract(
+    suite_id: &[u8],
+    salt: &[u8],
+    label: &[u8],
+    ikm: &[u8],
+) -> (Output<Sha512>, Hkdf<Sha512>) {
+    let mut extract = HkdfExtract::<Sha512>::new(Some(salt));
+    extract.input_ikm(b"HPKE-v1");
+    extract.input_ikm(suite_id);
+    extract.input_ikm(label);
+    extract.input_ikm(ikm);
+    extract.finalize()
+}
+
+/// LabeledExpand(prk, label, info, L) = Expand(prk, I2OSP(L, 2) || "HPKE-v1" || suite_id || label || info, L)
+///
+/// L is the length of `okm`, which can be at most 255 * 64 bytes.
+fn labeled_expand(
+    prk: &Hkdf<Sha512>,
+    suite_id: &[u8],
+    label: &[u8],
+    info: &[u8],
+    okm: &mut [u8],
+) -> Result<(), HpkeError> {
+    let length = u16::try_from(okm.len()).map_err(|_| HpkeError::InvalidExportLength)?;
+    prk.expand_multi_info(
+        &[&length.to_be_bytes(), b"HPKE-v1", suite_id, label, info],
+        okm,
+    )
+    .map_err(|_| HpkeError::InvalidExportLength)
+}
+
+/// Sets up the context of the sender with the given ephemeral secret key
+fn setup_sender_with_ephemeral(
+    mode: &SenderMode,
+    aead: Aead,
+    pk_r: &PublicKey,
+    info: &[u8],
+    sk_e: &StaticSecret,
+) -> Result<([u8; ENCAPPED_KEY_LENGTH], SenderContext), HpkeError> {
+    let (sk_s, psk) = match mode {
+        SenderMode::Base => (None, None),
+        SenderMode::Psk(psk) => (None, Some(psk)),
+        SenderMode::Auth(sk_s) => (Some(*sk_s), None),
+        SenderMode::AuthPsk(sk_s, psk) => (Some(*sk_s), Some(psk)),
+    };
+
+    #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+    let (mut shared_secret, enc) = kem::encap(sk_e, pk_r, sk_s)?;
+    let context = SenderContext::new(mode.id(), aead, &shared_secret, info, psk);
+
+    #[cfg(feature = "zeroize")]
+    shared_secret.zeroize();
+
+    Ok((enc, context?))
+}
+
+/// Sets up the context of the sender, see SetupBaseS, SetupPSKS, SetupAuthS and SetupAuthPSKS in RFC 9180
+///
+/// Returns the encapsulated key, which is sent to the recipient, and the context.
+pub fn setup_sender<R: RngCore + CryptoRng>(
+    mode: &SenderMode,
+    aead: Aead,
+    pk_r: &PublicKey,
+    info: &[u8],
+    rng: &mut R,
+) -> Result<([u8; ENCAPPED_KEY_LENGTH], SenderContext), HpkeError> {
+    let mut ikm = [0u8; X448_KEY_LENGTH];
+    rng.fill_bytes(&mut ikm);
+    let (sk_e, _) = derive_key_pair(&ikm);
+
+    #[cfg(feature = "zeroize")]
+    ikm.zeroize();
+
+    setup_sender_with_ephemeral(mode, aead, pk_r, info, &sk_e)
+}
+
+/// Sets up the context of the recipient, see SetupBaseR, SetupPSKR, SetupAuthR and SetupAuthPSKR in RFC 9180
+pub fn setup_recipient(
+    mode: &RecipientMode,
+    aead: Aead,
+    enc: &[u8; ENCAPPED_KEY_LENGTH],
+    sk_r: &StaticSecret,
+    info: &[u8],
+) -> Result<RecipientContext, HpkeError> {
+    let (pk_s, psk) = match mode {
+        RecipientMode::Base => (None, None),
+        RecipientMode::Psk(psk) => (None, Some(psk)),
+        RecipientMode::Auth(pk_s) => (Some(*pk_s), None),
+        RecipientMode::AuthPsk(pk_s, psk) => (Some(*pk_s), Some(psk)),
+    };
+
+    #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+    let mut shared_secret = kem::decap(enc, sk_r, pk_s)?;
+    let context = RecipientContext::new(mode.id(), aead, &shared_secret, info, psk);
+
+    #[cfg(feature = "zeroize")]
+    shared_secret.zeroize();
+
+    context
+}
+
+/// Encrypts a single message to the recipient, returning the encapsulated key and the ciphertext
+pub fn seal<R: RngCore + CryptoRng>(
+    mode: &SenderMode,
+    aead: Aead,
+    pk_r: &PublicKey,
+    info: &[u8],
+    aad: &[u8],
+    plaintext: &[u8],
+    rng: &mut R,
+) -> Result<([u8; ENCAPPED_KEY_LENGTH], Vec<u8>), HpkeError> {
+    let (enc, mut context) = setup_sender(mode, aead, pk_r, info, rng)?;
+    let ciphertext = context.seal(aad, plaintext)?;
+    Ok((enc, ciphertext))
+}
+
+/// Decrypts a single message which was encrypted with [`seal`]
+pub fn open(
+    mode: &RecipientMode,
+    aead: Aead,
+    enc: &[u8; ENCAPPED_KEY_LENGTH],
+    sk_r: &StaticSecret,
+    info: &[u8],
+    aad: &[u8],
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, HpkeError> {
+    let mut context = setup_recipient(mode, aead, enc, sk_r, info)?;
+    context.open(aad, ciphertext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+    use rand_chacha::rand_core::SeedableRng;
+
+    struct Vector {
+        mode: u8,
+        aead: Aead,
+        info: &'static [u8],
+        ikm_r: &'static [u8],
+        ikm_e: &'static [u8],
+        ikm_s: &'static [u8],
+        psk: &'static [u8],
+        psk_id: &'static [u8],
+        sk_rm: &'static [u8],
+        pk_rm: &'static [u8],
+        pk_sm: &'static [u8],
+        enc: &'static [u8],
+        key: &'static [u8],
+        base_nonce: &'static [u8],
+        exporter_secret: &'static [u8],
+        // (aad, pt, ct) for the sequence numbers 0 and 1
+        encryptions: &'static [(&'static [u8], &'static [u8], &'static [u8])],
+        // (exporter_context, exported_value) with L = 32
+        exports: &'static [(&'static [u8], &'static [u8])],
+    }
+
+    // The vectors with kem_id 0x0021 and kdf_id 0x0003 from the test-vectors.json of the RFC 9180 draft repository
+    const VECTORS: &[Vector] = &[
+        Vector {
+            mode: 0,
+            aead: Aead::Aes128Gcm,
+            info: &hex!("4f6465206f6e2061204772656369616e2055726e"),
+            ikm_r: &hex!("e4228208165477bd7e6fd51dbd5e1261234b4e5de5e83643b99bee8d4c6d76e0e702a14535b3f7748399d5e95e6abaedf88ab9ed08e627bd"),
+            ikm_e: &hex!("28001d9a01eb2f2738a713d4785d139b8fc68a9393eb4e13fff9678c83fe26249748c692cd3f7664b930a40b37906131377f9481ba84a885"),
+            ikm_s: &hex!(""),
+            psk: &hex!(""),
+            psk_id: &hex!(""),
+            sk_rm: &hex!("b59d33ccc522678b38224e14f46197b9f3d54d23ee6f3d93b971d6901863038b6c2d0a1ae85cb0b0f57e6f738a571552a1d4d2a69321c4f4"),
+            pk_rm: &hex!("66614788404568d059741319ed47991d42a545a56c2ffc51738460b4338342aa4ee6d48a4eaf6b4490f86185cd17f443925964f3dfbf03f5"),
+            pk_sm: &hex!(""),
+            enc: &hex!("0aea40233b445e66f997ce3efe0584e4609b9f4ea217074aed73fe4b36aecaaf55897530e55bea8cd18360ca4dbcac0966cb3deb8f5aad85"),
+            key: &hex!("7acd507e78e52c19d8e0d77046e97fd9"),
+            base_nonce: &hex!("ef8694a7ae7a9c221d36432c"),
+            exporter_secret: &hex!("6b0b03ecd48acc6e7a9661b2397b8cc66fd5bdaedc584250bd094d39badf4a0599639742194d1678337338d6256bae82b529fdaff13ca81467552cae3b83c115"),
+            encryptions: &[
+                (&hex!("436f756e742d30"), &hex!("4265617574792069732074727574682c20747275746820626561757479"), &hex!("ede82da58afd1cd19165542875456530df4c92ede352d427bccabf08884a22b81e3da8e0055c637d549fb11f40")),
+                (&hex!("436f756e742d31"), &hex!("4265617574792069732074727574682c20747275746820626561757479"), &hex!("e70a0d29006e5e8a7009caa16a3499d2ac0f661f18e7f9cef3fe6813ae58304ab5df756cde658e184d41548382")),
+            ],
+            exports: &[
+                (&hex!(""), &hex!("ab89681d22a7940e702374bbdb782d5cc911feea6f1c27f8cfba15367282fca2")),
+                (&hex!("00"), &hex!("6ed2455955d533ca777d60bb24755a1467a747fc3b37d9eebe154014321b8f7c")),
+                (&hex!("54657374436f6e74657874"), &hex!("1293be0746dadac170a94e56971204b34b1eba8375f1e8acaf5775f171796723")),
+            ],
+        },
+        Vector {
+            mode: 1,
+            aead: Aead::Aes128Gcm,
+            info: &hex!("4f6465206f6e2061204772656369616e2055726e"),
+            ikm_r: &hex!("659d357f67d539ea93e85be062b62fdd1c1d805b2e60dd3617eb86b8a3e135e5304fcb8b375df7b44fd60df053ca3c93f9ede651a8c5c7ea"),
+            ikm_e: &hex!("8cdbdcdbdef748c6282896b51dff1c92e3d6151313f02725cfe4dc69da4ced34cb49748bc7fd987158352abe9f0638f79e6751fcf7202e3d"),
+            ikm_s: &hex!(""),
+            psk: &hex!("0247fd33b913760fa1fa51e1892d9f307fbe65eb171e8132c2af18555a738b82"),
+            psk_id: &hex!("456e6e796e20447572696e206172616e204d6f726961"),
+            sk_rm: &hex!("ebb63c56b0e8248374a87b8cd4ae3fb3122651bbe89a7fe614972dc10a89a391e5d32429f1c9ba32a7b70f936ee5a285766e7480714fc923"),
+            pk_rm: &hex!("407a582531f4d45125732a39a315fb3ff1665e0375cd1b975364474ccf9fa83f7b8f9b4ab4ed154619bb4130f299789bab01473884595c57"),
+            pk_sm: &hex!(""),
+            enc: &hex!("4b0e5a62bedd3ee8ce257f048675980a2c9431bc4c2b3679887e3675148a690376c5f8ab73fb5db1d56251cdb22c2d7dfe3452a3c1f68886"),
+            key: &hex!("39f795e15e3b297f7cb9bfb533c14036"),
+            base_nonce: &hex!("ecb7926e7ac3de323dd0b6e4"),
+            exporter_secret: &hex!("49bbe2cbbdaed6e3c4ff5702a53a0c18c638052cba22a18d8854c5c3603a964bf0501ed548febd35da3d88fcfa3c77b8cb097258e80759441cc38ed6ba608408"),
+            encryptions: &[
+                (&hex!("436f756e742d30"), &hex!("4265617574792069732074727574682c20747275746820626561757479"), &hex!("cab1c86059737935d9288d666c9f97e56bc4d51417a7cf16683396f121d893806fda0c3c11f2095df8a9b87a54")),
+                (&hex!("436f756e742d31"), &hex!("4265617574792069732074727574682c20747275746820626561757479"), &hex!("5bdb385e55730ba9b03bf6b6d91fb05f190d83d4dced275d69753964ce533bd1b17da8bd4f13b65d2fde7eeb2b")),
+            ],
+            exports: &[
+                (&hex!(""), &hex!("9c4cfc5497fc175da27edd0904e7f8d7d232ffaace6832446a67a8ea581e2428")),
+                (&hex!("00"), &hex!("ef02beef8d82835221a053d2fb483a2509ac68897343bd095f00a2977ad652bd")),
+                (&hex!("54657374436f6e74657874"), &hex!("1242eb6b4aedb5e7f2c5d996d109b30e446541ae4d5864eabb268448d7170cae")),
+            ],
+        },
+        Vector {
+            mode: 2,
+            aead: Aead::Aes128Gcm,
+            info: &hex!("4f6465206f6e2061204772656369616e2055726e"),
+            ikm_r: &hex!("1263af791f251d642bff5763141389f31e4afaf77f67f420b51e0e6764acfde0c618d0628f91c4d4151b56c646c4c683da1e72caf476e030"),
+            ikm_e: &hex!("d0eadd52d001c27c4672a95e2acf070a0de416600ac31a8ee1b523b748acd13241a25bbd19c043b5d93a7eefeaa1e2ed213edd4921978da8"),
+            ikm_s: &hex!("b80515f826811be1eef89883071c97b9582138815de32cfe166d360bb34d30d4d1c8d01317352d84bf09c1eb86e31035e416283c077bc917"),
+            psk: &hex!(""),
+            psk_id: &hex!(""),
+            sk_rm: &hex!("7230e55eccc935b606d814b8c8f52c5652f2b77755ecf25f27ba748f4b44e7c9035efdcbb62b8709d0ffb52008530809f607931277b4cc91"),
+            pk_rm: &hex!("5f340db11681ca2c98546235ccaaf5b20f633ec750fc09c353a28df0cdd851a89d1b31df61e8b5450b08bd77a4d3860116b2e16f8db358b0"),
+            pk_sm: &hex!("c04dab742a70bc1626d85013537c7595997e774c12d1b48b938e05e0df26a521fb519c458565f2dd062ee3c90310f7762ba8ecdfeb26495c"),
+            enc: &hex!("169096591292f2dd46698209f820eb3fee58610c801d375b7ab1d05797114ab6efe206a026cf55209ee3e5be02515c2395d66e1ffbb8d7a5"),
+            key: &hex!("39f928fd01ab69fd1ae98745d3d5cee2"),
+            base_nonce: &hex!("e763503a1e4610955b238414"),
+            exporter_secret: &hex!("5b25b3709d57a5d1acbe3fb78ac63db0f2aac095206ac5a9c64f63a55dbb4411338f083809660e782fedec79ca459fad155ae15abebcd959e1e5a5861e2fc7aa"),
+            encryptions: &[
+                (&hex!("436f756e742d30"), &hex!("4265617574792069732074727574682c20747275746820626561757479"), &hex!("bae206ce434275939be05eee854becce0ba3c2aea77c5991bd88ac5d440a78b80f538f0cfc1dc0ce7a60711f97")),
+                (&hex!("436f756e742d31"), &hex!("4265617574792069732074727574682c20747275746820626561757479"), &hex!("f8efe1b52090d5c50efb2d541228689b8c7ec0019dd17886b5f86cd8e6001fd95f92edd9bea9dad91a046ce576")),
+            ],
+            exports: &[
+                (&hex!(""), &hex!("37d633b43ec52edf256b769114701b6152f8d922536a115d680ed9cd58d2292f")),
+                (&hex!("00"), &hex!("ad83c91132fad6c70e5d8ab2ee6de38758a1c740cb6d69baac5f55b1d6713ca6")),
+                (&hex!("54657374436f6e74657874"), &hex!("baa58363eb069726ac344933a11b0bd82292c89a1f5ef75b393176886cf379dd")),
+            ],
+        },
+        Vector {
+            mode: 3,
+            aead: Aead::Aes128Gcm,
+            info: &hex!("4f6465206f6e2061204772656369616e2055726e"),
+            ikm_r: &hex!("1b5825b7e4e12ffe9850bb1d6fcae6c1993380982cfe3b328cc0c83558aca1e05fde8d92f0ae82308a4c0167970a9c3d2ae4a722e3e56ae0"),
+            ikm_e: &hex!("da4a83ad6352dce79b6a0d7a96338670fcded42abc00de8cd155086a9d0dc91bfe0961b0645279f68cac01b6c99666861331a36ed0b88305"),
+            ikm_s: &hex!("0bd9e7fc70aeb8c8372db9c3f9fb19895cee7c049b381301a05ad7cfd2be2de46598a895dcd1fd3e6e0e95eeda481ebc14dd64688d17c24e"),
+            psk: &hex!("0247fd33b913760fa1fa51e1892d9f307fbe65eb171e8132c2af18555a738b82"),
+            psk_id: &hex!("456e6e796e20447572696e206172616e204d6f726961"),
+            sk_rm: &hex!("78d6921806284e036415bb7288995a8edfb6378ed27bb4fe119299030c81227b324afa94d79963903240f2bfddc9d8963a529578ae6909df"),
+            pk_rm: &hex!("e932b05161f2ab2de7c7e9ee7da7e9ad5a6c61b1d06bb5fa1f510d8066b974a1a7905f83908e0e78b50224ba45b1d01f5e719358b1930ff4"),
+            pk_sm: &hex!("906a22a7b4eba6e3620646e9ee036b1b9ebcf8f914cf5a0e2dfd32df67d866715ce5c6f90fe0b30e1d6fc6e2439d62239f77963e024e9257"),
+            enc: &hex!("47b06ca32bcbbac329566113c27752ba508bc89d8c69f8fa8d8355764ac1e784bc20212b14981fa2d45e82d77b9f7e97e0d468e1398861f3"),
+            key: &hex!("fcf4baa0cf9fc4fa01ab2829fef9f087"),
+            base_nonce: &hex!("c88b7e625d7b6ea3e834866e"),
+            exporter_secret: &hex!("9dc95cf4fe755e506a8dca9ba68b6016a5c78decbb298bd57e3c1acc1f4be13389c26ee15add9f52140aba723669c86a81f0b2ce528a3f9f830b45a340d96401"),
+            encryptions: &[
+                (&hex!("436f756e742d30"), &hex!("4265617574792069732074727574682c20747275746820626561757479"), &hex!("b56a272eaa9635cc682d47f1e44b4f26499a07618c4f09f11b48ebb8bafca4ca88f39ad4c1c2867373f37605b3")),
+                (&hex!("436f756e742d31"), &hex!("4265617574792069732074727574682c20747275746820626561757479"), &hex!("369e7e28df86be4fe40ee1af8c8c2da042fef27f8234dcbceb3be5f6dd07c96ef8a0815779fbf53db4ebc61d37")),
+            ],
+            exports: &[
+                (&hex!(""), &hex!("1b2b9ccc981f6a5bb6b7b102321c6ce7ad8953f4a52b2ef04c18531af4d48adc")),
+                (&hex!("00"), &hex!("ab988e047ab4ed198a091be0d7d6edac1ad9e34e7b441a9c1c8a4f6d0b175407")),
+                (&hex!("54657374436f6e74657874"), &hex!("14c1213dc56398617089c7369f6b7bcb42f319f38eb8d0645ce50bcb37e6b877")),
+            ],
+        },
+        Vector {
+            mode: 0,
+            aead: Aead::Aes256Gcm,
+            info: &hex!("4f6465206f6e2061204772656369616e2055726e"),
+            ikm_r: &hex!("93e714430d3cb00e8e8a03dd820dcbcc7f0141f93c63a7dede2dfb152b5b23982a1a55f2d86dd9e0f5a0f53b9c21605257ec1349d7f89e53"),
+            ikm_e: &hex!("39ed47496020ec7c2afc214425fc6a15fb6f1e16759c2b066265b6624c84ed50ee6c3129d9ed71318b19a96e5c5cc6b27aca5e1ae9cdc7e0"),
+            ikm_s: &hex!(""),
+            psk: &hex!(""),
+            psk_id: &hex!(""),
+            sk_rm: &hex!("c4e72a57af1640806c01617b947ee6d1bbe5eb1a5b4616fb705a5d2ed30b7f4317365c504249750e090805d44a2ddc2970172414a90a09e5"),
+            pk_rm: &hex!("d920db89afdb25df110a44cf0d7dc4e4d4b74f09ceaba5e76a12d3cafefcd962e244804a58bfd12303732be21d511f877ddc2ed694447b3d"),
+            pk_sm: &hex!(""),
+            enc: &hex!("390f2971ca97d513915a2bc5aac0cb81b832d9424d2264eaa9e868d80862edd7918276883a8d0434309e049408fec2340ae5799702f948d7"),
+            key: &hex!("5011eed55726d94fae0cd116b80e7832ecde3a457ef816a4a42f862ec2820ade"),
+            base_nonce: &hex!("c9899ce0c487a96933695f69"),
+            exporter_secret: &hex!("775a6404afd0eaeec9e0806a55332118f5fd7ec983e1cbf69d0fe9ce197d8f8ab64fa31de4b7f4db637eea2157a6d9c294840ad4db7b3d2542f310e04be2bbfd"),
+            encryptions: &[
+                (&hex!("436f756e742d30"), &hex!("4265617574792069732074727574682c20747275746820626561757479"), &hex!("6a5ef0f8c88a17c6d26bee63b4468cd43360eb69804fb392d8c9b8eba2f9bd806726c7d99cb9073022000ce41a")),
+                (&hex!("436f756e742d31"), &hex!("4265617574792069732074727574682c20747275746820626561757479"), &hex!("0f1b8fa3a61ead5f4cee5362eff2bcbf0f9a1c16c550365f022351fd939e91714a59171b00a7bd642b5ae929ed")),
+            ],
+            exports: &[
+                (&hex!(""), &hex!("596003579117f3edeeeeb84e602b1ff316fd6771ebeb9bd400fd5ae9155199ab")),
+                (&hex!("00"), &hex!("d0a4a36284288e3bffe9da9b84bc99da99d7912011bc26c462504e2596229246")),
+                (&hex!("54657374436f6e74657874"), &hex!("419d16ff65523a00452d37ba2fd5f2b1a9261aeb30f1b1736cc2f3febb16c884")),
+            ],
+        },
+        Vector {
+            mode: 0,
+            aead: Aead::ChaCha20Poly1305,
+            info: &hex!("4f6465206f6e2061204772656369616e2055726e"),
+            ikm_r: &hex!("1a91ec4a112661d663caad07437e07486dcc80b499c83c6bf17fb2faba77c180404d983bd32ed4284fa1aee3bb3887b61402036b058c3c8d"),
+            ikm_e: &hex!("178e4db14a03ebf5b5205e11a3c3918431b4d4bb143b62a52bebdd61d107d23122868395cca3dbc46e98964d4c1dfdc4b0e05cbb2934d9e5"),
+            ikm_s: &hex!(""),
+            psk: &hex!(""),
+            psk_id: &hex!(""),
+            sk_rm: &hex!("c2f51845154d6bb6917e44ef0fa0a1fbf1d80f61d199486e75295e8a7e50432d548a7f8040953826c4f1bce79e433dedb4469391c3cc98a1"),
+            pk_rm: &hex!("f2fdb31a7829a6d2d78b9d8b670397457c92cb2417af37dbe0c1c12a9547e4eda9fde09fc3fe0f359bb7b4151e8a6fb592530af71d9dc0b5"),
+            pk_sm: &hex!(""),
+            enc: &hex!("3d4f6aa08c635205bcd96a0791695d08638714474b4d2c0132b69e25cdb826e1a2a84bc0c40c4fc75f52051b034e0afa82b8457e28794f92"),
+            key: &hex!("87ad565738a70049699288c975dc90faddb076f6280136cee4c26c3111f64e0d"),
+            base_nonce: &hex!("b76f001f82b908e92ad2639a"),
+            exporter_secret: &hex!("d42d015324e068d95aa4e5d3dc53a7165f4963a5c30c8d073ce286ee4ecd29e37df81b897e1698e943d4273397f860299c37db445aafe499ece9f6cb1bbfb768"),
+            encryptions: &[
+                (&hex!("436f756e742d30"), &hex!("4265617574792069732074727574682c20747275746820626561757479"), &hex!("4df124bd68d45b84dd5b82146597cdab8b56ab618166f814c2fe98ce35f43b09917283a58810aac71e852bff0a")),
+                (&hex!("436f756e742d31"), &hex!("4265617574792069732074727574682c20747275746820626561757479"), &hex!("5b78efb13bcbbbc2bb69aed60c30287c20c15fc708ed19fe007ffa796e5be0832cb09ca389b4afc15101acf3c4")),
+            ],
+            exports: &[
+                (&hex!(""), &hex!("d13d9f30a9de3369f25b8de6a733d9c5b68a79b148a662a44cb84e9296419ed6")),
+                (&hex!("00"), &hex!("e584af331daaab516a39e2ba8a3421e428918e108c88dda9e921fc6ecb86fd5f")),
+                (&hex!("54657374436f6e74657874"), &hex!("f4b7df9f1f608657f97084d9847cab976e88083fd2d35f3636dcaa9a14ce62e5")),
+            ],
+        },
+        Vector {
+            mode: 3,
+            aead: Aead::ChaCha20Poly1305,
+            info: &hex!("4f6465206f6e2061204772656369616e2055726e"),
+            ikm_r: &hex!("abb2f8e7bece4050fccbc8ae0a70ea83bb2d829dbbc20d480238f3226cd8f42a93cc83e72010fff033c1638a20421ceac4288ca372d2c088"),
+            ikm_e: &hex!("963266c3f339c24f0bd233a2951a8b829efcdb7b598cb48b6c5cb30446f986ffc2a78f3fdebab08c58431f2b67c6beb4a2167e9b423feca0"),
+            ikm_s: &hex!("01cbb9affb519cdae3e479a5d76f5829e9fb3b5ef81fee15e33ee7244508fe41263168780a23226d601f4cfbe04a6e94165a684f72e076c6"),
+            psk: &hex!("0247fd33b913760fa1fa51e1892d9f307fbe65eb171e8132c2af18555a738b82"),
+            psk_id: &hex!("456e6e796e20447572696e206172616e204d6f726961"),
+            sk_rm: &hex!("c6c5c40c8d6e90140dceadd36207a3663d63d1e3c77ce66c64eff2f7842106b2e1f7eb27308c1b5059937dcb1d0219ac7aada27641913810"),
+            pk_rm: &hex!("3e594e34092e43967bb2de3ff8238240ec42ac0ead806f220909ebea97e5bad54dff5ce4e42cb9fec8f9e1080cce7bab3e432d6c4e40fcab"),
+            pk_sm: &hex!("a30529359f7e0c3d9ff9fc337ef7e58bec802f8a70c1e5a79eb7b0a86f37225c79d337f8d450b329bba26a2afbfc807fd3b6061903ac650e"),
+            enc: &hex!("154435de9c8b92bf3619abe9ec981b1d4116b77fdcd38da89d0bb6e0dbacfe1921f08e8afaed9dc2972c14c24516aaa9de168fcb14a65344"),
+            key: &hex!("c4f74b6c33abac5e5d38f8d6dd7ef2dfa22102bf1183ddd3a635882ce328ac83"),
+            base_nonce: &hex!("3903dda4a1f7d6f915790a0b"),
+            exporter_secret: &hex!("afff14fec0130383cc29c6d36209271ea5f6fedaa91b46dd1b58eaacc27cd208113456e84a774bbba159addf3c6450af0ca02e2b4067482d92d84445a45e0c92"),
+            encryptions: &[
+                (&hex!("436f756e742d30"), &hex!("4265617574792069732074727574682c20747275746820626561757479"), &hex!("b792898afbc2f976bd287e3975d4f1ef838e4af161f77f1d78dbf0027fe846fd33a4b120e5d67a0acd12d904d4")),
+                (&hex!("436f756e742d31"), &hex!("4265617574792069732074727574682c20747275746820626561757479"), &hex!("94fe1a8f9ab2c2408be2593636b6c6e746bb95df8910f79a47cd8eef3aab3b570971ad94b6e5e6351c40bd98b2")),
+            ],
+            exports: &[
+                (&hex!(""), &hex!("2394e29f37a25f70847dae21097ec400d70a89e808fd7169b58561ebdb41157d")),
+                (&hex!("00"), &hex!("bffc1b9dcc7c4037bebed54b315270a703b99cd9fbce2caad7115dce707c6fdb")),
+                (&hex!("54657374436f6e74657874"), &hex!("65bdf3ccccae2d2b1dbc3fe2939b4a88d43f068105d149aca16356174abe73cd")),
+            ],
+        },
+        Vector {
+            mode: 0,
+            aead: Aead::ExportOnly,
+            info: &hex!("4f6465206f6e2061204772656369616e2055726e"),
+            ikm_r: &hex!("52ccb09542f76169c8f36836dcd62868d664d168ff53248da4000e2a33bd42fdf7cb1d29704543721f46e025fab4be7a2c0bc5ea7ccbb1c7"),
+            ikm_e: &hex!("e69397fe1aba5d55aaa486996aed51a104d32f0e566d1bdf4d860ac5c8b04b191f1cc7c28a06080f192acd7eab45b5b8aff0db40e2b7e7e7"),
+            ikm_s: &hex!(""),
+            psk: &hex!(""),
+            psk_id: &hex!(""),
+            sk_rm: &hex!("86fca43d13352c8cf2b5ff9ed2e7c350a31cba8a556a5fd0e2d0669edcb773a601a76a29f7db13838880dc42399a720fbf548ab19352d6c5"),
+            pk_rm: &hex!("e049b8fe98be54332bde59c76df7b178bf10b5a32b559f5090f29921a29e0d528b447edd468ac3f47e46906f791383fef836387c17fbf0b8"),
+            pk_sm: &hex!(""),
+            enc: &hex!("dabc59b3963c151fbb7c6d442f2c3440312a1078207eb11fb62c034cb85b85912c7500fbb992f28ceee449405a8b776c79746b2182984f37"),
+            key: &hex!(""),
+            base_nonce: &hex!(""),
+            exporter_secret: &hex!("0618de9b12ce06835e1daad463e21f4c602edced632980ed7fa4f876a649cb7da3c7890c21e8061f943de1fa5b963af855e37b8a6236358ef179f59c0d3502f7"),
+            encryptions: &[
+            ],
+            exports: &[
+                (&hex!(""), &hex!("c75a00c8028d2c0724eca7cb9ff99c5134a836ed92f6662ea92ee614e4f52d80")),
+                (&hex!("00"), &hex!("2eb93c0f358c9a1716b752502efeb3fb5352839670442b11392d5d4a62b4cb99")),
+                (&hex!("54657374436f6e74657874"), &hex!("9aff60c41fde98ca6d10591bc2da1cb4dccdda0b3368c12cbde5a6a3bd864582")),
+            ],
+        },
+        Vector {
+            mode: 3,
+            aead: Aead::ExportOnly,
+            info: &hex!("4f6465206f6e2061204772656369616e2055726e"),
+            ikm_r: &hex!("f9521ace0192f9e7878482d9dc27707a7b322d46e75e86e55a2b8c54e56f4537816a1ca27e85d9724bc437b010a20b730c20bcbb1b4351c5"),
+            ikm_e: &hex!("48b2f7b629ec684d6fc45e33d29d960037c4c301bcb018d81cc1cf4b686ca74897c62f0d74b4960ee80959cfd5b010286f8342e454e656d1"),
+            ikm_s: &hex!("99ad055c83d879a406f20ef7853ba4bad4b8030a99ab4b1297950eccb77357d5ad1d21e2c14e2715ac2ea45f0c6e5b1c04ef7d80f5dc76cc"),
+            psk: &hex!("0247fd33b913760fa1fa51e1892d9f307fbe65eb171e8132c2af18555a738b82"),
+            psk_id: &hex!("456e6e796e20447572696e206172616e204d6f726961"),
+            sk_rm: &hex!("48d7abad68078fd1bf06739152b7cfe56b27bed70d83df6d2b9292259e46ec91806270c0f7b402b8d9e25e49a336800834855b35f34c61a6"),
+            pk_rm: &hex!("d3fb0e65e61290932072eea3678149dbd31cf154231334081af5a0a0fe88709e404d4acd9d4d899e3942262325af1de443d2e5f02f79c3f0"),
+            pk_sm: &hex!("4b17fcf1f56912df85e463a17f512cb6a255a0006b3c07de7eb4cd508c6fec60b50da73aa9854d80ad93f445b584beae24fa3b0d67cf1ec8"),
+            enc: &hex!("b118422303e8b206b9052e283ad57da6dedeb445d1de3046a007b00e7e1f328ac683c3c98148182eee443bf55f9f151164fe15443a70df05"),
+            key: &hex!(""),
+            base_nonce: &hex!(""),
+            exporter_secret: &hex!("ccf37f8db74226001c6890970118cdf5f5985699020b0daade098e97e5cd8d24bf4726a1f2a72932e4c360b3617827bd8f3769524044a991870f1fb0c5978738"),
+            encryptions: &[
+            ],
+            exports: &[
+                (&hex!(""), &hex!("44e961a4684d12b78e2f5bdacfb4394179dceff54f2f65d42ae3e153524762a2")),
+                (&hex!("00"), &hex!("b42d3331e147a87243ad97d6eb88d7e91d5938a75555b836914d1ebea56e8d82")),
+                (&hex!("54657374436f6e74657874"), &hex!("e96093211d8750b6d993a06f4470256bb7c8f006fe5a198df43ff0a4117f2428")),
+            ],
+        },
+    ];
+
+    #[test]
+    fn test_rfc9180_vectors() {
+        for vector in VECTORS {
+            let (sk_r, pk_r) = derive_key_pair(vector.ikm_r);
+            assert_eq!(sk_r.as_bytes(), vector.sk_rm);
+            assert_eq!(pk_r.as_bytes(), vector.pk_rm);
+            let (sk_e, _) = derive_key_pair(vector.ikm_e);
+            let (sk_s, pk_s) = derive_key_pair(vector.ikm_s);
+            if vector.mode >= 2 {
+                assert_eq!(pk_s.as_bytes(), vector.pk_sm);
+            }
+
+            let psk = Psk {
+                psk: vector.psk,
+                psk_id: vector.psk_id,
+            };
+            let (sender_mode, recipient_mode) = match vector.mode {
+                0 => (SenderMode::Base, RecipientMode::Base),
+                1 => (SenderMode::Psk(psk), RecipientMode::Psk(psk)),
+                2 => (SenderMode::Auth(&sk_s), RecipientMode::Auth(&pk_s)),
+                _ => (
+                    SenderMode::AuthPsk(&sk_s, psk),
+                    RecipientMode::AuthPsk(&pk_s, psk),
+                ),
+            };
+
+            let (enc, mut sender) =
+                setup_sender_with_ephemeral(&sender_mode, vector.aead, &pk_r, vector.info, &sk_e)
+                    .unwrap();
+            assert_eq!(enc, vector.enc);
+            let (key, base_nonce, exporter_secret) = sender.secrets();
+            assert_eq!(key, vector.key);
+            if vector.aead != Aead::ExportOnly {
+                assert_eq!(base_nonce, vector.base_nonce);
+            }
+            assert_eq!(exporter_secret, vector.exporter_secret);
+
+            let mut recipient =
+                setup_recipient(&recipient_mode, vector.aead, &enc, &sk_r, vector.info).unwrap();
+            for (aad, pt, ct) in vector.encryptions {
+                assert_eq!(sender.seal(aad, pt).unwrap(), *ct);
+                assert_eq!(recipient.open(aad, ct).unwrap(), *pt);
+            }
+            for (exporter_context, exported_value) in vector.exports {
+                assert_eq!(
+                    sender.export(exporter_context, 32).unwrap(),
+                    *exported_value
+                );
+                assert_eq!(
+                    recipient.export(exporter_context, 32).unwrap(),
+                    *exported_value
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_single_shot() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let (sk_r, pk_r) =
+            derive_key_pair(b"recipient key pair of at least 56 bytes of entropy....");
+        let (sk_s, pk_s) =
+            derive_key_pair(b"sender key pair of at least 56 bytes of entropy.......");
+        let psk = Psk {
+            psk: b"a pre-shared key of 32 bytes....",
+            psk_id: b"psk id",
+        };
+        let modes = [
+            (SenderMode::Base, RecipientMode::Base),
+            (SenderMode::Psk(psk), RecipientMode::Psk(psk)),
+            (SenderMode::Auth(&sk_s), RecipientMode::Auth(&pk_s)),
+            (
+                SenderMode::AuthPsk(&sk_s, psk),
+                RecipientMode::AuthPsk(&pk_s, psk),
+            ),
+        ];
+        for (sender_mode, recipient_mode) in modes {
+            for aead in [Aead::Aes128Gcm, Aead::Aes256Gcm, Aead::ChaCha20Poly1305] {
+                let (enc, ciphertext) = seal(
+                    &sender_mode,
+                    aead,
+                    &pk_r,
+                    b"info",
+                    b"aad",
+                    b"message",
+                    &mut rng,
+                )
+                .unwrap();
+                let plaintext = open(
+                    &recipient_mode,
+                    aead,
+                    &enc,
+                    &sk_r,
+                    b"info",
+                    b"aad",
+                    &ciphertext,
+                );
+                assert_eq!(plaintext.unwrap(), b"message");
+
+                // The info and aad must match
+                assert_eq!(
+                    open(
+                        &recipient_mode,
+                        aead,
+                        &enc,
+                        &sk_r,
+                        b"other",
+                        b"aad",
+                        &ciphertext
+                    ),
+                    Err(HpkeError::OpenFailed)
+                );
+                assert_eq!(
+                    open(
+                        &recipient_mode,
+                        aead,
+                        &enc,
+                        &sk_r,
+                        b"info",
+                        b"other",
+                        &ciphertext
+                    ),
+                    Err(HpkeError::OpenFailed)
+                );
+            }
+        }
+
+        // The recipient must use the same mode
+        let (enc, ciphertext) = seal(
+            &SenderMode::Auth(&sk_s),
+            Aead::Aes128Gcm,
+            &pk_r,
+            b"info",
+            b"aad",
+            b"message",
+            &mut rng,
+        )
+        .unwrap();
+        assert_eq!(
+            open(
+                &RecipientMode::Auth(&pk_r),
+                Aead::Aes128Gcm,
+                &enc,
+                &sk_r,
+                b"info",
+                b"aad",
+                &ciphertext
+            ),
+            Err(HpkeError::OpenFailed)
+        );
+        assert_eq!(
+            open(
+                &RecipientMode::Base,
+                Aead::Aes128Gcm,
+                &enc,
+                &sk_r,
+                b"info",
+                b"aad",
+                &ciphertext
+            ),
+            Err(HpkeError::OpenFailed)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let (sk_r, pk_r) = derive_key_pair(&[1; 56]);
+
+        // Empty PSKs
+        let empty = Psk {
+            psk: b"a pre-shared key of 32 bytes....",
+            psk_id: b"",
+        };
+        assert_eq!(
+            setup_sender(
+                &SenderMode::Psk(empty),
+                Aead::Aes128Gcm,
+                &pk_r,
+                b"",
+                &mut rng
+            )
+            .err(),
+            Some(HpkeError::InvalidPsk)
+        );
+
+        // Public keys of small order
+        let small_order = PublicKey::from([0u8; 56]);
+        assert_eq!(
+            setup_sender(
+                &SenderMode::Base,
+                Aead::Aes128Gcm,
+                &small_order,
+                b"",
+                &mut rng
+            )
+            .err(),
+            Some(HpkeError::InvalidPublicKey)
+        );
+        assert_eq!(
+            setup_recipient(
+                &RecipientMode::Base,
+                Aead::Aes128Gcm,
+                &[0u8; 56],
+                &sk_r,
+                b""
+            )
+            .err(),
+            Some(HpkeError::InvalidPublicKey)
+        );
+
+        // Export-only contexts
+        let (enc, mut sender) =
+            setup_sender(&SenderMode::Base, Aead::ExportOnly, &pk_r, b"", &mut rng).unwrap();
+        let recipient =
+            setup_recipient(&RecipientMode::Base, Aead::ExportOnly, &enc, &sk_r, b"").unwrap();
+        assert_eq!(sender.seal(b"", b"message"), Err(HpkeError::ExportOnly));
+        assert_eq!(
+            sender.export(b"context", 255 * 64).unwrap(),
+            recipient.export(b"context", 255 * 64).unwrap()
+        );
+        assert_eq!(
+            sender.export(b"context", 255 * 64 + 1),
+            Err(HpkeError::InvalidExportLength)
+        );
+    }
+}
@@ -8,10 +8,22 @@
 pub(crate) mod macros;
 
 // As usual, we will use this file to carefully define the API/ what we expose to the user
+pub mod adaptor;
 pub mod constants;
 pub mod curve;
 pub mod decaf;
+pub mod ed448;
 mod field;
+pub mod frost;
+pub mod hd;
+#[cfg(feature = "hpke")]
+pub mod hpke;
+#[cfg(feature = "noise")]
+pub mod noise;
 pub mod ristretto;
+#[cfg(feature = "x3dh")]
+pub mod x3dh;
+pub mod x448;
+pub mod xeddsa;
 
 pub use field::Scalar;
@@ -0,0 +1,296 @@
+// The HandshakeState object, see section 5.3 of the specification
+
+use super::pattern::{HandshakePattern, Token};
+use super::primitives::{Cipher, Hash, TAG_LENGTH};
+use super::state::{CipherState, SymmetricState};
+use super::{NoiseError, MAX_MESSAGE_LENGTH, PSK_LENGTH};
+use crate::x448::{PublicKey, StaticSecret, X448_KEY_LENGTH};
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The state of a party during the handshake
+///
+/// The parties call [`HandshakeState::write_message`] and [`HandshakeState::read_message`] in
+/// turn, as given by the pattern, then [`HandshakeState::split`] to get the transport cipher
+/// states. If any call returns an error, the handshake has failed and the state must be discarded.
+pub struct HandshakeState<C: Cipher, H: Hash> {
+    symmetric_state: SymmetricState<C, H>,
+    pattern: HandshakePattern,
+    initiator: bool,
+    s: Option<StaticSecret>,
+    e: StaticSecret,
+    rs: Option<PublicKey>,
+    re: Option<PublicKey>,
+    psks: Vec<[u8; PSK_LENGTH]>,
+    psk_index: usize,
+    message_index: usize,
+}
+
+#[cfg(feature = "zeroize")]
+impl<C: Cipher, H: Hash> Drop for HandshakeState<C, H> {
+    fn drop(&mut self) {
+        for psk in self.psks.iter_mut() {
+            psk.zeroize();
+        }
+    }
+}
+
+impl<C: Cipher, H: Hash> HandshakeState<C, H> {
+    /// Initializes the handshake of the pattern `pattern`, such as "XX" or "IKpsk2"
+    ///
+    /// The protocol name is `Noise_<pattern>_448_<cipher>_<hash>`. `s` is the static secret key
+    /// of the party, and `rs` the static public key of the other party if it is known before the
+    /// handshake. `psks` are the PSKs in the order they are used by the pattern. The ephemeral
+    /// secret key is generated with `rng`.
+    pub fn new<R: RngCore + CryptoRng>(
+        pattern: &str,
+        initiator: bool,
+        prologue: &[u8],
+        s: Option<&StaticSecret>,
+        rs: Option<&PublicKey>,
+        psks: &[[u8; PSK_LENGTH]],
+        rng: &mut R,
+    ) -> Result<HandshakeState<C, H>, NoiseError> {
+        let e = StaticSecret::random_from_rng(rng);
+        HandshakeState::with_ephemeral(pattern, initiator, prologue, s, e, rs, psks)
+    }
+
+    /// Initializes the handshake with the given ephemeral secret key
+    pub(crate) fn with_ephemeral(
+        name: &str,
+        initiator: bool,
+        prologue: &[u8],
+        s: Option<&StaticSecret>,
+        e: StaticSecret,
+        rs: Option<&PublicKey>,
+        psks: &[[u8; PSK_LENGTH]],
+    ) -> Result<HandshakeState<C, H>, NoiseError> {
+        let pattern = HandshakePattern::from_name(name).ok_or(NoiseError::UnknownPattern)?;
+        if psks.len() != pattern.psk_count() {
+            return Err(NoiseError::InvalidPsks);
+        }
+
+        // The static keys which are known before the handshake, or which this party sends
+        let (local_pre_message, remote_pre_message) = if initiator {
+            (
+                &pattern.initiator_pre_message,
+                &pattern.responder_pre_message,
+            )
+        } else {
+            (
+                &pattern.responder_pre_message,
+                &pattern.initiator_pre_message,
+            )
+        };
+        let sends_s = pattern
+            .messages
+            .iter()
+            .skip(usize::from(!initiator))
+            .step_by(2)
+            .any(|tokens| tokens.contains(&Token::S));
+        if (local_pre_message.contains(&Token::S) || sends_s) && s.is_none() {
+            return Err(NoiseError::MissingKey);
+        }
+        if remote_pre_message.contains(&Token::S) && rs.is_none() {
+            return Err(NoiseError::MissingKey);
+        }
+
+        let protocol_name = format!("Noise_{}_448_{}_{}", name, C::NAME, H::NAME);
+        let mut symmetric_state = SymmetricState::new(protocol_name.as_bytes());
+        symmetric_state.mix_hash(prologue);
+
+        let s = s.cloned();
+        let rs = rs.copied();
+        let local_s = s.as_ref().map(PublicKey::from);
+        let (initiator_s, responder_s) = if initiator {
+            (local_s, rs)
+        } else {
+            (rs, local_s)
+        };
+        if pattern.initiator_pre_message.contains(&Token::S) {
+            symmetric_state.mix_hash(initiator_s.as_ref().unwrap().as_bytes());
+        }
+        if pattern.responder_pre_message.contains(&Token::S) {
+            symmetric_state.mix_hash(responder_s.as_ref().unwrap().as_bytes());
+        }
+
+        Ok(HandshakeState {
+            symmetric_state,
+            pattern,
+            initiator,
+            s,
+            e,
+            rs,
+            re: None,
+            psks: psks.to_vec(),
+            psk_index: 0,
+            message_index: 0,
+        })
+    }
+
+    /// Returns whether this party is the initiator
+    pub fn is_initiator(&self) -> bool {
+        self.initiator
+    }
+
+    /// Returns whether all the messages of the pattern have been written and read
+    pub fn is_finished(&self) -> bool {
+        self.message_index == self.pattern.messages.len()
+    }
+
+    /// Returns whether the next message of the pattern is written by this party
+    pub fn is_my_turn(&self) -> bool {
+        !self.is_finished() && (self.message_index & 1 == 0) == self.initiator
+    }
+
+    /// Returns the handshake hash, which can be used for channel binding once the handshake is finished
+    pub fn handshake_hash(&self) -> &[u8] {
+        self.symmetric_state.handshake_hash()
+    }
+
+    /// Returns the static public key of the other party, if it is known
+    pub fn remote_static(&self) -> Option<&PublicKey> {
+        self.rs.as_ref()
+    }
+
+    /// Mixes the Diffie-Hellman output of the keys given by the token into the chaining key
+    fn mix_dh(&mut self, token: Token) -> Result<(), NoiseError> {
+        let (local, remote) = match (token, self.initiator) {
+            (Token::EE, _) => (Some(&self.e), self.re.as_ref()),
+            (Token::ES, true) | (Token::SE, false) => (Some(&self.e), self.rs.as_ref()),
+            (Token::ES, false) | (Token::SE, true) => (self.s.as_ref(), self.re.as_ref()),
+            (Token::SS, _) => (self.s.as_ref(), self.rs.as_ref()),
+            _ => unreachable!("only the Diffie-Hellman tokens are mixed"),
+        };
+        let local = local.ok_or(NoiseError::MissingKey)?;
+        let remote = remote.ok_or(NoiseError::MissingKey)?;
+        let shared = local
+            .diffie_hellman(remote)
+            .ok_or(NoiseError::InvalidPublicKey)?;
+        self.symmetric_state.mix_key(shared.as_bytes());
+        Ok(())
+    }
+
+    /// Mixes the next PSK into the chaining key and the handshake hash
+    fn mix_psk(&mut self) {
+        self.symmetric_state
+            .mix_key_and_hash(&self.psks[self.psk_index]);
+        self.psk_index += 1;
+    }
+
+    /// Writes the next handshake message, with the payload `payload`
+    ///
+    /// The payload is encrypted once a Diffie-Hellman output or PSK has been mixed in, which
+    /// depends on the pattern and the message.
+    pub fn write_message(&mut self, payload: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        if !self.is_my_turn() {
+            return Err(NoiseError::OutOfTurn);
+        }
+        if payload.len() > MAX_MESSAGE_LENGTH {
+            return Err(NoiseError::MessageTooLong);
+        }
+
+        let mut message = Vec::new();
+        for token in self.pattern.messages[self.message_index].clone() {
+            match token {
+                Token::E => {
+                    let e = PublicKey::from(&self.e);
+                    message.extend_from_slice(e.as_bytes());
+                    self.symmetric_state.mix_hash(e.as_bytes());
+                    if self.pattern.has_psk() {
+                        self.symmetric_state.mix_key(e.as_bytes());
+                    }
+                }
+                Token::S => {
+                    let s = PublicKey::from(self.s.as_ref().ok_or(NoiseError::MissingKey)?);
+                    let ciphertext = self.symmetric_state.encrypt_and_hash(s.as_bytes())?;
+                    message.extend_from_slice(&ciphertext);
+                }
+                Token::Psk => self.mix_psk(),
+                _ => self.mix_dh(token)?,
+            }
+        }
+        let ciphertext = self.symmetric_state.encrypt_and_hash(payload)?;
+        message.extend_from_slice(&ciphertext);
+        if message.len() > MAX_MESSAGE_LENGTH {
+            return Err(NoiseError::MessageTooLong);
+        }
+
+        self.message_index += 1;
+        Ok(message)
+    }
+
+    /// Reads the next handshake message of the other party, returning its payload
+    pub fn read_message(&mut self, message: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        if self.is_finished() || self.is_my_turn() {
+            return Err(NoiseError::OutOfTurn);
+        }
+        if message.len() > MAX_MESSAGE_LENGTH {
+            return Err(NoiseError::MessageTooLong);
+        }
+
+        let mut rest = message;
+        for token in self.pattern.messages[self.message_index].clone() {
+            match token {
+                Token::E => {
+                    let (re, remaining) = split_key(rest, 0)?;
+                    rest = remaining;
+                    self.symmetric_state.mix_hash(re);
+                    if self.pattern.has_psk() {
+                        self.symmetric_state.mix_key(re);
+                    }
+                    self.re = Some(PublicKey::from(to_key(re)));
+                }
+                Token::S => {
+                    let tag_length = if self.symmetric_state.has_key() {
+                        TAG_LENGTH
+                    } else {
+                        0
+                    };
+                    let (ciphertext, remaining) = split_key(rest, tag_length)?;
+                    rest = remaining;
+                    let rs = self.symmetric_state.decrypt_and_hash(ciphertext)?;
+                    self.rs = Some(PublicKey::from(to_key(&rs)));
+                }
+                Token::Psk => self.mix_psk(),
+                _ => self.mix_dh(token)?,
+            }
+        }
+        let payload = self.symmetric_state.decrypt_and_hash(rest)?;
+
+        self.message_index += 1;
+        Ok(payload)
+    }
+
+    /// Returns the transport cipher states once the handshake is finished, the one which encrypts the messages of this party first
+    ///
+    /// In the one-way patterns, only the initiator sends messages, so the responder should
+    /// discard its sending state.
+    pub fn split(&self) -> Result<(CipherState<C>, CipherState<C>), NoiseError> {
+        if !self.is_finished() {
+            return Err(NoiseError::HandshakeNotFinished);
+        }
+        let (initiator_to_responder, responder_to_initiator) = self.symmetric_state.split();
+        if self.initiator {
+            Ok((initiator_to_responder, responder_to_initiator))
+        } else {
+            Ok((responder_to_initiator, initiator_to_responder))
+        }
+    }
+}
+
+/// Splits an X448 public key, followed by a tag of `tag_length` bytes, off the beginning of `message`
+fn split_key(message: &[u8], tag_length: usize) -> Result<(&[u8], &[u8]), NoiseError> {
+    if message.len() < X448_KEY_LENGTH + tag_length {
+        return Err(NoiseError::MessageTooShort);
+    }
+    Ok(message.split_at(X448_KEY_LENGTH + tag_length))
+}
+
+/// Converts a decrypted public key to an array, which has the right length
+fn to_key(bytes: &[u8]) -> [u8; X448_KEY_LENGTH] {
+    let mut key = [0u8; X448_KEY_LENGTH];
+    key.copy_from_slice(bytes);
+    key
+}
@@ -0,0 +1,883 @@
+// This is the module for the Noise Protocol Framework with the DH function "448"
+// http://www.noiseprotocol.org/noise.html
+//
+// The DH function is X448 from the `x448` module, which rejects the all-zero output of small
+// order public keys. The cipher and hash functions are pluggable through the `Cipher` and `Hash`
+// traits, and the ones of the specification are implemented: the ciphers ChaChaPoly and AESGCM,
+// and the hash functions SHA256, SHA512, BLAKE2s and BLAKE2b.
+//
+// All the one-way, interactive and deferred patterns of the specification are supported, with
+// any PSK modifiers. The handshake produces two CipherStates, which encrypt the transport
+// messages in each direction.
+
+mod handshake;
+mod pattern;
+mod primitives;
+mod state;
+
+pub use handshake::HandshakeState;
+pub use primitives::{
+    AesGcm, Blake2b, Blake2s, ChaChaPoly, Cipher, Hash, Sha256, Sha512, KEY_LENGTH, TAG_LENGTH,
+};
+pub use state::CipherState;
+
+use std::fmt;
+
+/// The length of the PSKs
+pub const PSK_LENGTH: usize = 32;
+
+/// The maximum length of a Noise message, handshake or transport
+pub const MAX_MESSAGE_LENGTH: usize = 65535;
+
+/// Errors which can occur during the handshake and the transport
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NoiseError {
+    /// The pattern is not one of the specification, or has invalid modifiers
+    UnknownPattern,
+    /// A static key which the pattern needs was not given
+    MissingKey,
+    /// The number of PSKs does not match the PSK modifiers of the pattern
+    InvalidPsks,
+    /// A public key is of small order, so the Diffie-Hellman output is all zero
+    InvalidPublicKey,
+    /// The ciphertext is not authentic, or the keys, PSKs or prologue do not match
+    DecryptFailed,
+    /// The message is too short for the tokens of the pattern
+    MessageTooShort,
+    /// The message would be longer than 65535 bytes
+    MessageTooLong,
+    /// The nonce of the cipher state is exhausted
+    NonceExhausted,
+    /// The message is written or read out of turn, or after the end of the handshake
+    OutOfTurn,
+    /// The transport cipher states are requested before the end of the handshake
+    HandshakeNotFinished,
+}
+
+impl fmt::Display for NoiseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NoiseError::UnknownPattern => write!(f, "unknown handshake pattern"),
+            NoiseError::MissingKey => write!(f, "missing static key"),
+            NoiseError::InvalidPsks => write!(f, "wrong number of PSKs"),
+            NoiseError::InvalidPublicKey => write!(f, "public key of small order"),
+            NoiseError::DecryptFailed => write!(f, "decryption failed"),
+            NoiseError::MessageTooShort => write!(f, "message too short"),
+            NoiseError::MessageTooLong => write!(f, "message too long"),
+            NoiseError::NonceExhausted => write!(f, "nonce exhausted"),
+            NoiseError::OutOfTurn => write!(f, "message out of turn"),
+            NoiseError::HandshakeNotFinished => write!(f, "the handshake is not finished"),
+        }
+    }
+}
+
+impl std::error::Error for NoiseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::pattern::{HandshakePattern, Token};
+    use super::*;
+    use crate::x448::{PublicKey, StaticSecret};
+    use hex_literal::hex;
+    use rand_chacha::rand_core::SeedableRng;
+
+    // The keys, prologue, PSK and payloads are the same in all the vectors
+    const PROLOGUE: &[u8] = b"John Galt";
+    const PSK: [u8; PSK_LENGTH] = *b"This is my Austrian perspective!";
+    const INIT_STATIC: [u8; 56] = hex!("34d564c4be963d1b2a89fcfe83e6a72b5e3f5e3127f9f596ffc7575e418dfc1f4e827cfc10c9fed38e92ad56ddf8f08571430df2e76d5411");
+    const INIT_EPHEMERAL: [u8; 56] = hex!("7fd26c8b8a0d5c98c85ff9ca1d7bc66d78578b9f2c4c170850748b27992767e6ea6cc9992a561c9d19dfc342e260c280ef4f3f9b8f879d4e");
+    const RESP_STATIC: [u8; 56] = hex!("a9b45971180882a79b89a3399544a425ef8136d278efa443ed67d3ff9d36e883bc330c6295bbf6ed73ff6fd10cbed767ad05ce03ebd27c7c");
+    const RESP_EPHEMERAL: [u8; 56] = hex!("3facf7503ebee252465689f1d4e3b1dd219639ef9de4ffd6049d6d71a0f62126840febb99042421ce12af6626d98d9170260390fbc8399a5");
+    const PAYLOADS: [&[u8]; 6] = [
+        b"Ludwig von Mises",
+        b"Murray Rothbard",
+        b"F. A. Hayek",
+        b"Carl Menger",
+        b"Jean-Baptiste Say",
+        b"Eugen B\xf6hm von Bawerk",
+    ];
+
+    struct Vector {
+        name: &'static str,
+        handshake_hash: &'static [u8],
+        // The handshake messages, then the transport messages
+        ciphertexts: &'static [&'static [u8]],
+    }
+
+    // A selection of the vectors with the DH function 448 from cacophony, covering every cipher
+    // and hash function, and every token in every position
+    const VECTORS: &[Vector] = &[
+        Vector {
+            name: "Noise_XX_448_ChaChaPoly_BLAKE2b",
+            handshake_hash: &hex!("1bf832e5208f9d50731a2c2a033cf95f4b211a336d0e2f860aa5ba66e6a551b23ea651d958ef9585f7129bf598bc32e6c48a267e8d9b9f30126a41587618389a"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d656b7b04c0e19828bc0ce30fc7b271319f645702ac2e6af3c00f1f57f18fe3c9754a6669fdc0b40fe7b83d5e1eaf67fd960c125327c69bf835fb5c3d118e21b737edfc35b30a5f7fc43eb31790e63b70cd1208297d5e75aea6f658d7363a700ddb4789f0c50a054e"),
+                &hex!("4d30fe9c4ef4135d17c4b5d915cf1b9d88f782d3ce4a022b0a59d6e780baf549b8d1febf5be8bf12d796d2917937dc589f8b46958c1fc4a2a00367cbc1401a648d80cbcc68e5f86ad0a1384b257605ea76cacc075f22347c71548981210d7db67dee5b"),
+                &hex!("a6bd6852849d84d9ce24d818359d748fbfe6e9b44b297516790451"),
+                &hex!("7ffc6ec7e1d7af42be95acb9215669addf2d569255f706fef6e2c20704129f34a7"),
+                &hex!("29299b1184c4f91bb4f7b15921dd158ba88aae5f8016d5f921d39a6c98075d229dafc4ecf7"),
+            ],
+        },
+        Vector {
+            name: "Noise_XX_448_AESGCM_SHA256",
+            handshake_hash: &hex!("0a76718fc587129824ff7004b54a7cf3897b42e2def8997ec78ac78036b33248"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d6505794a84d0af56738307d215a9ce0f4f30b078da97046b9f957aa8dd94bbe64976d49463f40a4b2a7122c3d416d4097fd0a193319783da620f7264a9662d23424962b7ad324d64a64424d0ee631b9a4cf84f0972704fa238a91960e023fee0dbc4879ab7df53db"),
+                &hex!("7ce28d208639c5e88a34c4126d794a0ee24e67807a1ce86ec247c4ce047f67ad80e203768558941ac23e7b3282bd4e687b9d3a7759cc6ade407ecf0a9f1d2b5e43b18f550bdf1c14d274772c01212665f1df16559042a5b66f59779cc2090ab4d4b053"),
+                &hex!("ac14cea0069ab0991b0ee1cda77f29db88ccadb48745e6e9ed7d97"),
+                &hex!("d3110a51d3b9804d26d1b197f03a3b894010e5cb1c2520b9042f7a76ec709d433b"),
+                &hex!("76052cf75d7468cf733b9394b40c05b54cf28617546c4902bb134ecbc2bfd4f43a96faec27"),
+            ],
+        },
+        Vector {
+            name: "Noise_XX_448_ChaChaPoly_SHA512",
+            handshake_hash: &hex!("0dad111c440d62ee7ad18c6e9a498896fd8a41b2a8bf9116dbe8a269d53dc7d85ffa81d8b2244d150863c800f5f33fb4d67a563c95738a81c7ff085f86c5b642"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d65e97ed56bb229af09cd5a7853fd48a0a764b27663602b4774d42e7c61a3cce33e5c7ed3595135578d9c620976f42d01273f17242291e4a2f2ee3b0621d7ab1e8c531b6e95cddf2b38aac9c9d4bdc18ee6008326dc2b736cc5ef91b1e07b8e781d2b4baca02fcbcf"),
+                &hex!("7f269e88932739be86484a89fce5151d0afac70a26384b7acbcd76fe782bbfb060ecf37d59e8a0223fb9aa890aa6ca472cd0aa728341db6da7fd91a29be0ffb0d4ea2e7b1457c05373425b9189c9e8567a11a9666bef8f3257b693647c9a79ae14550b"),
+                &hex!("2b52a782a91f25f8a4e0520be46b344531308a3da31b4554bfef31"),
+                &hex!("832bf36d0a584dd31866ac80425b0c1034d615fe48cfc47254679aa801a6372bd6"),
+                &hex!("6f853b19b1eef37217d3b0c6aa81a3ffe1357dd6af97a77cfe34bd17c0541142f68419c59b"),
+            ],
+        },
+        Vector {
+            name: "Noise_XX_448_AESGCM_BLAKE2s",
+            handshake_hash: &hex!("1ce3b7874429bafff0888250ad22ef3bdd4a2bed16f51ab93af3039e140be690"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d65a3bcbb2a252517c5ba4d4c27c8ab2d04731351eb766430aca8f82f973af41e330d833b350f6dce9bf06aee6b7822c6a14d010ffb8f2d79157ab220ad614bb6fa2266965e804b276fdf04d7bc5b0b36a14920d8d454e4de2b54cd1c695cf8eb2e7d8281aaee2754"),
+                &hex!("68ac156bda3adcaafe3be69565704482bfd36088246840ee210d636030ad235cfdcfb42583f9153c90883ce1a71b28cd7c35cd150ce1433b9e7436e8e5a1d8f040ec923a0ff745440eb2d58aee5127d3cdfced43114b1130eb995c9b79d4c4bc5a2962"),
+                &hex!("9d73faf3e0e2fe97ac976166d9784078a639b39bd0f9dc4c6d48d5"),
+                &hex!("a47440e5ced3d70b7f261dd472854176a73502d1f563459368a4aa82fc114f9cbd"),
+                &hex!("543ae48b11e0ea26172e92f5250a86f07aa98e625e49863eaf849cdc59772392fe21a5af8c"),
+            ],
+        },
+        Vector {
+            name: "Noise_XX_448_ChaChaPoly_SHA256",
+            handshake_hash: &hex!("93b4b0011d14b7d927427e33808881bb5355744993f9ba9c44875402178ec1a8"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d658269b54af7a8bb4f35fedc879516f10c1034ccd93c408c2c388e2f6ee009f4b7bcfe41fcb48c1804b015b37fdc0c814d67b38ecd28e1ebb4e7593c13a896ce253a874321d75f3dade5bf037a67728ba842a22071143673473eb3c217c68b5a2367a78f35ff70ff"),
+                &hex!("5b1ac5d03deb7cd4ee5ff337d5132b2e5f8e38e0cc9555dc4929175f4e963ec9fba27b87d9cb909af7648735e5ae0ac529e7a301c6cc7851003bc841ee2a98fd148704057f872b82cadad144b52a6094673ff255d3702ef0a2d9b343881fd0c9c2ddf6"),
+                &hex!("e6004dc4d3cfc226ffa474dec3107d6445204c4287c7996e984985"),
+                &hex!("76b0ff01e9bcb3dee7fffad8955eddaa357d5eb7a7376debe37cf9c62aaab2e183"),
+                &hex!("7fa78724e77de8209256327c4d5210c4ff95117810f5f4832a7b5463049bdd33d042917101"),
+            ],
+        },
+        Vector {
+            name: "Noise_XX_448_AESGCM_BLAKE2b",
+            handshake_hash: &hex!("f716b0977ff01fdacc208a9df96703153f374062d554024284390ba773351c737a6170f8aba6fb7e78b3b7ec21adff8ea5c82710ce6788adcf57c3b1df817188"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d656b5104e88ec0a25ac061a7133a6b2a12afedb990b92fa7ee241d13da8a48175fa5d88b6abd5976ed4790c183ff71d96987fdfd7766f2974ebe5974ce5f3b5c45605958453ffed2a587b80a3bcb5396de2cc0e3d78893c121346da00cdffce8b09f8822d0146157"),
+                &hex!("4357707702598ddf4e18ec9089761cab124d2c31627a76050793eb8a3791116ca76d3aaaae11636d3ebe4670bea7a0c7b067526bc86a0774f7aec8ac7b25862e74a3174dfd2d97ab01c7e2d79e80914a4eef78f754962f9935eaa31a682a7f98af164f"),
+                &hex!("8119e4a785938817853d78e89f75fdbe5fae0b405bb8e06b1ee8cd"),
+                &hex!("a3b8b306337c99689970141d6e77db43a61200c1f5b0b37acc2f37530a74b0df8e"),
+                &hex!("ab3792ada0ac0189bf18ec919f9106ff028a1e66fb346e7f02c850f4f6202e03e27031380d"),
+            ],
+        },
+        Vector {
+            name: "Noise_XX_448_ChaChaPoly_BLAKE2s",
+            handshake_hash: &hex!("f89dcbe060b0e8c3a28b326998bbdf30500fb591b23e80645c0f5d5743efdcc3"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d657d2aa90e1b30e898c03e49f761318a1d8ee26775698c2aa63426e920ee4ce8162ae89e191eace820f61bcba5ed40b815c3333bfbeda8406e9ec12b1231435bb0c5c94edf5d1caed406d8c27c6d432a5fdc77e6e5e7cdcb67d3afcde16a9c787916fdcc2186843e"),
+                &hex!("1748e3f62df314b65f950428dc6a5f3ef2c3f73bbd2ffba853c2a7a893d7e1128592d61a53dd7939a3d1223e6d9fc939587119286752b165d6b93bf39d6fee498338ae3a262ab9c9f81f5e226beeff4715ef74ed612bf747f3d297fa441a4719b2c00d"),
+                &hex!("6eb0b5ef812535f28521a69b99193e97b9948ae0d8444347451384"),
+                &hex!("1aae55ad9b0a6e8f7040247d3cb7b1606f85517238a69874227f6f4258d6ad35a8"),
+                &hex!("8fe7803f5cf41c1deca1320c74b64640a10d0c26e22e60ea3792ced93c8b2d94be537c2c35"),
+            ],
+        },
+        Vector {
+            name: "Noise_XX_448_AESGCM_SHA512",
+            handshake_hash: &hex!("44ed8b84e345625faa669398e8b259076d5fc41c0499645f499dbccf8f81d6b9037a1f56c0f61a28651250c43fe7e52db836dff14eddc8efe75de3b082aff39f"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d655a4e3c3b3e28088cbb7356574405c5b5a66de233a533d2f3d5a49415252564497cac366934ddb6d14340d5e799e8217c1b864824350a8f24407df1b7168218b4e37e71a60401a30f7124cd0dda5c5cb48ec3010d1df4d1a166bc3bf926d58e0d3969fda4a979b5"),
+                &hex!("cf6411586c1252a04dc33ef6d3c44cd8c47896f889e414c180478298f06d1dddfe3bcfd4391cdd916d8decff95f9295cceac6be376aac2b288d7d70360c00bf8dcd535e6461ae59f2cf36e9cdcd6e44d70776a9ec532e2c8b2f51fcc89ab8ff4fa5fe0"),
+                &hex!("4cfc885a8ec3e55c07a8f468bf36c3c222fba3598cebdcf4cdd6a1"),
+                &hex!("ada1e633a5ef954ae2371acd9858767e4dee19b1f3afc1635fd013c7714aaa0cf4"),
+                &hex!("4070206c3af79f68a7d9f0afac22bb32ee1454baa7c27f52e45c79ef64036c4f7fee71d194"),
+            ],
+        },
+        Vector {
+            name: "Noise_IK_448_ChaChaPoly_BLAKE2b",
+            handshake_hash: &hex!("7c08d7cb084cfe9aee27a9be91845001e700945edc8e938575cdc0f76b4e503234a7763b3c487de8ff1babcc4a0879639723c01b88b4c4b5841d9137911b1a85"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49ad722bcaf1eeafbbc35a7cc65168143bf6e6094ecda0baa69fde2da8be4fe7894a13d0040e7d22c6335155fcaa3eec3ca846f5b5a4483736859d1d299ae6cbf092ce5eff52b05e2bc0a6662372ddffc98acf00a42517902d3e12aca78b503fa29ef300896aa39c92e"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d65780dd82f6a9d1a9c02155f42719918d9f74b90cab7b8778f3db61d544ee016"),
+                &hex!("b47460956428b1badeaaf611be498746c3728ca5299670667239d9"),
+                &hex!("5ac6b40533a66340fedd4a1a9bab395978212d733de9d1b0ae237d"),
+                &hex!("12101f0bbf13ac105c03e2a3dc6aab6090cf4282ac1c87885ee572ac65b8aa2d2d"),
+                &hex!("87ff954000cd67b9853dde79ac2cf346dbc2028b1f862f99e65b9d047f155cd76d72503952"),
+            ],
+        },
+        Vector {
+            name: "Noise_IK_448_AESGCM_SHA256",
+            handshake_hash: &hex!("99dffb6c280641fecbbcd88a51c74ef26a9ff5122e85901c82e344e14f7ddc20"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a35bc31dce105e1d7ed1be79a962b967b2039b1db3ab05e4afcd9320e6bef8834b92f81097f23d50b6c3dd65e5c5d6b6cee5b95f37b4fd920cf923d6ab36b5fdf8e8f9102b31a09e24b1c8f14e2beeab428c285527e9ac1732ae499f2f39f80702db9a74dd4f716a4"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d654a9ef2030db8569a07942f2c5718280a72849321235249df7a6266169812f0"),
+                &hex!("ea91c9c61d3bf6547958958840df010f2e33b6acf5a79488586622"),
+                &hex!("6697fe69b0e1c6a7399388ac29cfc3dddf677f8488318ed2c72ac9"),
+                &hex!("e26360b6dfa860d87b615b6fa1587223541fc523f85556d37d570345fd4b2e6b4b"),
+                &hex!("502a028a54a55a64263ab9e1acd6d617e318fa14fcef75ba3bc45d634285cb7d4e672460a5"),
+            ],
+        },
+        Vector {
+            name: "Noise_IK_448_ChaChaPoly_SHA512",
+            handshake_hash: &hex!("34f7fc287d79a444369c27949ef71c32d6c02cde87eeee03dcc6df4fbd4aa8161a9a8fc544a5cbd9b062de899cef35caece6ab8def793b9704b70cb8c2269eac"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a1c15cbcb45be690918b6502c7ad6225bbe1338dd37e181bde79b30fdd9a486d024d063354d7cce596ec4acf3051cd54ea90c56ab85d1661efb7f66f74a345ba10030d20cc55387b00bf2bbf2fd1f3f284ba18e36d9ea5c6f750d8dfeb9833f0b7bcd541d03e1a155"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d6576eb73ef62b5f680bba7e7c4c6db9acdcf5547ae7092ebe404898104b72983"),
+                &hex!("adaa61bfa33c31487c95457639edbb63ba6e493129ac180bdec1df"),
+                &hex!("1e47c4d2292e515be90e4cc9930cc500dcf3b0a30f08b3a5c77121"),
+                &hex!("cb2912c81039ac4a808013bd52835ef235de1e08761e4b8cb355c85323f58275f3"),
+                &hex!("7ead620e1b0733eb87d0246d70ee190c91ea75a5b30e36c7a4d345b73fcc659e2df500276c"),
+            ],
+        },
+        Vector {
+            name: "Noise_N_448_AESGCM_BLAKE2s",
+            handshake_hash: &hex!("833c47fd04e3fa6ccae3072d9a0ab5fdfd05605992ba75ff9c311ff396966a61"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49af5835cccc494aab2d753ff2006c32a3ca9035d52e1fedfa16d41a2f54e7bf000"),
+                &hex!("3adaa7730bed8c0020dc6d2fb7dc202e8ed586a3afa23ee12170387d834f54"),
+                &hex!("261fb0363c68fe3df37a9eb0ce0ec31a4bfe2828d4f5aaf8dbc9e9"),
+                &hex!("ccb584a3e805e07aa9dae431cbc0aaa83b663b239780c5c68b1f9e"),
+                &hex!("af56a51270430ea3f6197946d81bddc6d13aa27ecff6c1114b4415a92c2be54959"),
+                &hex!("f923cd305cceaa496b8597eea4504f1e746ad3ec16b1615deb336eed722f9fc7f365274dd3"),
+            ],
+        },
+        Vector {
+            name: "Noise_K_448_ChaChaPoly_SHA256",
+            handshake_hash: &hex!("6c6a42ce55d7d5109890f45099cd1d30850aead9d738fb5d1e2577a8f55446a3"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49ad37511bac6093e092ff207761ba3f5a5bf15d293bb8ba245f30ef2f53212691c"),
+                &hex!("789d95029550cb12bca33ecacd7091f2fad2856c933dc9be4a8893e7418b38"),
+                &hex!("7439a2a484fcaa8b26fd8f7ad291848ff4a6b945fcbac1db28828d"),
+                &hex!("4e12fbaf75ee21f24c3337209df6e3fdcc02a662da211d9f17ba8b"),
+                &hex!("24960123e50c7da732d681dc4d3cb76a21f712048fc60059ec97c665435fc602aa"),
+                &hex!("2ae78e59f57d7c608947148b610fed8432b176c4b967385f5d9571afb113e4cafc7ac7a897"),
+            ],
+        },
+        Vector {
+            name: "Noise_X_448_AESGCM_BLAKE2b",
+            handshake_hash: &hex!("92f1d41ccbeddf1c847f88f345dfdfbf4827abba0349a0e4f535312bedd5158b80c465b83fb35a08ca1a30e0be40ac6e623bb8f083ea32ae9deb185ce8d54205"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a899d3bea8e66d1fe751f9d3268b1b4e1e949150a8bd21528530d353d65c4a286cd737b46cbceae8d2e6082882ade4ff6e372cfb95385529f29a483ade2527e26f04f5af2a91f6b024c1bfee06d455b72aacca4da60c61f4e46faac2f980c2672915a523ac70536d2"),
+                &hex!("3eb07aaec7227e707ed371f9d5d42482319df7592fd6f1f5551890abf11dd7"),
+                &hex!("256dec34565c0ceb5954193b63ec4dc2b52ba1a6699b976b364282"),
+                &hex!("99b7c3b2c732f40e77bfabaf1f6a95ee16cc6f99ba2ebe1f237b86"),
+                &hex!("6a08c0f9b34bb1ffcb4982c63d7c30368a85b33856b8e2d8353f9d84d343dcf661"),
+                &hex!("894af0ec756adf097763a1ea2ba306b0051faf8331a0c42fc6ca02b4ac70f97e0d244f0b5f"),
+            ],
+        },
+        Vector {
+            name: "Noise_NN_448_ChaChaPoly_BLAKE2s",
+            handshake_hash: &hex!("d72cdbf80757a8e048ba28a4468f58c7fdfd8af68435026ef17ac1199c73edb6"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d65e668dc276b2f33defc6ecf5a549fa779e67a3388367d0944e2fc40c747acfb"),
+                &hex!("97e4fd576aaac3a9bf92c58058ee0f7f13a06a46aedc66c2525d38"),
+                &hex!("614bd1b75f11562f0cc5b09d090da32db1f31c8d739b3d361b4d42"),
+                &hex!("f75d42bd4fb545f3f9cbe573c8d5023aaf9cd663e01794f0812387db078905fe14"),
+                &hex!("2f267a3b923ce6f9561220db3810bf03d0f8a8ed4382b256bb8c902f6814241e5dd2195aab"),
+            ],
+        },
+        Vector {
+            name: "Noise_NK_448_AESGCM_SHA512",
+            handshake_hash: &hex!("c1bafdb069de3f9c68ea8421a87e7b35ff8089675a311d99f2b1fa73eb4c58b141501d8047467a0d99a0d3b5f03339660cf7e489a3371f8b1adeb6263eb8f6e5"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49adb6433e209291ed99efe0f2ef85a92577fae5e581046ab98b07665f5e7ce8b1a"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d655aadc2ac87aaf84b239cef2d1615c81fc86c7166fedd7421fb95c493823a16"),
+                &hex!("93be59b5d419a926ae538440bdd0ee3b4163cec7ce4ee30ab2f0bb"),
+                &hex!("d0677d4892eb2261f04decae75414a3cece1cdecd58b3073f2f061"),
+                &hex!("ec0c44c44f2f25cd5a06ec3f5d00b883184f49f261ab424c1f68f3d9dcf1ca259e"),
+                &hex!("655379864157bd67b92f011b7cc1da695490b759504ba67b220ba1b213fa073b4aea4b3a12"),
+            ],
+        },
+        Vector {
+            name: "Noise_KK_448_ChaChaPoly_BLAKE2b",
+            handshake_hash: &hex!("62b195b9ac6ccc1db24eca7efcc491b810e31acb1b91ca50ab274eed1330b6417d9a829c68c389a88aad643744ca8b5afddefb32cfd509b5d8c82df58095d9d1"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a67469ece170b9e6581b109147ebe0cfed852d9041570f542084511ca451b801a"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d6539a1ee1aec0b7a8264945632e023549f8e14ce5cb3f15ec3d88a597891eea0"),
+                &hex!("0a2a8b37cbad5e64b23bc9fa07f59c43db29048600b5024dc84b73"),
+                &hex!("b07c7ba3b8e3acf6bc0723987aea86f623d19d8d82af864f74aa01"),
+                &hex!("28455b3d950f08cb2cfbfd3d94d54bbccbe423b8d74b8b14d7df0152a2a0dad75e"),
+                &hex!("e2e40df583d49fc3ac5e7a9e7653093062a3b832c1417153d88e2838ba5c0dcc11b4b95c2a"),
+            ],
+        },
+        Vector {
+            name: "Noise_NX_448_AESGCM_SHA256",
+            handshake_hash: &hex!("863c4d452b5e3b40803cabbcdb07dbcc38701315637a0680ba6fa3ac49943ed1"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d658a5a1e15fb5641d67640ef7f8ef39600449256a8c577fa61eaa3c5cdd35f008995cdfd25f906494175801ce4c01ede4d7b23fb2a99f32a704a4ebb0454c47337fab988977130405393072c798e2a1ac72a897cbddcf2e216f2a8756977d348290dd202eaa1ac22"),
+                &hex!("26820b91745e56977fdf5c399f6a6c8a78853d0159e5eb2c172e31"),
+                &hex!("02196f7c426a2dcbeb87ca5b14320409cf8eb334120b702cfd95db"),
+                &hex!("460c0348377c0943de684b854d2afad5e4faf4f184b94b03226a68d59d62ce9595"),
+                &hex!("a00dd2cca81f975f3b04f9044918b061f714286967a730b6567aa2ba6510a69535b9f154d3"),
+            ],
+        },
+        Vector {
+            name: "Noise_KX_448_ChaChaPoly_SHA512",
+            handshake_hash: &hex!("3879bd05b156b57bb78e4857597d8a44b119e13e06c46b4076859e1d700b1bbd96016110951fffb7606ac6ae377c3bd930332dfb6ace5feec868dc58342df676"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d651a9210c915fe23d6bdc87dcc8f5ec49dac1beee766bce9f98e55fce8ba00a3935e7f80370a78c28aa5b1a1213f28a008179ddadef73f8ca4874e6251116de9cd70f7a11e4a8fbb325651e516a5e3ab261db12508fd82191c8155bbab3e250b302c517518296cca"),
+                &hex!("2d73f8eb23240b22790a0a25ad9492cbc4547a3bba2f49e4488464"),
+                &hex!("089732e2532614d94b91980d9e3862ad3f23c02fb477d1c2b44de7"),
+                &hex!("ee2093dc7b3a8173b8a8c114732c83486a309dc35556d5fe9fb35ebaa7ea476dc2"),
+                &hex!("f12ad331068db240840d1d387fafbd7e17545465f7668b097324aaf31524fbda61a6528939"),
+            ],
+        },
+        Vector {
+            name: "Noise_XN_448_AESGCM_BLAKE2s",
+            handshake_hash: &hex!("26008ae367c93e45185ae2273603f47885096a09f8ceb38394b4ad0e4c4a39f9"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d6574fbcaa65bcb85d1487ae4cfbf7a762fbcbfb3ef39d20ee5574b4b7d0c8bbf"),
+                &hex!("d69f0311bf74dba96f0c8c429671e5c08f65f33b4824018b1a9d5a27cae669eefe36be9fdceb0e1e411aff4bb9c02ceb73039a4af57a2a7a4ff2c42efdc26d25db3e9320568b5880fbecdc64939b2a29638da16784b15df27b89b253cdc2041a417165"),
+                &hex!("9814ed6d954b03441b37151373a51841b80c67f6be5de9ecc709d2"),
+                &hex!("1bad94ed64430dc9035808dbaa1bde20e5e40c1aaf9b806cd2782743a5492f5a24"),
+                &hex!("271e828f315adc258d0877089a8436d7b9d59d3a01e5ce64207ffa17b2bd4db0f7f36720b0"),
+            ],
+        },
+        Vector {
+            name: "Noise_IN_448_ChaChaPoly_SHA256",
+            handshake_hash: &hex!("c82f17bc84ec0543add79de9857be48414f6e4d57b54f4a0c95e480a20f62e1b"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a301551eca1788f4451c269beafed110b51f08c0494a8de614a184ff3d467d7defdfc7c138e4669591108b69a056d25cafda289f22d1f32c04c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d652ebab07b9e3efa78580dc4c9962e02e8a3f94fa667f31260054748ee299f78"),
+                &hex!("15161beaeef11204e36c3be8208aeed493bd4c726e5a46f2c95bac"),
+                &hex!("91f771ae92bf8593dc5d6eafc696c695a899de8531ceaaa58e7636"),
+                &hex!("d89d0a05de0284f60013042319f75f2898b61d4090978295bd077923191be167b7"),
+                &hex!("09200b424a5bc6e8110a8f26e86fe219ce2bf35a1db7a8ba830faf376744fd87b97bc545b1"),
+            ],
+        },
+        Vector {
+            name: "Noise_XK_448_AESGCM_BLAKE2b",
+            handshake_hash: &hex!("491f22f2993353156ee1bf9b458f4b8801049a28a48181a9b3e01054ba11be1c6c8fe22d3323db4fdc3f21168d31a8b138151f6650f138458ff5b63a5fa1ca4e"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49ab4ec7ff6ec7f8e8426a7b769daf37050b5cf36b734f80c4dd9c03a55ada74fd3"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d6575d6fbaaae8c7bd8732e8d0e132a13ffc2bc7694d491e0b79d401c505acf27"),
+                &hex!("40e02672b0f93171b80522214d8f1da922f8848dd1eff5d379c301129f53410ed949fa25b7ade8bfb4e3dab1da88529225d572509018e44edb2cc427002237d251ba3e18c2595d6b39ecc66fc4e3af07e0748e6c62f8cc86f27da47ec685fbcd38da7c"),
+                &hex!("0f5ab37507e46961ef53765696017b27364d1b79b54950559291ef"),
+                &hex!("57781b9bf8d51a7c847981f879177bcecc4805c0688bceef3ddfd789a733ca76fc"),
+                &hex!("add9496c28622b223c509fa242483e0d482cff609c43fc41b95740570795264a89cd5411b6"),
+            ],
+        },
+        Vector {
+            name: "Noise_IX_448_ChaChaPoly_BLAKE2s",
+            handshake_hash: &hex!("fdb9ddfacf03a0f969f5947a19e47885affbcb71a86f72665947adfe89e95426"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a301551eca1788f4451c269beafed110b51f08c0494a8de614a184ff3d467d7defdfc7c138e4669591108b69a056d25cafda289f22d1f32c04c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d65d7874d6828c0c00efa65934038f3145a1884282371f7fa666a7516487b8f415a1d695560a164f7f869cbb89383eb2d5d7ba9276d5848aa9c66e557d080e88be46e4c965cd2ca28a2c6f3a4fff00a98d1c0bc91f36f4de94c4610e345692a6d9a160152c62840ad"),
+                &hex!("d17df2b5403721a7f0f73f1ed3949ab377720c35c2015f865ac2ed"),
+                &hex!("a51247849f22f6c8c0aa1661927180ed0d9b82516e37804a21a1eb"),
+                &hex!("40c1f5ba7b6c2ff605bb2e94ab18d7190a6a2606f01e2dfc79f35b8e6260664307"),
+                &hex!("2ad45e48b62fbad3c345d6c9e5013a5c2753b08a127c3b384e70d2eed18826f002f54f739e"),
+            ],
+        },
+        Vector {
+            name: "Noise_KN_448_AESGCM_SHA512",
+            handshake_hash: &hex!("0ab7b29b9ba55950edc9b17a53170c38efb7e49cc84e7824fbdf3cbc18756a8dd2a6c0fee091e7770f8878fa5024706f45b5f480eecd56f52fc0c9e4072b3f4b"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d654942e0f64f3c52f39863d65c8291424af5043342ea0944d6ef4267d7d63150"),
+                &hex!("54c1ca1b03f28d439324d5be5eaa4822a9e048d73071a0448e67f7"),
+                &hex!("ca013e673e9e8a1fa59dc23402f6a7e33810f03cc4dd9831af9b54"),
+                &hex!("97da1e6f132fd5b9c15f396ff412289dc4a38edc7ecd2fa8de8f1f4af87aed6b95"),
+                &hex!("947729039388f0d4f9d33bb7e63d4b85b0335adaf9e979f86b1b9da839cc25e4c7e83f4dd5"),
+            ],
+        },
+        Vector {
+            name: "Noise_NNpsk0_448_ChaChaPoly_BLAKE2b",
+            handshake_hash: &hex!("b8ecbcf253d57397282936bb2847b56923897051fc9331bcdcf7b936dd588d3e801d343dac2ef2dec5861df188a76285f46cc5f9a26de5f9072ff716b2f7c91b"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49aa304cfe91f92c782cd4faa9e2e0a816916ebf45402c1358447efc660cae81e75"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d65abb1e6db3eb4203624505df10fa1d9e9534d3633f2bbf9b66e3d4b15d0b89a"),
+                &hex!("dd6c0da005ebbc892aa95dc0758f026d448b84e4341025451aec04"),
+                &hex!("fbb05a6f10f703e2df8a5c3296940754d48a80f1f0a5f441f4c597"),
+                &hex!("5c67abb1a0e43ae21617521601d6b4fd1859dd99ba7c2c075bf015c3deabc59b2f"),
+                &hex!("e40926fa9deccb62c8b98af88d19de8e1225e36965dbc24815367770335ced36779edf858b"),
+            ],
+        },
+        Vector {
+            name: "Noise_NNpsk2_448_AESGCM_SHA256",
+            handshake_hash: &hex!("b12af373898e6980de381c0552da81a7abba3cac93251b169302c457949ebf95"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49af0f06ba44327920b869dede51d128f9c92a9285a54a45367796f1a2c9912bb23"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d655d1710bef2aaeb4893e8ea22cff85ed78aa39b3c1a0ed3ad2daa1aa9141642"),
+                &hex!("27467b20e44850b0f0187913a1660603ee36aac40ac0233fba02e9"),
+                &hex!("bcc73a94b46cf3055d3c131bda87f0f9917e97da3f6a7f5c1f5eb2"),
+                &hex!("1b1b86359d59c9560daf6f72182a03398fe473b57cb6aa3f56609dd85910716de5"),
+                &hex!("c3aa0762fec5f4c0179edd0b5a36300975531857b0b6c71bf8b22a15c4ec805a0e9b31f3f6"),
+            ],
+        },
+        Vector {
+            name: "Noise_XXpsk3_448_ChaChaPoly_SHA512",
+            handshake_hash: &hex!("85fbc77aa8e7b9a102a6d5dfb6585c16c111766b20e1951f062216d173fa6e5493d92a47272c2fae19af7f537311df598efcc99d6ec13f0154d8f7f33429d48b"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a1ce71ebd2e4a8c4401e585388b4bf19eadfc65c0ed7f9dad4d29dd37b93c1350"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d65a664eff8f8864d37044328d4368927709edcdc2630bd8bc909809f9014ea9fcf11433d42dff2a12633b679c9960a425507434f133d13db5590f7be1e4655b3d48bf57d770dd082ca037951d4df10c0f2eba65f3f03da5907e9381c56db0ca6cfce4ddcd5bd6045"),
+                &hex!("6ff8d97f5dd54cf04b4f5b418d07815f94f23a897611d4f28f31b3859cdff81f9810828b0e0a7cce99698d7587349faf7e174278873943022040c65bf9805da847b910750da8a970d9d6c470d742cb21d048320e8469fdfde63bf1f21f5256c2b93a0b"),
+                &hex!("67c9178c498911be1fff927b9e581f528b5af4daffb79e13e0dcab"),
+                &hex!("4b5187b911bc2117a22c6457e8527feae34bfde0bae8f3f6bd44ec97652a733c19"),
+                &hex!("1ec9b4cf888478d8207d9b8334b97f5b64d9ffb585ac0e550bf9b25e09ab89112c6eaf2cc8"),
+            ],
+        },
+        Vector {
+            name: "Noise_IKpsk1_448_AESGCM_BLAKE2s",
+            handshake_hash: &hex!("66fc7fb10185b1d41ba34ebf03c864d2a355dd79433200ac5ee7b363363778f9"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49ab197a8b6f8f44b25a16563562fa74308015232ad424683d58e3acc816288fee77197fb83b90ae4435613c0a2f6ade60a86da42424ce1d22fb7f4a258856f102a62164fa0a3c757032009aca98b0ad768f0d72c5f0b7368da7ba38f3c283b3d268d4164dfec9550c9"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d65ce4803d01dd07ba092bbb3a95217d35d3403f843da68f6d66b800142398ec0"),
+                &hex!("8bd38cc1388d3367350a3cbd5d1f9589768aa0bbe64198721f6ffe"),
+                &hex!("7af0a1b637f0347b784eab5d5c29ace028a52cf8ba3e29b3406bc4"),
+                &hex!("fde6cd038335b1c82934f852c486d72b2fe747210e8aa62ee1b08e6fdc026dc9c5"),
+                &hex!("13fc8158fe318a81701726b7d2803da77c2603e03e2f525e6f2e06c458774a741851cc81fb"),
+            ],
+        },
+        Vector {
+            name: "Noise_IKpsk2_448_ChaChaPoly_SHA256",
+            handshake_hash: &hex!("4b0b27a1fd7ce722c83d1abc9bd9d956060d32e5b17f7598cb60c2d69f834951"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a5c1c32f0864e3f987caeff9a5ec9dcfbb737fb93b8781ea9ad3206fad096909b103998b6c2fbf19210fb605e705b31d96044b6283891b87bf675df13f7522dd791832a979905cc51be9551474abda26ab07d8a5edb0bfd69b6acd6e0b7b159ae7478b07075b622be"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d659b5452d45db98ee369634e9d7effa6ddac97ee89af7850a1c8e8e530080686"),
+                &hex!("5b9470408d0944ea2cdc415ace1c474ea2f40935e8c317ecaa97a7"),
+                &hex!("a0cceaf62268bfd3a8733e0f79c7a60bbe438f7b52d2420e068da8"),
+                &hex!("f4c4f7a6cd84cb46663c055ab9d6f512a2d7273933ffc418c8fb25e44ebaff6f1d"),
+                &hex!("46c52642c99c0017792e3793ec50add0db524f5de77c23e56225ee4403b7d4f40e47c62dc7"),
+            ],
+        },
+        Vector {
+            name: "Noise_Npsk0_448_AESGCM_BLAKE2b",
+            handshake_hash: &hex!("94e253b66605095cead7dfd5f47e825b106c8a6134040545fab127620fb055aeae961e5f1d18bb13fe2aca9d392d396cc554dc34b18a799e709b5c0bff73c067"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49ac024ee43c044f8cce8d2ff9a781cf6c8406160e723996e234e5626bdd2f40500"),
+                &hex!("01e3a690255a1587505d3c2822ee2fd32c75e07c90c69f45ccc163780d0e11"),
+                &hex!("4b9804643fcc1292c807d20c88cd608c23fade8e6b1e5b2d827f5c"),
+                &hex!("d2e70ea849f961643a5ecce3936f47d6fc6e7449fedc8c7097a3ae"),
+                &hex!("4d9e16e555da42834f2828756340f5a424aa0a1cfeae8c2c12bf3027d5d254116e"),
+                &hex!("4dfa5c23a94e19ccff783b358a4f4c6f4e02eb7aaf1bc6125903c82bcdea374493761c38f1"),
+            ],
+        },
+        Vector {
+            name: "Noise_Kpsk0_448_ChaChaPoly_BLAKE2s",
+            handshake_hash: &hex!("32ca5535377db4f4f8b513f53869af9b93c2d96c0f91f40835787001845d7184"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a9e99430d1fad1a3e0c7649e1e3845d38c41c93a500d1befcd66c6d171cb05812"),
+                &hex!("2acc08cb3c49927f42b16c6cdcc4f0b70746035881ed6a306a866fe6bf84b5"),
+                &hex!("e6c54e1f6ddecc1ad333abf3aabd25ed37025ed7e4bfec4a6b5b76"),
+                &hex!("f9b1dd2ebaf4e53492ccb4bc30e9495265f1b9d0044aff9b92c7c9"),
+                &hex!("e96c22550f8c6a8a6935ddab224544b0e53f36f777267d6d5162b4c34e6ed6f9e8"),
+                &hex!("b6712f8d9e2131864c8917f551df422940588aaf9a76a8f320cb10f3b45402a3b57149db4d"),
+            ],
+        },
+        Vector {
+            name: "Noise_Xpsk1_448_AESGCM_SHA512",
+            handshake_hash: &hex!("0e22262eb06b4930a7c44934e25419f02144bbcf05123d6c01b12fa1a8bd7a4cd503d046801dfd696aa87affc9b9f5ef284e646f720fa21c51975abb0f5112b0"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a9797bac00a949969847afaf8cfd53e539f8115aacc3f5647edc93dcfbb7f94bb1bd2ea0d1a126c67dba92f586950d1563062fb760f1d9650409541a85cebdde5e58e8baf44c1c3302b11d3a63f795e2666725fda0e9b97339db592b20d73e4671fb7b9d21439184a"),
+                &hex!("754b1759ecaba34c741b371f04fca23f5d13f597794f993bd2e8c4d3761fcc"),
+                &hex!("02c12e41a0e5075d14def2c5f556c0c0b713f19b813e5d3c5fd2fd"),
+                &hex!("b4edecd17d6acb2e185900d5a3e9331f28df953691283653074629"),
+                &hex!("19abd65d9529e8f767614824df08490d05dc3c4bf4a31e0ecca0871dafef8087f0"),
+                &hex!("39b9ac668bdd554897649215b92f0b7e6b1322087012ff32d9765f3d201a6f1303dab066a1"),
+            ],
+        },
+        Vector {
+            name: "Noise_KKpsk0_448_ChaChaPoly_BLAKE2b",
+            handshake_hash: &hex!("6e1a1b0706c4a89c74488523e6dc6fbf4bb067ff4d917d13cb38763a0098128187abb0ad4354aa56db51756093defc4ee713ed45fab482398918ff6c01bc94c9"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a5bc9e737d4876165873b6cc41d1f28055042e341369d4a36f2f423b5b15e68a5"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d653d28700a8cc9685b9ac806ff9ea9e6eb7e403c9863603775b8905a7b314f44"),
+                &hex!("b807d6baa90797f7eb484d2bfd91b07731b2594f885d00f54fc840"),
+                &hex!("3d02a8bde656a7efe40133bbcf61c883e254ce9b133b68dd757711"),
+                &hex!("dc6bf65f79c0ae4c52f865d7c36dff13f503606a7975a42efc7ec3aae4233b73f9"),
+                &hex!("9e5ee8890d9a70cbcf6b60f28480c4193029b95d91968fa0b69cf99c6f45514040036c81a2"),
+            ],
+        },
+        Vector {
+            name: "Noise_INpsk1_448_AESGCM_SHA256",
+            handshake_hash: &hex!("dc32a8ffbd04fe1ed7733fbc975feaa929a96aba0fe8eed7345442ec0972e330"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49ac0e82cf14f695961e0b1f1a4f86742e96c9e7947e4c657867d2b152ce9236db016dcd9ad62354949fd91e19c1b81ec96507e107d1448b1d82e1ddf45f97d7623f1512fc2b54f97c2045266c54c77ff0f173475f8320fe75bae544963b3cd518e0d0b93f9655239ac"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d654226c16d822c74650dbd18e9737d709bd05d53ae172569f114891b4b75903e"),
+                &hex!("842d51bb818bd5e86e6d2cb5abd3af0ea38dc089f5ee53f5cda27a"),
+                &hex!("5625558593aadea2e81ad23994547b4abb26797e5c80f0daead8de"),
+                &hex!("03591dcb160fe1cb35ea12d2d893d054f7543e4bde64dae47826321591f0a82894"),
+                &hex!("34b8addb850c027bc287eba9a0ecfb1bd3111342df29abcd056178098369a585bf91cf07f6"),
+            ],
+        },
+        Vector {
+            name: "Noise_NK1_448_ChaChaPoly_SHA512",
+            handshake_hash: &hex!("e804b83fc234a6cbd237f3e16395d6b4529470f7b6931c3f539459304430c541d683fa3edc0371590533f45a35f6d6953a484e1f3e70c91d658136b1a0fc4b4f"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d656c4a94d4dd331b6cf5b2d092736b04e4e6a995c36cc6bd29dee8738e2f9cd4"),
+                &hex!("295f1674020ca57087bda9da4c946c7ab2d34dc93c71019864af3f"),
+                &hex!("b45f454b2c65f1ef4c6c7768139444be226ca0138e394aa01499f0"),
+                &hex!("71ad600931dd9a28d522e3541766ce27a6daa87e112d1206565b8245922e27ae8e"),
+                &hex!("5313518e344c15039f01299005fcb08eee08e6a6ac3802cb57e6d240ab39a4d31d1c0f444b"),
+            ],
+        },
+        Vector {
+            name: "Noise_NX1_448_AESGCM_BLAKE2s",
+            handshake_hash: &hex!("7cc0eba40501e2c36f7a39fe5ca915189727840f9ee88cf375a730b6f8c6ba59"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d65186697ed49968d907bd043a6045286130dec393d11ffe500c30e58988082772ab72f5e58af076ee14b971262c8481bdb2e48a73e1341cb30eba0fc1eaf7a1b155ce336b3e282069da415893b2c0b56bd2d9c3b113c54be138c393aebe4be4989c75d67905d7f56"),
+                &hex!("5920e783a36f9a4407b7101c8a65d8e6a39a809068f24916b5cf59"),
+                &hex!("7927611f903506711e56659a532aa25bd26911e4b563d5c2151f90"),
+                &hex!("e0e7ef41025c402e3aaf96e4f2f88ed18116a22c17a67b88f6ee852935fde425df"),
+                &hex!("ac040dcb7553e9a498413938d5febad3fb001f11aab7f8a2b163b4876a83cfabc75a5f23eb"),
+            ],
+        },
+        Vector {
+            name: "Noise_X1N_448_ChaChaPoly_SHA256",
+            handshake_hash: &hex!("86904be523f3b8de078d2c4b4120b441c2f29eab6c41ac6682f3755e81184374"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d65aa97913623ac6cc1186430fa746a39af1d94d97857fdc7123357c31e0df112"),
+                &hex!("5efdfda1b2ad7c0b490835e58f0765d51ef6507e525ce71132952b7103b995be90e8bad21b8a1c9a0fc2a4588aad6c02a88b1fc7c52b0a221bcf78e5ab400a434c9e9a8908371dd293b636b65af75d40324708933d5bb5893efdcea9ec7261d8f4178c"),
+                &hex!("c5f5a24aea576009407ea44794d7775aa77788c8483fe70497bb62"),
+                &hex!("d77680d2f99210c212aa20decaafda73fb8bb212581a9543a042e3681b1b801a08"),
+                &hex!("b8ad33608f1d902d617ef90c33330bf5e5ffbc5adf2724da8adcbd808aafb536f8d10241e2"),
+            ],
+        },
+        Vector {
+            name: "Noise_X1X1_448_AESGCM_BLAKE2b",
+            handshake_hash: &hex!("a45209a0d776071eaeed467098087161e441d7310b0db577de35a5fe15c79915e50e7edb7d599c9b76cfd11d6f90e856fc8833ab81fe2852baad659f6de82111"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d65085673b45089d4d3edf9a45a87ea35037187ef9af21550dd9ac8844048c39a7a7eb48d5cc9f3b3cb447d7bd33b12299ed6e9450c5f119cbf21dae745cdfd1f74a8a076c7a00c95cb151865ae7255142f1bc20334cca47975873fbf7f776a7649ad8e3becd8b88e"),
+                &hex!("92d6b49955191ade49daee2caddc6d5435e14e62f21f322ca07b499e98370526db03e9ec4957361b65e28a498fc981eaebb4e525b63d12887f79ede3b185a95dfbc54ab11a6f057e48e7cfe03311ab0e5a0dc0c48fbd3ea216ed6868d211b72bd0bc17"),
+                &hex!("530bf6258d836ca982732b9880bd0636de6a4bafb450bf77b2ca8e"),
+                &hex!("7ac50d55b8d7e94954cad6e3ee2df34dff36a4c8ba6f6461a1d2dca1402e3de4f5"),
+                &hex!("e8bd4d0e1496be4697df01c5c4fe03d150e8838cae940d834f5cc85a452e919a57a235f8f2"),
+            ],
+        },
+        Vector {
+            name: "Noise_K1K1_448_ChaChaPoly_BLAKE2s",
+            handshake_hash: &hex!("403e5272ee8bcdb8c230619cd94fb499409853a1d239a8c92d9bdf17615cb09e"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d65a453a86c0eb447a1abd2037b2ec67938e90bc5ee79c9708409a1b5af05f655"),
+                &hex!("6354f8dbeb6acdd893bd55c1c059dcb48711b1eada8b003b6f14b9"),
+                &hex!("32966120e6056e0037bc28499dd002ab2a92ec513fab7b2d036827"),
+                &hex!("13eddb39b88bc08d98461b2ba8777c2c0ba86a2d043a39269ab5f7c76d448381af"),
+                &hex!("911b8a25dadd8522d139cb59a3f3b84b9bbda3577e970163084f87dd6fc56ffd5b17d35cd4"),
+            ],
+        },
+        Vector {
+            name: "Noise_KX1_448_AESGCM_SHA512",
+            handshake_hash: &hex!("0eb70bca9ae03ba3a31b12b5f3d50d2930f21c760c85556fb0481d0f424e1d3fc59977298aeae75c6cdac9a3775bee6d050a9fc344b9bd0572279b8918a82926"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a4c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d65b2c25d7a99474c64c0ca743ad37829c751752930bc5b3bb4c4397f661c512c2909c80cf503742e359fbee46bd086240681072ba8064dfbba8739edc6b0133fb960eb79d221d055ff69f6e849d8121c23129970d1c351db6bff7be8dd5a972e59a5c6e37a0a20da"),
+                &hex!("aca5adb86ff117a7e4ef27533ad6a36bb5eec856a9545718157413"),
+                &hex!("1cd511e6c401ab8ef33ad03af1ef09af8b877a8980b984e9c4a6aa"),
+                &hex!("2164fd38671a87482a50d4b62d88283e67048147e48b7e7cd81a5a72aaec73ed5f"),
+                &hex!("6575abc5376c312e4f0d8d627263531e10e3a5ed97830c4027c34fb58d72c2ef2f2b25d572"),
+            ],
+        },
+        Vector {
+            name: "Noise_I1K1_448_ChaChaPoly_BLAKE2b",
+            handshake_hash: &hex!("2ec9f6bff034b93acc3ed455095e7de60210a314cda497cb4db0bfe5c035b6e7febb04ca6f28cba51740709f70a5be0cdd1ac8c3f97071db683f54d82bd1069f"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a301551eca1788f4451c269beafed110b51f08c0494a8de614a184ff3d467d7defdfc7c138e4669591108b69a056d25cafda289f22d1f32c04c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d650175742623a5a6b27bdbf891a7b7aca48fbdef2662236ed5bf46df252a9226"),
+                &hex!("27325e6ba31f9912b44e132d0dd09692f8b1b891a7a7beae57fb3f"),
+                &hex!("71d2e756da8484ccd0b58e6f7f749780ece6fa4c123570a4af925a"),
+                &hex!("d15253e2f6ff5cb8054ef60d166e334ce00d6352a5d9043aa0e51841695ccdd281"),
+                &hex!("a4980157c31763407a355a3a63e63c17cedecff4ff2249afee0b8f676150e339f0a0766623"),
+            ],
+        },
+        Vector {
+            name: "Noise_I1X1_448_AESGCM_SHA256",
+            handshake_hash: &hex!("95463ae80e22aeeb409fc7dc2bb5075215c62e610f2be261dc6fa13a865bc8d6"),
+            ciphertexts: &[
+                &hex!("6cfcb98ae6b1bc5659cadc595bf664e17094404eae6b45fde6fc40ca937d1dbe1464cb66eb21fdbaa487cd0d11d6dce5aa07b8219bfdc49a301551eca1788f4451c269beafed110b51f08c0494a8de614a184ff3d467d7defdfc7c138e4669591108b69a056d25cafda289f22d1f32c04c756477696720766f6e204d69736573"),
+                &hex!("f7eb9a09468f9564819de07ada77a6cf5d5eacd84682067538bf2c4e4c905e5cc35cc3ff41241e47ae3bd296477a236ef185e5a8a0f18d6570d882dec33aa52806c5162f7ca0247c9c0434eb6302af827430f49a5d9c9af677be99feb83c331dbe20df4ce80ad8bc6c90f6831ad356cd8284e6b66a8673572251308832d24dee45c96abd5656f428e03d568a3728cfc2239ddd6ec1554ee06fd56c937b8569"),
+                &hex!("c11f12192482f039294648d8b92959a785701b6357243b8a25c26d"),
+                &hex!("1763b2ec97b0f526ae3564908fbd099b20817367c313a9af21f6a3"),
+                &hex!("e79e1221c9b32da4a5378268af1a45310ca424569a402ee5b74967ac4e9eee0b6a"),
+                &hex!("dd44c4a760a0d1394f64c6b2c4c0eff87b676fc927fe13fc4389f8ab12b28df4b2ea6b28ce"),
+            ],
+        },
+    ];
+
+    fn check_vector<C: Cipher, H: Hash>(name: &str, handshake_hash: &[u8], ciphertexts: &[&[u8]]) {
+        let pattern = HandshakePattern::from_name(name).unwrap();
+        let init_s = StaticSecret::from(INIT_STATIC);
+        let resp_s = StaticSecret::from(RESP_STATIC);
+        let init_rs = pattern
+            .responder_pre_message
+            .contains(&Token::S)
+            .then(|| PublicKey::from(&resp_s));
+        let resp_rs = pattern
+            .initiator_pre_message
+            .contains(&Token::S)
+            .then(|| PublicKey::from(&init_s));
+        let psks = vec![PSK; pattern.psk_count()];
+
+        let mut initiator = HandshakeState::<C, H>::with_ephemeral(
+            name,
+            true,
+            PROLOGUE,
+            Some(&init_s),
+            StaticSecret::from(INIT_EPHEMERAL),
+            init_rs.as_ref(),
+            &psks,
+        )
+        .unwrap();
+        let mut responder = HandshakeState::<C, H>::with_ephemeral(
+            name,
+            false,
+            PROLOGUE,
+            Some(&resp_s),
+            StaticSecret::from(RESP_EPHEMERAL),
+            resp_rs.as_ref(),
+            &psks,
+        )
+        .unwrap();
+
+        let handshake_length = pattern.messages.len();
+        let mut messages = PAYLOADS.iter().zip(ciphertexts);
+        for (payload, ciphertext) in messages.by_ref().take(handshake_length) {
+            let (sender, receiver) = if initiator.is_my_turn() {
+                (&mut initiator, &mut responder)
+            } else {
+                (&mut responder, &mut initiator)
+            };
+            assert_eq!(sender.write_message(payload).unwrap(), *ciphertext);
+            assert_eq!(receiver.read_message(ciphertext).unwrap(), *payload);
+        }
+        assert_eq!(initiator.handshake_hash(), handshake_hash);
+        assert_eq!(responder.handshake_hash(), handshake_hash);
+
+        // In the one-way patterns, all the transport messages are sent by the initiator
+        let (mut init_send, mut init_receive) = initiator.split().unwrap();
+        let (mut resp_send, mut resp_receive) = responder.split().unwrap();
+        for (i, (payload, ciphertext)) in messages.enumerate() {
+            let (sender, receiver) = if handshake_length == 1 || (handshake_length + i) & 1 == 0 {
+                (&mut init_send, &mut resp_receive)
+            } else {
+                (&mut resp_send, &mut init_receive)
+            };
+            assert_eq!(sender.encrypt_with_ad(&[], payload).unwrap(), *ciphertext);
+            assert_eq!(receiver.decrypt_with_ad(&[], ciphertext).unwrap(), *payload);
+        }
+    }
+
+    /// Checks a vector of the protocol `Noise_<pattern>_448_<cipher>_<hash>`
+    fn check(protocol_name: &str, handshake_hash: &[u8], ciphertexts: &[&[u8]]) {
+        let parts: Vec<&str> = protocol_name.split('_').collect();
+        assert_eq!((parts[0], parts[2]), ("Noise", "448"));
+        let name = parts[1];
+        match (parts[3], parts[4]) {
+            ("ChaChaPoly", "SHA256") => {
+                check_vector::<ChaChaPoly, Sha256>(name, handshake_hash, ciphertexts)
+            }
+            ("ChaChaPoly", "SHA512") => {
+                check_vector::<ChaChaPoly, Sha512>(name, handshake_hash, ciphertexts)
+            }
+            ("ChaChaPoly", "BLAKE2s") => {
+                check_vector::<ChaChaPoly, Blake2s>(name, handshake_hash, ciphertexts)
+            }
+            ("ChaChaPoly", "BLAKE2b") => {
+                check_vector::<ChaChaPoly, Blake2b>(name, handshake_hash, ciphertexts)
+            }
+            ("AESGCM", "SHA256") => {
+                check_vector::<AesGcm, Sha256>(name, handshake_hash, ciphertexts)
+            }
+            ("AESGCM", "SHA512") => {
+                check_vector::<AesGcm, Sha512>(name, handshake_hash, ciphertexts)
+            }
+            ("AESGCM", "BLAKE2s") => {
+                check_vector::<AesGcm, Blake2s>(name, handshake_hash, ciphertexts)
+            }
+            ("AESGCM", "BLAKE2b") => {
+                check_vector::<AesGcm, Blake2b>(name, handshake_hash, ciphertexts)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_cacophony_vectors() {
+        for vector in VECTORS {
+            check(vector.name, vector.handshake_hash, vector.ciphertexts);
+        }
+    }
+
+    #[test]
+    fn test_handshake() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let init_s = StaticSecret::random_from_rng(&mut rng);
+        let resp_s = StaticSecret::random_from_rng(&mut rng);
+
+        let mut initiator = HandshakeState::<ChaChaPoly, Blake2b>::new(
+            "XXpsk3",
+            true,
+            b"prologue",
+            Some(&init_s),
+            None,
+            &[PSK],
+            &mut rng,
+        )
+        .unwrap();
+        let mut responder = HandshakeState::<ChaChaPoly, Blake2b>::new(
+            "XXpsk3",
+            false,
+            b"prologue",
+            Some(&resp_s),
+            None,
+            &[PSK],
+            &mut rng,
+        )
+        .unwrap();
+        assert!(initiator.is_initiator() && !responder.is_initiator());
+
+        let message = initiator.write_message(b"one").unwrap();
+        assert_eq!(responder.read_message(&message).unwrap(), b"one");
+        let message = responder.write_message(b"two").unwrap();
+        assert_eq!(initiator.read_message(&message).unwrap(), b"two");
+        assert_eq!(
+            initiator.split().err(),
+            Some(NoiseError::HandshakeNotFinished)
+        );
+        let message = initiator.write_message(b"three").unwrap();
+        assert_eq!(responder.read_message(&message).unwrap(), b"three");
+
+        assert!(initiator.is_finished() && responder.is_finished());
+        assert_eq!(initiator.handshake_hash(), responder.handshake_hash());
+        assert_eq!(initiator.remote_static(), Some(&PublicKey::from(&resp_s)));
+        assert_eq!(responder.remote_static(), Some(&PublicKey::from(&init_s)));
+        assert_eq!(initiator.write_message(b"four"), Err(NoiseError::OutOfTurn));
+
+        let (mut init_send, mut init_receive) = initiator.split().unwrap();
+        let (mut resp_send, mut resp_receive) = responder.split().unwrap();
+        let ciphertext = init_send
+            .encrypt_with_ad(b"ad", b"to the responder")
+            .unwrap();
+        assert_eq!(
+            resp_receive.decrypt_with_ad(b"ad", &ciphertext).unwrap(),
+            b"to the responder"
+        );
+        let ciphertext = resp_send
+            .encrypt_with_ad(b"ad", b"to the initiator")
+            .unwrap();
+        assert_eq!(
+            init_receive.decrypt_with_ad(b"ad", &ciphertext).unwrap(),
+            b"to the initiator"
+        );
+
+        // Rekeying on both sides
+        init_send.rekey();
+        resp_receive.rekey();
+        let ciphertext = init_send.encrypt_with_ad(&[], b"rekeyed").unwrap();
+        assert_eq!(init_send.nonce(), 2);
+        assert_eq!(
+            resp_receive.decrypt_with_ad(&[], &ciphertext).unwrap(),
+            b"rekeyed"
+        );
+
+        // Replays and reordering are rejected
+        assert_eq!(
+            resp_receive.decrypt_with_ad(&[], &ciphertext),
+            Err(NoiseError::DecryptFailed)
+        );
+        resp_receive.set_nonce(1);
+        assert!(resp_receive.decrypt_with_ad(&[], &ciphertext).is_ok());
+        init_send.set_nonce(u64::MAX);
+        assert_eq!(
+            init_send.encrypt_with_ad(&[], b""),
+            Err(NoiseError::NonceExhausted)
+        );
+        assert_eq!(
+            init_send.encrypt_with_ad(&[], &[0u8; MAX_MESSAGE_LENGTH]),
+            Err(NoiseError::MessageTooLong)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let s = StaticSecret::from(INIT_STATIC);
+        let resp_s = StaticSecret::from(RESP_STATIC);
+        let rs = PublicKey::from(&resp_s);
+        let new = |pattern, initiator, s, rs, psks: &[[u8; PSK_LENGTH]], rng: &mut _| {
+            HandshakeState::<AesGcm, Sha256>::new(pattern, initiator, b"", s, rs, psks, rng)
+        };
+
+        // The pattern, keys and PSKs must match
+        assert_eq!(
+            new("XY", true, None, None, &[], &mut rng).err(),
+            Some(NoiseError::UnknownPattern)
+        );
+        assert_eq!(
+            new("IK", true, Some(&s), None, &[], &mut rng).err(),
+            Some(NoiseError::MissingKey)
+        );
+        assert_eq!(
+            new("XX", false, None, None, &[], &mut rng).err(),
+            Some(NoiseError::MissingKey)
+        );
+        assert_eq!(
+            new("NNpsk0", true, None, None, &[], &mut rng).err(),
+            Some(NoiseError::InvalidPsks)
+        );
+
+        // Messages out of turn
+        let mut initiator = new("NK", true, None, Some(&rs), &[], &mut rng).unwrap();
+        let mut responder = new("NK", false, Some(&resp_s), None, &[], &mut rng).unwrap();
+        assert_eq!(responder.write_message(b""), Err(NoiseError::OutOfTurn));
+        assert_eq!(
+            initiator.read_message(&[0; 100]),
+            Err(NoiseError::OutOfTurn)
+        );
+
+        // Truncated and tampered messages
+        let message = initiator.write_message(b"payload").unwrap();
+        assert_eq!(
+            responder.read_message(&message[..55]),
+            Err(NoiseError::MessageTooShort)
+        );
+        let mut tampered = message.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert_eq!(
+            new("NK", false, Some(&resp_s), None, &[], &mut rng)
+                .unwrap()
+                .read_message(&tampered),
+            Err(NoiseError::DecryptFailed)
+        );
+        assert_eq!(
+            responder.read_message(&[0u8; MAX_MESSAGE_LENGTH + 1]),
+            Err(NoiseError::MessageTooLong)
+        );
+
+        // Ephemeral keys of small order
+        let mut small_order = message.clone();
+        small_order[..56].copy_from_slice(&[0u8; 56]);
+        assert_eq!(
+            responder.read_message(&small_order),
+            Err(NoiseError::InvalidPublicKey)
+        );
+
+        // A different PSK
+        let mut initiator = new("NNpsk0", true, None, None, &[PSK], &mut rng).unwrap();
+        let mut responder = new("NNpsk0", false, None, None, &[[0; 32]], &mut rng).unwrap();
+        let message = initiator.write_message(b"payload").unwrap();
+        assert_eq!(
+            responder.read_message(&message),
+            Err(NoiseError::DecryptFailed)
+        );
+    }
+}
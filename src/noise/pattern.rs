@@ -0,0 +1,185 @@
+// The handshake patterns of Noise, see sections 7 and 9 of the specification
+//
+// Patterns are written as in the specification: the pre-messages of the initiator and of the
+// responder, then the messages, which alternate between the initiator and the responder
+// starting with the initiator. The one-way patterns have a single message.
+
+/// A token of a message pattern
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Token {
+    E,
+    S,
+    EE,
+    ES,
+    SE,
+    SS,
+    Psk,
+}
+
+/// The one-way, interactive and deferred patterns of sections 7.4, 7.5 and 7.6
+///
+/// (name, pre-message of the initiator, pre-message of the responder, messages)
+const PATTERNS: &[(&str, &str, &str, &[&str])] = &[
+    ("N", "", "s", &["e, es"]),
+    ("K", "s", "s", &["e, es, ss"]),
+    ("X", "", "s", &["e, es, s, ss"]),
+    ("NN", "", "", &["e", "e, ee"]),
+    ("NK", "", "s", &["e, es", "e, ee"]),
+    ("NX", "", "", &["e", "e, ee, s, es"]),
+    ("XN", "", "", &["e", "e, ee", "s, se"]),
+    ("XK", "", "s", &["e, es", "e, ee", "s, se"]),
+    ("XX", "", "", &["e", "e, ee, s, es", "s, se"]),
+    ("KN", "s", "", &["e", "e, ee, se"]),
+    ("KK", "s", "s", &["e, es, ss", "e, ee, se"]),
+    ("KX", "s", "", &["e", "e, ee, se, s, es"]),
+    ("IN", "", "", &["e, s", "e, ee, se"]),
+    ("IK", "", "s", &["e, es, s, ss", "e, ee, se"]),
+    ("IX", "", "", &["e, s", "e, ee, se, s, es"]),
+    ("NK1", "", "s", &["e", "e, ee, es"]),
+    ("NX1", "", "", &["e", "e, ee, s", "es"]),
+    ("X1N", "", "", &["e", "e, ee", "s", "se"]),
+    ("X1K", "", "s", &["e, es", "e, ee", "s", "se"]),
+    ("XK1", "", "s", &["e", "e, ee, es", "s, se"]),
+    ("X1K1", "", "s", &["e", "e, ee, es", "s", "se"]),
+    ("X1X", "", "", &["e", "e, ee, s, es", "s", "se"]),
+    ("XX1", "", "", &["e", "e, ee, s", "es, s, se"]),
+    ("X1X1", "", "", &["e", "e, ee, s", "es, s", "se"]),
+    ("K1N", "s", "", &["e", "e, ee", "se"]),
+    ("K1K", "s", "s", &["e, es", "e, ee", "se"]),
+    ("KK1", "s", "s", &["e", "e, ee, se, es"]),
+    ("K1K1", "s", "s", &["e", "e, ee, es", "se"]),
+    ("K1X", "s", "", &["e", "e, ee, s, es", "se"]),
+    ("KX1", "s", "", &["e", "e, ee, se, s", "es"]),
+    ("K1X1", "s", "", &["e", "e, ee, s", "se, es"]),
+    ("I1N", "", "", &["e, s", "e, ee", "se"]),
+    ("I1K", "", "s", &["e, es, s", "e, ee", "se"]),
+    ("IK1", "", "s", &["e, s", "e, ee, se, es"]),
+    ("I1K1", "", "s", &["e, s", "e, ee, es", "se"]),
+    ("I1X", "", "", &["e, s", "e, ee, s, es", "se"]),
+    ("IX1", "", "", &["e, s", "e, ee, se, s", "es"]),
+    ("I1X1", "", "", &["e, s", "e, ee, s", "se, es"]),
+];
+
+/// Parses a comma-separated list of tokens
+fn parse_tokens(tokens: &str) -> Vec<Token> {
+    tokens
+        .split(", ")
+        .filter(|token| !token.is_empty())
+        .map(|token| match token {
+            "e" => Token::E,
+            "s" => Token::S,
+            "ee" => Token::EE,
+            "es" => Token::ES,
+            "se" => Token::SE,
+            "ss" => Token::SS,
+            _ => unreachable!("the table only has valid tokens"),
+        })
+        .collect()
+}
+
+/// A handshake pattern, with its modifiers applied
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HandshakePattern {
+    pub(crate) initiator_pre_message: Vec<Token>,
+    pub(crate) responder_pre_message: Vec<Token>,
+    pub(crate) messages: Vec<Vec<Token>>,
+}
+
+impl HandshakePattern {
+    /// Parses the name of a pattern, such as "XX", "IKpsk2" or "NNpsk0+psk2"
+    ///
+    /// The only modifiers are the PSK modifiers of section 9.
+    pub(crate) fn from_name(name: &str) -> Option<HandshakePattern> {
+        let (base, modifiers) = match name.find("psk") {
+            Some(index) => (&name[..index], Some(&name[index..])),
+            None => (name, None),
+        };
+        let &(_, initiator_pre_message, responder_pre_message, messages) =
+            PATTERNS.iter().find(|pattern| pattern.0 == base)?;
+
+        let mut pattern = HandshakePattern {
+            initiator_pre_message: parse_tokens(initiator_pre_message),
+            responder_pre_message: parse_tokens(responder_pre_message),
+            messages: messages.iter().map(|tokens| parse_tokens(tokens)).collect(),
+        };
+
+        // psk0 puts a "psk" token at the beginning of the first message, pskN at the end of message N
+        for modifier in modifiers
+            .into_iter()
+            .flat_map(|modifiers| modifiers.split('+'))
+        {
+            let position: usize = modifier.strip_prefix("psk")?.parse().ok()?;
+            if modifier != format!("psk{}", position) || position > pattern.messages.len() {
+                return None;
+            }
+            if position == 0 {
+                pattern.messages[0].insert(0, Token::Psk);
+            } else {
+                pattern.messages[position - 1].push(Token::Psk);
+            }
+        }
+        Some(pattern)
+    }
+
+    /// Returns whether the pattern has PSK modifiers, in which case the ephemeral keys are mixed into the key as well
+    pub(crate) fn has_psk(&self) -> bool {
+        self.psk_count() > 0
+    }
+
+    /// Returns the number of PSKs of the pattern
+    pub(crate) fn psk_count(&self) -> usize {
+        self.messages
+            .iter()
+            .flatten()
+            .filter(|&&token| token == Token::Psk)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_name() {
+        let xx = HandshakePattern::from_name("XX").unwrap();
+        assert!(xx.initiator_pre_message.is_empty() && xx.responder_pre_message.is_empty());
+        assert_eq!(
+            xx.messages,
+            vec![
+                vec![Token::E],
+                vec![Token::E, Token::EE, Token::S, Token::ES],
+                vec![Token::S, Token::SE],
+            ]
+        );
+        assert!(!xx.has_psk());
+
+        let nn = HandshakePattern::from_name("NNpsk0+psk2").unwrap();
+        assert_eq!(
+            nn.messages,
+            vec![
+                vec![Token::Psk, Token::E],
+                vec![Token::E, Token::EE, Token::Psk]
+            ]
+        );
+        assert_eq!(nn.psk_count(), 2);
+
+        let k = HandshakePattern::from_name("Kpsk0").unwrap();
+        assert_eq!(k.initiator_pre_message, vec![Token::S]);
+        assert_eq!(k.responder_pre_message, vec![Token::S]);
+        assert_eq!(k.messages.len(), 1);
+
+        for name in [
+            "",
+            "YY",
+            "XXpsk",
+            "XXpsk4",
+            "XXpsk01",
+            "XXpsk1+",
+            "XXfallback",
+            "xx",
+        ] {
+            assert_eq!(HandshakePattern::from_name(name), None);
+        }
+    }
+}
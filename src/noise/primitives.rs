@@ -0,0 +1,288 @@
+// The cipher and hash functions of Noise, see sections 4.2, 4.3, 12.3 and 12.4 of the specification
+
+use aes_gcm::aead::generic_array::GenericArray;
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::Aes256Gcm;
+use sha2::Digest;
+
+/// The length of the cipher keys
+pub const KEY_LENGTH: usize = 32;
+
+/// The length of the authentication tags
+pub const TAG_LENGTH: usize = 16;
+
+/// The longest output of the hash functions, MAXHASHLEN
+pub(crate) const MAX_HASH_LENGTH: usize = 64;
+
+/// The longest block of the hash functions
+pub(crate) const MAX_BLOCK_LENGTH: usize = 128;
+
+/// An AEAD cipher with 32-byte keys, 64-bit nonces and 16-byte tags
+pub trait Cipher {
+    /// The name of the cipher in protocol names
+    const NAME: &'static str;
+
+    /// Encrypts `plaintext`, authenticating `ad` along with it
+    fn encrypt(key: &[u8; KEY_LENGTH], nonce: u64, ad: &[u8], plaintext: &[u8]) -> Vec<u8>;
+
+    /// Decrypts `ciphertext`, returning `None` if it or `ad` is not authentic
+    fn decrypt(key: &[u8; KEY_LENGTH], nonce: u64, ad: &[u8], ciphertext: &[u8])
+        -> Option<Vec<u8>>;
+
+    /// Returns a new key, which is the encryption of 32 zero bytes with the nonce 2^64 - 1
+    fn rekey(key: &[u8; KEY_LENGTH]) -> [u8; KEY_LENGTH] {
+        let ciphertext = Self::encrypt(key, u64::MAX, &[], &[0u8; KEY_LENGTH]);
+        let mut new_key = [0u8; KEY_LENGTH];
+        new_key.copy_from_slice(&ciphertext[..KEY_LENGTH]);
+        new_key
+    }
+}
+
+/// A hash function with an output of 32 or 64 bytes
+pub trait Hash {
+    /// The name of the hash function in protocol names
+    const NAME: &'static str;
+
+    /// The length of the output, HASHLEN
+    const HASH_LENGTH: usize;
+
+    /// The length of the blocks, BLOCKLEN, which is used by HMAC
+    const BLOCK_LENGTH: usize;
+
+    /// Hashes the concatenation of `inputs` into `output`, which has HASHLEN bytes
+    fn hash(inputs: &[&[u8]], output: &mut [u8]);
+}
+
+/// ChaCha20-Poly1305 from RFC 8439, with the nonce encoded in little-endian
+pub struct ChaChaPoly;
+
+/// AES-256-GCM, with the nonce encoded in big-endian
+pub struct AesGcm;
+
+/// SHA-256
+pub struct Sha256;
+
+/// SHA-512
+pub struct Sha512;
+
+/// BLAKE2s with an output of 32 bytes
+pub struct Blake2s;
+
+/// BLAKE2b with an output of 64 bytes
+pub struct Blake2b;
+
+/// Encrypts or decrypts with the AEAD `A` and the 96-bit nonce `nonce`
+fn crypt<A: KeyInit + Aead>(
+    key: &[u8; KEY_LENGTH],
+    nonce: &[u8; 12],
+    payload: Payload,
+    encrypt: bool,
+) -> Option<Vec<u8>> {
+    let cipher = A::new_from_slice(key).expect("the key length matches the AEAD");
+    let nonce = GenericArray::from_slice(nonce);
+    if encrypt {
+        cipher.encrypt(nonce, payload).ok()
+    } else {
+        cipher.decrypt(nonce, payload).ok()
+    }
+}
+
+impl Cipher for ChaChaPoly {
+    const NAME: &'static str = "ChaChaPoly";
+
+    fn encrypt(key: &[u8; KEY_LENGTH], nonce: u64, ad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mut bytes = [0u8; 12];
+        bytes[4..].copy_from_slice(&nonce.to_le_bytes());
+        let payload = Payload {
+            msg: plaintext,
+            aad: ad,
+        };
+        crypt::<chacha20poly1305::ChaCha20Poly1305>(key, &bytes, payload, true)
+            .expect("Noise messages are short enough for ChaCha20-Poly1305")
+    }
+
+    fn decrypt(
+        key: &[u8; KEY_LENGTH],
+        nonce: u64,
+        ad: &[u8],
+        ciphertext: &[u8],
+    ) -> Option<Vec<u8>> {
+        let mut bytes = [0u8; 12];
+        bytes[4..].copy_from_slice(&nonce.to_le_bytes());
+        let payload = Payload {
+            msg: ciphertext,
+            aad: ad,
+        };
+        crypt::<chacha20poly1305::ChaCha20Poly1305>(key, &bytes, payload, false)
+    }
+}
+
+impl Cipher for AesGcm {
+    const NAME: &'static str = "AESGCM";
+
+    fn encrypt(key: &[u8; KEY_LENGTH], nonce: u64, ad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mut bytes = [0u8; 12];
+        bytes[4..].copy_from_slice(&nonce.to_be_bytes());
+        let payload = Payload {
+            msg: plaintext,
+            aad: ad,
+        };
+        crypt::<Aes256Gcm>(key, &bytes, payload, true)
+            .expect("Noise messages are short enough for AES-256-GCM")
+    }
+
+    fn decrypt(
+        key: &[u8; KEY_LENGTH],
+        nonce: u64,
+        ad: &[u8],
+        ciphertext: &[u8],
+    ) -> Option<Vec<u8>> {
+        let mut bytes = [0u8; 12];
+        bytes[4..].copy_from_slice(&nonce.to_be_bytes());
+        let payload = Payload {
+            msg: ciphertext,
+            aad: ad,
+        };
+        crypt::<Aes256Gcm>(key, &bytes, payload, false)
+    }
+}
+
+/// Hashes the concatenation of `inputs` with the digest `D`
+fn digest<D: Digest>(inputs: &[&[u8]], output: &mut [u8]) {
+    let mut hasher = D::new();
+    for input in inputs {
+        hasher.update(input);
+    }
+    output.copy_from_slice(&hasher.finalize());
+}
+
+impl Hash for Sha256 {
+    const NAME: &'static str = "SHA256";
+    const HASH_LENGTH: usize = 32;
+    const BLOCK_LENGTH: usize = 64;
+
+    fn hash(inputs: &[&[u8]], output: &mut [u8]) {
+        digest::<sha2::Sha256>(inputs, output)
+    }
+}
+
+impl Hash for Sha512 {
+    const NAME: &'static str = "SHA512";
+    const HASH_LENGTH: usize = 64;
+    const BLOCK_LENGTH: usize = 128;
+
+    fn hash(inputs: &[&[u8]], output: &mut [u8]) {
+        digest::<sha2::Sha512>(inputs, output)
+    }
+}
+
+impl Hash for Blake2s {
+    const NAME: &'static str = "BLAKE2s";
+    const HASH_LENGTH: usize = 32;
+    const BLOCK_LENGTH: usize = 64;
+
+    fn hash(inputs: &[&[u8]], output: &mut [u8]) {
+        digest::<blake2::Blake2s256>(inputs, output)
+    }
+}
+
+impl Hash for Blake2b {
+    const NAME: &'static str = "BLAKE2b";
+    const HASH_LENGTH: usize = 64;
+    const BLOCK_LENGTH: usize = 128;
+
+    fn hash(inputs: &[&[u8]], output: &mut [u8]) {
+        digest::<blake2::Blake2b512>(inputs, output)
+    }
+}
+
+/// HMAC-HASH(key, data), where `key` is at most BLOCKLEN bytes, see RFC 2104
+fn hmac<H: Hash>(key: &[u8], data: &[&[u8]], output: &mut [u8]) {
+    let mut inner_pad = [0x36u8; MAX_BLOCK_LENGTH];
+    let mut outer_pad = [0x5cu8; MAX_BLOCK_LENGTH];
+    for (i, byte) in key.iter().enumerate() {
+        inner_pad[i] ^= byte;
+        outer_pad[i] ^= byte;
+    }
+
+    let mut inputs = Vec::with_capacity(data.len() + 1);
+    inputs.push(&inner_pad[..H::BLOCK_LENGTH]);
+    inputs.extend_from_slice(data);
+    let mut inner = [0u8; MAX_HASH_LENGTH];
+    H::hash(&inputs, &mut inner[..H::HASH_LENGTH]);
+    H::hash(
+        &[&outer_pad[..H::BLOCK_LENGTH], &inner[..H::HASH_LENGTH]],
+        output,
+    );
+}
+
+/// HKDF(chaining_key, input_key_material, num_outputs) of section 4.3, with one output per slice of `outputs`
+///
+/// Each output has HASHLEN bytes, and there are two or three of them.
+pub(crate) fn hkdf<H: Hash>(
+    chaining_key: &[u8],
+    input_key_material: &[u8],
+    outputs: &mut [&mut [u8]],
+) {
+    let mut temp_key = [0u8; MAX_HASH_LENGTH];
+    let temp_key = &mut temp_key[..H::HASH_LENGTH];
+    hmac::<H>(chaining_key, &[input_key_material], temp_key);
+
+    let mut previous = [0u8; MAX_HASH_LENGTH];
+    for (i, output) in outputs.iter_mut().enumerate() {
+        let counter = [i as u8 + 1];
+        let data: &[&[u8]] = if i == 0 {
+            &[&counter]
+        } else {
+            &[&previous[..H::HASH_LENGTH], &counter]
+        };
+        hmac::<H>(temp_key, data, output);
+        previous[..H::HASH_LENGTH].copy_from_slice(output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_hmac() {
+        // RFC 4231, test case 2
+        let mut output = [0u8; 64];
+        hmac::<Sha512>(
+            b"Jefe",
+            &[b"what do ya want ", b"for nothing?"],
+            &mut output,
+        );
+        assert_eq!(
+            output,
+            hex!(
+                "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554"
+                "9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+            )
+        );
+        let mut output = [0u8; 32];
+        hmac::<Sha256>(b"Jefe", &[b"what do ya want for nothing?"], &mut output);
+        assert_eq!(
+            output,
+            hex!("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+    }
+
+    #[test]
+    fn test_rekey() {
+        let key = [7u8; KEY_LENGTH];
+        for new_key in [ChaChaPoly::rekey(&key), AesGcm::rekey(&key)] {
+            assert_ne!(new_key, key);
+        }
+        let ciphertext = ChaChaPoly::encrypt(&key, 0, b"ad", b"message");
+        assert_eq!(ciphertext.len(), 7 + TAG_LENGTH);
+        assert_eq!(
+            ChaChaPoly::decrypt(&key, 0, b"ad", &ciphertext).unwrap(),
+            b"message"
+        );
+        assert!(ChaChaPoly::decrypt(&key, 1, b"ad", &ciphertext).is_none());
+        assert!(AesGcm::decrypt(&key, 0, b"ad", &ciphertext).is_none());
+    }
+}
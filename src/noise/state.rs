@@ -0,0 +1,252 @@
+// The CipherState and SymmetricState objects, see sections 5.1 and 5.2 of the specification
+
+use super::primitives::{hkdf, Cipher, Hash, KEY_LENGTH, MAX_HASH_LENGTH, TAG_LENGTH};
+use super::{NoiseError, MAX_MESSAGE_LENGTH};
+use std::marker::PhantomData;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// A cipher key and its nonce, which encrypt the messages in one direction
+///
+/// After the handshake, a `CipherState` encrypts the transport messages. The nonce is
+/// incremented with each message, so the messages must be decrypted in the order they were
+/// encrypted.
+pub struct CipherState<C: Cipher> {
+    key: Option<[u8; KEY_LENGTH]>,
+    nonce: u64,
+    cipher: PhantomData<C>,
+}
+
+#[cfg(feature = "zeroize")]
+impl<C: Cipher> Drop for CipherState<C> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.as_mut() {
+            key.zeroize();
+        }
+    }
+}
+
+impl<C: Cipher> CipherState<C> {
+    pub(crate) fn new(key: Option<[u8; KEY_LENGTH]>) -> CipherState<C> {
+        CipherState {
+            key,
+            nonce: 0,
+            cipher: PhantomData,
+        }
+    }
+
+    /// Returns whether the state has a key, which is always the case after the handshake
+    pub fn has_key(&self) -> bool {
+        self.key.is_some()
+    }
+
+    /// Returns the nonce of the next message
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Sets the nonce of the next message, for transports which deliver messages out of order
+    pub fn set_nonce(&mut self, nonce: u64) {
+        self.nonce = nonce;
+    }
+
+    /// Encrypts `plaintext` with the associated data `ad`, and increments the nonce
+    ///
+    /// Without a key, which only happens during the handshake, the plaintext is returned as it is.
+    pub fn encrypt_with_ad(&mut self, ad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        let key = match &self.key {
+            Some(key) => key,
+            None => return Ok(plaintext.to_vec()),
+        };
+        if plaintext.len() + TAG_LENGTH > MAX_MESSAGE_LENGTH {
+            return Err(NoiseError::MessageTooLong);
+        }
+        // The nonce 2^64 - 1 is reserved for rekeying
+        if self.nonce == u64::MAX {
+            return Err(NoiseError::NonceExhausted);
+        }
+        let ciphertext = C::encrypt(key, self.nonce, ad, plaintext);
+        self.nonce += 1;
+        Ok(ciphertext)
+    }
+
+    /// Decrypts `ciphertext` with the associated data `ad`, and increments the nonce on success
+    ///
+    /// Without a key, which only happens during the handshake, the ciphertext is returned as it is.
+    pub fn decrypt_with_ad(&mut self, ad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        let key = match &self.key {
+            Some(key) => key,
+            None => return Ok(ciphertext.to_vec()),
+        };
+        if ciphertext.len() > MAX_MESSAGE_LENGTH {
+            return Err(NoiseError::MessageTooLong);
+        }
+        if self.nonce == u64::MAX {
+            return Err(NoiseError::NonceExhausted);
+        }
+        let plaintext =
+            C::decrypt(key, self.nonce, ad, ciphertext).ok_or(NoiseError::DecryptFailed)?;
+        self.nonce += 1;
+        Ok(plaintext)
+    }
+
+    /// Replaces the key with a key derived from it, see section 11.3
+    ///
+    /// The nonce is not reset. Both parties must rekey at the same point of the transport.
+    pub fn rekey(&mut self) {
+        if let Some(key) = self.key.as_mut() {
+            *key = C::rekey(key);
+        }
+    }
+}
+
+/// The chaining key and the handshake hash, along with the CipherState of the handshake
+pub(crate) struct SymmetricState<C: Cipher, H: Hash> {
+    cipher_state: CipherState<C>,
+    chaining_key: [u8; MAX_HASH_LENGTH],
+    handshake_hash: [u8; MAX_HASH_LENGTH],
+    hash: PhantomData<H>,
+}
+
+#[cfg(feature = "zeroize")]
+impl<C: Cipher, H: Hash> Drop for SymmetricState<C, H> {
+    fn drop(&mut self) {
+        self.chaining_key.zeroize();
+    }
+}
+
+/// Truncates an output of HKDF to a cipher key
+fn cipher_key(output: &[u8]) -> [u8; KEY_LENGTH] {
+    let mut key = [0u8; KEY_LENGTH];
+    key.copy_from_slice(&output[..KEY_LENGTH]);
+    key
+}
+
+impl<C: Cipher, H: Hash> SymmetricState<C, H> {
+    /// InitializeSymmetric(protocol_name)
+    pub(crate) fn new(protocol_name: &[u8]) -> SymmetricState<C, H> {
+        let mut handshake_hash = [0u8; MAX_HASH_LENGTH];
+        if protocol_name.len() <= H::HASH_LENGTH {
+            handshake_hash[..protocol_name.len()].copy_from_slice(protocol_name);
+        } else {
+            H::hash(&[protocol_name], &mut handshake_hash[..H::HASH_LENGTH]);
+        }
+        SymmetricState {
+            cipher_state: CipherState::new(None),
+            chaining_key: handshake_hash,
+            handshake_hash,
+            hash: PhantomData,
+        }
+    }
+
+    /// MixKey(input_key_material)
+    pub(crate) fn mix_key(&mut self, input_key_material: &[u8]) {
+        let mut chaining_key = [0u8; MAX_HASH_LENGTH];
+        let mut temp_key = [0u8; MAX_HASH_LENGTH];
+        hkdf::<H>(
+            &self.chaining_key[..H::HASH_LENGTH],
+            input_key_material,
+            &mut [
+                &mut chaining_key[..H::HASH_LENGTH],
+                &mut temp_key[..H::HASH_LENGTH],
+            ],
+        );
+        self.chaining_key = chaining_key;
+        self.cipher_state = CipherState::new(Some(cipher_key(&temp_key)));
+
+        #[cfg(feature = "zeroize")]
+        {
+            chaining_key.zeroize();
+            temp_key.zeroize();
+        }
+    }
+
+    /// MixHash(data)
+    pub(crate) fn mix_hash(&mut self, data: &[u8]) {
+        let mut handshake_hash = [0u8; MAX_HASH_LENGTH];
+        H::hash(
+            &[&self.handshake_hash[..H::HASH_LENGTH], data],
+            &mut handshake_hash[..H::HASH_LENGTH],
+        );
+        self.handshake_hash = handshake_hash;
+    }
+
+    /// MixKeyAndHash(input_key_material), which mixes in a PSK
+    pub(crate) fn mix_key_and_hash(&mut self, input_key_material: &[u8]) {
+        let mut chaining_key = [0u8; MAX_HASH_LENGTH];
+        let mut temp_hash = [0u8; MAX_HASH_LENGTH];
+        let mut temp_key = [0u8; MAX_HASH_LENGTH];
+        hkdf::<H>(
+            &self.chaining_key[..H::HASH_LENGTH],
+            input_key_material,
+            &mut [
+                &mut chaining_key[..H::HASH_LENGTH],
+                &mut temp_hash[..H::HASH_LENGTH],
+                &mut temp_key[..H::HASH_LENGTH],
+            ],
+        );
+        self.chaining_key = chaining_key;
+        self.mix_hash(&temp_hash[..H::HASH_LENGTH]);
+        self.cipher_state = CipherState::new(Some(cipher_key(&temp_key)));
+
+        #[cfg(feature = "zeroize")]
+        {
+            chaining_key.zeroize();
+            temp_key.zeroize();
+        }
+    }
+
+    /// GetHandshakeHash()
+    pub(crate) fn handshake_hash(&self) -> &[u8] {
+        &self.handshake_hash[..H::HASH_LENGTH]
+    }
+
+    /// Returns whether the handshake messages are encrypted yet
+    pub(crate) fn has_key(&self) -> bool {
+        self.cipher_state.has_key()
+    }
+
+    /// EncryptAndHash(plaintext)
+    pub(crate) fn encrypt_and_hash(&mut self, plaintext: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        let ciphertext = self
+            .cipher_state
+            .encrypt_with_ad(&self.handshake_hash[..H::HASH_LENGTH], plaintext)?;
+        self.mix_hash(&ciphertext);
+        Ok(ciphertext)
+    }
+
+    /// DecryptAndHash(ciphertext)
+    pub(crate) fn decrypt_and_hash(&mut self, ciphertext: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        let plaintext = self
+            .cipher_state
+            .decrypt_with_ad(&self.handshake_hash[..H::HASH_LENGTH], ciphertext)?;
+        self.mix_hash(ciphertext);
+        Ok(plaintext)
+    }
+
+    /// Split(), which returns the CipherStates of the initiator to the responder and of the responder to the initiator
+    pub(crate) fn split(&self) -> (CipherState<C>, CipherState<C>) {
+        let mut temp_key1 = [0u8; MAX_HASH_LENGTH];
+        let mut temp_key2 = [0u8; MAX_HASH_LENGTH];
+        hkdf::<H>(
+            &self.chaining_key[..H::HASH_LENGTH],
+            &[],
+            &mut [
+                &mut temp_key1[..H::HASH_LENGTH],
+                &mut temp_key2[..H::HASH_LENGTH],
+            ],
+        );
+        let cipher_states = (
+            CipherState::new(Some(cipher_key(&temp_key1))),
+            CipherState::new(Some(cipher_key(&temp_key2))),
+        );
+
+        #[cfg(feature = "zeroize")]
+        {
+            temp_key1.zeroize();
+            temp_key2.zeroize();
+        }
+
+        cipher_states
+    }
+}
@@ -1 +1,5 @@
+use crate::constants::TWISTED_EDWARDS_BASE_POINT;
+use crate::ristretto::RistrettoPoint;
 
+/// The generator of Ristretto448, which is the generator of Decaf448
+pub const RISTRETTO_BASEPOINT: RistrettoPoint = RistrettoPoint(TWISTED_EDWARDS_BASE_POINT);
@@ -1,6 +1,12 @@
-// This will be the module for Ristretto over Ed448
+// This is the module for Ristretto over Ed448
+//
+// Ristretto builds a prime-order group from a curve whose cofactor is 4 or 8. For the cofactor
+// 4 of Ed448 it is the same construction as Decaf, so Ristretto448 is decaf448 from RFC 9496:
+// the encodings are byte for byte the ones of the decaf module, and points convert between the
+// two types with `From`. The separate type is kept for code written against the Ristretto API.
 
 pub mod constants;
+mod ops;
 pub mod ristretto;
 
 pub use ristretto::{CompressedRistretto, RistrettoPoint};
@@ -0,0 +1,77 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::{curve::scalar_mul::double_and_add, Scalar};
+
+use super::RistrettoPoint;
+
+// Scalar multiplication
+
+impl Mul<&Scalar> for &RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn mul(self, scalar: &Scalar) -> RistrettoPoint {
+        RistrettoPoint(double_and_add(&self.0, scalar))
+    }
+}
+impl Mul<&RistrettoPoint> for &Scalar {
+    type Output = RistrettoPoint;
+    fn mul(self, point: &RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint(double_and_add(&point.0, self))
+    }
+}
+impl Mul<RistrettoPoint> for Scalar {
+    type Output = RistrettoPoint;
+    fn mul(self, point: RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint(double_and_add(&point.0, &self))
+    }
+}
+impl Mul<Scalar> for RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn mul(self, scalar: Scalar) -> RistrettoPoint {
+        RistrettoPoint(double_and_add(&self.0, &scalar))
+    }
+}
+
+// Point addition
+
+impl Add<&RistrettoPoint> for &RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn add(self, other: &RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint(self.0.to_extensible().add_extended(&other.0).to_extended())
+    }
+}
+impl Add<RistrettoPoint> for RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn add(self, other: RistrettoPoint) -> RistrettoPoint {
+        (&self).add(&other)
+    }
+}
+
+// Point subtraction
+
+impl Sub<&RistrettoPoint> for &RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn sub(self, other: &RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint(self.0.to_extensible().sub_extended(&other.0).to_extended())
+    }
+}
+impl Sub<RistrettoPoint> for RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn sub(self, other: RistrettoPoint) -> RistrettoPoint {
+        (&self).sub(&other)
+    }
+}
+
+// Point negation
+
+impl Neg for &RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn neg(self) -> RistrettoPoint {
+        RistrettoPoint(self.0.negate())
+    }
+}
+impl Neg for RistrettoPoint {
+    type Output = RistrettoPoint;
+    fn neg(self) -> RistrettoPoint {
+        (&self).neg()
+    }
+}
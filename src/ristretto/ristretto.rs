@@ -1,13 +1,19 @@
 #![allow(non_snake_case)]
 
 use crate::curve::twedwards::extended::ExtendedPoint;
+use crate::decaf::{CompressedDecaf, DecafPoint};
+use crate::ristretto::constants::RISTRETTO_BASEPOINT;
 use std::fmt;
-use subtle::{Choice, ConstantTimeEq};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 
-pub struct RistrettoPoint(ExtendedPoint);
+/// An element of the prime-order group Ristretto448, represented by a point on the twisted curve
+///
+/// Two points represent the same element if they differ by a point of order 2.
+#[derive(Copy, Clone, Debug)]
+pub struct RistrettoPoint(pub(crate) ExtendedPoint);
 
 #[derive(Copy, Clone)]
-pub struct CompressedRistretto([u8; 56]);
+pub struct CompressedRistretto(pub [u8; 56]);
 
 impl fmt::Debug for CompressedRistretto {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
@@ -34,19 +40,62 @@ impl CompressedRistretto {
     }
 }
 
+impl ConstantTimeEq for RistrettoPoint {
+    fn ct_eq(&self, other: &RistrettoPoint) -> Choice {
+        // P and P + (0, -1) are the same element
+        (self.0.X * other.0.Y).ct_eq(&(self.0.Y * other.0.X))
+    }
+}
+
+impl PartialEq for RistrettoPoint {
+    fn eq(&self, other: &RistrettoPoint) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+impl Eq for RistrettoPoint {}
+
+impl ConditionallySelectable for RistrettoPoint {
+    fn conditional_select(
+        a: &RistrettoPoint,
+        b: &RistrettoPoint,
+        choice: Choice,
+    ) -> RistrettoPoint {
+        RistrettoPoint(ExtendedPoint::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl From<DecafPoint> for RistrettoPoint {
+    fn from(point: DecafPoint) -> RistrettoPoint {
+        RistrettoPoint(point.0)
+    }
+}
+
+impl From<RistrettoPoint> for DecafPoint {
+    fn from(point: RistrettoPoint) -> DecafPoint {
+        DecafPoint(point.0)
+    }
+}
+
 impl RistrettoPoint {
     pub fn identity() -> RistrettoPoint {
         RistrettoPoint(ExtendedPoint::identity())
     }
 
+    pub const fn generator() -> RistrettoPoint {
+        RISTRETTO_BASEPOINT
+    }
+
     pub fn equals(&self, other: &RistrettoPoint) -> bool {
-        let XY = self.0.X * other.0.Y;
-        let YX = self.0.Y * other.0.X;
-        XY == YX
+        self.ct_eq(other).into()
     }
 
+    /// Encodes the element as 56 bytes
+    ///
+    /// Ristretto was designed to quotient out the cofactor 8 of Curve25519. With the cofactor 4
+    /// of Ed448, it reduces to Decaf, so the encoding is the one of decaf448 from RFC 9496 and is
+    /// computed by the decaf module.
     pub fn encode(&self) -> CompressedRistretto {
-        todo!()
+        CompressedRistretto(DecafPoint(self.0).compress().0)
     }
 }
 
@@ -55,7 +104,117 @@ impl CompressedRistretto {
         CompressedRistretto([0; 56])
     }
 
+    /// Decodes an element, which is the decaf448 decoding from RFC 9496
+    ///
+    /// Returns `None` if the encoding is not canonical or is not the encoding of an element.
     pub fn decode(&self) -> Option<RistrettoPoint> {
-        todo!()
+        CompressedDecaf(self.0)
+            .decompress()
+            .map(RistrettoPoint::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::BASEPOINT_ORDER;
+    use crate::Scalar;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_vectors_small_multiples() {
+        // The multiples 0 to 15 of the generator, RFC 9496 appendix A.2
+        let encodings = [
+            hex!("0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
+            hex!("6666666666666666666666666666666666666666666666666666666633333333333333333333333333333333333333333333333333333333"),
+            hex!("c898eb4f87f97c564c6fd61fc7e49689314a1f818ec85eeb3bd5514ac816d38778f69ef347a89fca817e66defdedce178c7cc709b2116e75"),
+            hex!("a0c09bf2ba7208fda0f4bfe3d0f5b29a543012306d43831b5adc6fe7f8596fa308763db15468323b11cf6e4aeb8c18fe44678f44545a69bc"),
+            hex!("b46f1836aa287c0a5a5653f0ec5ef9e903f436e21c1570c29ad9e5f596da97eeaf17150ae30bcb3174d04bc2d712c8c7789d7cb4fda138f4"),
+            hex!("1c5bbecf4741dfaae79db72dface00eaaac502c2060934b6eaaeca6a20bd3da9e0be8777f7d02033d1b15884232281a41fc7f80eed04af5e"),
+            hex!("86ff0182d40f7f9edb7862515821bd67bfd6165a3c44de95d7df79b8779ccf6460e3c68b70c16aaa280f2d7b3f22d745b97a89906cfc476c"),
+            hex!("502bcb6842eb06f0e49032bae87c554c031d6d4d2d7694efbf9c468d48220c50f8ca28843364d70cee92d6fe246e61448f9db9808b3b2408"),
+            hex!("0c9810f1e2ebd389caa789374d78007974ef4d17227316f40e578b336827da3f6b482a4794eb6a3975b971b5e1388f52e91ea2f1bcb0f912"),
+            hex!("20d41d85a18d5657a29640321563bbd04c2ffbd0a37a7ba43a4f7d263ce26faf4e1f74f9f4b590c69229ae571fe37fa639b5b8eb48bd9a55"),
+            hex!("e6b4b8f408c7010d0601e7eda0c309a1a42720d6d06b5759fdc4e1efe22d076d6c44d42f508d67be462914d28b8edce32e7094305164af17"),
+            hex!("be88bbb86c59c13d8e9d09ab98105f69c2d1dd134dbcd3b0863658f53159db64c0e139d180f3c89b8296d0ae324419c06fa87fc7daaf34c1"),
+            hex!("a456f9369769e8f08902124a0314c7a06537a06e32411f4f93415950a17badfa7442b6217434a3a05ef45be5f10bd7b2ef8ea00c431edec5"),
+            hex!("186e452c4466aa4383b4c00210d52e7922dbf9771e8b47e229a9b7b73c8d10fd7ef0b6e41530f91f24a3ed9ab71fa38b98b2fe4746d51d68"),
+            hex!("4ae7fdcae9453f195a8ead5cbe1a7b9699673b52c40ab27927464887be53237f7f3a21b938d40d0ec9e15b1d5130b13ffed81373a53e2b43"),
+            hex!("841981c3bfeec3f60cfeca75d9d8dc17f46cf0106f2422b59aec580a58f342272e3a5e575a055ddb051390c54c24c6ecb1e0aceb075f6056"),
+        ];
+        let mut point = RistrettoPoint::identity();
+        for (i, encoding) in encodings.iter().enumerate() {
+            let compressed = CompressedRistretto(*encoding);
+            assert_eq!(point.encode(), compressed);
+            assert_eq!(compressed.decode(), Some(point));
+            assert_eq!(RistrettoPoint::generator() * Scalar::from(i as u32), point);
+            point = point + RistrettoPoint::generator();
+        }
+    }
+
+    #[test]
+    fn test_invalid_encodings() {
+        let mut p = [0xff; 56];
+        p[0] = 0xfe;
+        p[28] = 0xfe;
+        let mut one = [0u8; 56];
+        one[0] = 1;
+        // p is a non-canonical encoding of 0, 1 is negative
+        for bytes in [p, one, [0xff; 56]] {
+            assert_eq!(CompressedRistretto(bytes).decode(), None);
+        }
+        // About half of the non-negative s are not encodings
+        let decoded = (1..64u8)
+            .map(|s| {
+                let mut bytes = [0u8; 56];
+                bytes[0] = 2 * s;
+                CompressedRistretto(bytes).decode()
+            })
+            .filter(Option::is_some)
+            .count();
+        assert!(decoded > 0 && decoded < 63);
+    }
+
+    #[test]
+    fn test_group_operations() {
+        let B = RistrettoPoint::generator();
+        let a = Scalar::from(1234567u32);
+        let b = Scalar::from(7654321u32);
+        let A = B * a;
+        let C = B * b;
+
+        assert_eq!(A + C, B * (a + b));
+        assert_eq!(A - C, B * (a - b));
+        assert_eq!(-A + A, RistrettoPoint::identity());
+        assert_eq!(B * BASEPOINT_ORDER, RistrettoPoint::identity());
+        assert_eq!(b * A, a * C);
+
+        // Encoding is well-defined on the equivalence classes
+        let torqued = RistrettoPoint(A.0.torque());
+        assert_eq!(torqued, A);
+        assert_eq!(torqued.encode(), A.encode());
+        assert_eq!(A.encode().decode(), Some(A));
+
+        let selected = RistrettoPoint::conditional_select(&A, &C, Choice::from(1));
+        assert_eq!(selected, C);
+        assert_ne!(A, C);
+    }
+
+    #[test]
+    fn test_decaf_equivalence() {
+        // Ristretto448 and Decaf448 are the same group with the same encoding
+        let mut ristretto = RistrettoPoint::generator();
+        let mut decaf = DecafPoint::generator();
+        for _ in 0..32 {
+            assert_eq!(ristretto.encode().0, decaf.compress().0);
+            assert_eq!(DecafPoint::from(ristretto), decaf);
+            assert_eq!(RistrettoPoint::from(decaf), ristretto);
+            assert_eq!(
+                CompressedRistretto(decaf.compress().0).decode(),
+                Some(ristretto)
+            );
+            ristretto = ristretto + ristretto + RistrettoPoint::generator();
+            decaf = decaf + decaf + DecafPoint::generator();
+        }
     }
 }
@@ -0,0 +1,580 @@
+// This is the module for the X3DH key agreement protocol with X448 keys, as specified by Signal
+// https://signal.org/docs/specifications/x3dh/
+//
+// Bob publishes an identity key IK_B, a signed prekey SPK_B and optionally a one-time prekey
+// OPK_B in a prekey bundle. The signed prekey is signed with the identity key, which is an X448
+// key, with the XEdDSA signatures of the `xeddsa` module. Alice verifies the bundle, generates an
+// ephemeral key EK_A and computes
+//
+//     DH1 = DH(IK_A, SPK_B)    DH2 = DH(EK_A, IK_B)    DH3 = DH(EK_A, SPK_B)    DH4 = DH(EK_A, OPK_B)
+//
+// and SK = KDF(DH1 || DH2 || DH3 || DH4), where DH4 is omitted without a one-time prekey. The KDF
+// is HKDF-SHA512 with a zero salt, over F || KM where F is 57 0xFF bytes for X448, and the info
+// is an application-specific string. Bob computes the same DH outputs from the initial message.
+//
+// The signatures are XEdDSA-Goldilocks signatures, which convert keys with the 4-isogeny rather
+// than the birational map of the XEdDSA specification, so prekey bundles can only be verified
+// by this crate.
+//
+// Encode(PK) is left to the application by the specification: here it is the byte
+// CURVE448_KEY_TYPE followed by the 56-byte u-coordinate. The associated data of the initial
+// message is AD = Encode(IK_A) || Encode(IK_B).
+
+use crate::ed448::{Signature, SignatureError, SIGNATURE_LENGTH};
+use crate::x448::{PublicKey, SharedSecret, StaticSecret, X448_KEY_LENGTH};
+use crate::xeddsa;
+use hkdf::Hkdf;
+use rand_core::{CryptoRng, RngCore};
+use sha2::Sha512;
+use std::fmt;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The byte which identifies Curve448 public keys in Encode(PK)
+pub const CURVE448_KEY_TYPE: u8 = 0x06;
+
+/// The length of an encoded public key, Encode(PK)
+pub const ENCODED_KEY_LENGTH: usize = 1 + X448_KEY_LENGTH;
+
+/// The length of the shared key SK
+pub const SHARED_KEY_LENGTH: usize = 32;
+
+/// The length of the associated data AD = Encode(IK_A) || Encode(IK_B)
+pub const ASSOCIATED_DATA_LENGTH: usize = 2 * ENCODED_KEY_LENGTH;
+
+/// The length of F, which separates the KDF input from a key of another curve
+const PADDING_LENGTH: usize = 57;
+
+/// Errors which can occur when verifying a bundle, parsing a message or computing the shared key
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum X3dhError {
+    /// The signature of the signed prekey does not verify with the identity key
+    InvalidSignature,
+    /// A public key is invalid, or of small order so a DH output is all zero
+    InvalidPublicKey,
+    /// A bundle or message has the wrong length or key type
+    InvalidEncoding,
+    /// The initial message uses a one-time prekey which was not given, or the other way around
+    OneTimePreKeyMismatch,
+}
+
+impl fmt::Display for X3dhError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            X3dhError::InvalidSignature => write!(f, "invalid signed prekey signature"),
+            X3dhError::InvalidPublicKey => write!(f, "invalid public key"),
+            X3dhError::InvalidEncoding => write!(f, "invalid encoding"),
+            X3dhError::OneTimePreKeyMismatch => write!(f, "one-time prekey mismatch"),
+        }
+    }
+}
+
+impl std::error::Error for X3dhError {}
+
+/// Encode(PK)
+fn encode(key: &PublicKey) -> [u8; ENCODED_KEY_LENGTH] {
+    let mut bytes = [0u8; ENCODED_KEY_LENGTH];
+    bytes[0] = CURVE448_KEY_TYPE;
+    bytes[1..].copy_from_slice(key.as_bytes());
+    bytes
+}
+
+/// Decodes Encode(PK), rejecting other key types
+fn decode(bytes: &[u8]) -> Result<PublicKey, X3dhError> {
+    if bytes[0] != CURVE448_KEY_TYPE {
+        return Err(X3dhError::InvalidEncoding);
+    }
+    let u: [u8; X448_KEY_LENGTH] = bytes[1..ENCODED_KEY_LENGTH]
+        .try_into()
+        .expect("the slice has the length of a key");
+    Ok(PublicKey::from(u))
+}
+
+/// Reads a big-endian key identifier
+fn decode_id(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes(bytes[..4].try_into().expect("the slice has 4 bytes"))
+}
+
+/// The prekey bundle which Bob publishes, with an optional one-time prekey
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PreKeyBundle {
+    /// The identity key IK_B
+    pub identity_key: PublicKey,
+    /// The identifier of the signed prekey, which Bob uses to find its secret key
+    pub signed_prekey_id: u32,
+    /// The signed prekey SPK_B
+    pub signed_prekey: PublicKey,
+    /// The XEdDSA-Goldilocks signature of Encode(SPK_B) with the identity key
+    pub signature: Signature,
+    /// The identifier of the one-time prekey and the one-time prekey OPK_B
+    pub one_time_prekey: Option<(u32, PublicKey)>,
+}
+
+impl PreKeyBundle {
+    /// The length of the encoding of a bundle without a one-time prekey
+    pub const LENGTH: usize = ENCODED_KEY_LENGTH + 4 + ENCODED_KEY_LENGTH + SIGNATURE_LENGTH;
+
+    /// The length of the encoding of a bundle with a one-time prekey
+    pub const LENGTH_WITH_ONE_TIME_PREKEY: usize = PreKeyBundle::LENGTH + 4 + ENCODED_KEY_LENGTH;
+
+    /// Creates a bundle, signing the signed prekey with the identity secret key
+    pub fn new<R: RngCore + CryptoRng>(
+        identity_secret: &StaticSecret,
+        signed_prekey_id: u32,
+        signed_prekey: &PublicKey,
+        one_time_prekey: Option<(u32, PublicKey)>,
+        rng: &mut R,
+    ) -> PreKeyBundle {
+        PreKeyBundle {
+            identity_key: PublicKey::from(identity_secret),
+            signed_prekey_id,
+            signed_prekey: *signed_prekey,
+            signature: xeddsa::sign(identity_secret, &encode(signed_prekey), rng),
+            one_time_prekey,
+        }
+    }
+
+    /// Verifies the signature of the signed prekey
+    pub fn verify(&self) -> Result<(), X3dhError> {
+        xeddsa::verify(
+            &self.identity_key,
+            &encode(&self.signed_prekey),
+            &self.signature,
+        )
+        .map_err(|err| match err {
+            SignatureError::InvalidPublicKey => X3dhError::InvalidPublicKey,
+            _ => X3dhError::InvalidSignature,
+        })
+    }
+
+    /// Encodes the bundle as Encode(IK_B) || id || Encode(SPK_B) || signature, followed by
+    /// id || Encode(OPK_B) with a one-time prekey
+    ///
+    /// The identifiers are 4 bytes in big-endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(PreKeyBundle::LENGTH_WITH_ONE_TIME_PREKEY);
+        bytes.extend_from_slice(&encode(&self.identity_key));
+        bytes.extend_from_slice(&self.signed_prekey_id.to_be_bytes());
+        bytes.extend_from_slice(&encode(&self.signed_prekey));
+        bytes.extend_from_slice(&self.signature.to_bytes());
+        if let Some((id, key)) = &self.one_time_prekey {
+            bytes.extend_from_slice(&id.to_be_bytes());
+            bytes.extend_from_slice(&encode(key));
+        }
+        bytes
+    }
+
+    /// Parses a bundle from its encoding, without verifying the signature
+    pub fn from_bytes(bytes: &[u8]) -> Result<PreKeyBundle, X3dhError> {
+        if bytes.len() != PreKeyBundle::LENGTH
+            && bytes.len() != PreKeyBundle::LENGTH_WITH_ONE_TIME_PREKEY
+        {
+            return Err(X3dhError::InvalidEncoding);
+        }
+        let (identity_key, rest) = bytes.split_at(ENCODED_KEY_LENGTH);
+        let (signed_prekey_id, rest) = rest.split_at(4);
+        let (signed_prekey, rest) = rest.split_at(ENCODED_KEY_LENGTH);
+        let (signature, rest) = rest.split_at(SIGNATURE_LENGTH);
+        let one_time_prekey = if rest.is_empty() {
+            None
+        } else {
+            Some((decode_id(rest), decode(&rest[4..])?))
+        };
+        Ok(PreKeyBundle {
+            identity_key: decode(identity_key)?,
+            signed_prekey_id: decode_id(signed_prekey_id),
+            signed_prekey: decode(signed_prekey)?,
+            signature: Signature::try_from(signature)
+                .expect("the slice has the length of a signature"),
+            one_time_prekey,
+        })
+    }
+}
+
+/// The keys of the initial message which Alice sends to Bob, along with her first ciphertext
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InitialMessage {
+    /// The identity key IK_A
+    pub identity_key: PublicKey,
+    /// The ephemeral key EK_A
+    pub ephemeral_key: PublicKey,
+    /// The identifier of the signed prekey of the bundle
+    pub signed_prekey_id: u32,
+    /// The identifier of the one-time prekey of the bundle, if it had one
+    pub one_time_prekey_id: Option<u32>,
+}
+
+impl InitialMessage {
+    /// The length of the encoding of a message without a one-time prekey
+    pub const LENGTH: usize = 2 * ENCODED_KEY_LENGTH + 4;
+
+    /// The length of the encoding of a message with a one-time prekey
+    pub const LENGTH_WITH_ONE_TIME_PREKEY: usize = InitialMessage::LENGTH + 4;
+
+    /// Encodes the message as Encode(IK_A) || Encode(EK_A) || id, followed by the identifier
+    /// of the one-time prekey if there is one
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(InitialMessage::LENGTH_WITH_ONE_TIME_PREKEY);
+        bytes.extend_from_slice(&encode(&self.identity_key));
+        bytes.extend_from_slice(&encode(&self.ephemeral_key));
+        bytes.extend_from_slice(&self.signed_prekey_id.to_be_bytes());
+        if let Some(id) = self.one_time_prekey_id {
+            bytes.extend_from_slice(&id.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Parses a message from its encoding
+    pub fn from_bytes(bytes: &[u8]) -> Result<InitialMessage, X3dhError> {
+        if bytes.len() != InitialMessage::LENGTH
+            && bytes.len() != InitialMessage::LENGTH_WITH_ONE_TIME_PREKEY
+        {
+            return Err(X3dhError::InvalidEncoding);
+        }
+        let (identity_key, rest) = bytes.split_at(ENCODED_KEY_LENGTH);
+        let (ephemeral_key, rest) = rest.split_at(ENCODED_KEY_LENGTH);
+        let one_time_prekey_id = (rest.len() > 4).then(|| decode_id(&rest[4..]));
+        Ok(InitialMessage {
+            identity_key: decode(identity_key)?,
+            ephemeral_key: decode(ephemeral_key)?,
+            signed_prekey_id: decode_id(rest),
+            one_time_prekey_id,
+        })
+    }
+}
+
+/// The shared key SK and the associated data AD of a key agreement
+///
+/// Both parties should authenticate AD with the AEAD which encrypts the initial ciphertext.
+pub struct SharedKey {
+    key: [u8; SHARED_KEY_LENGTH],
+    associated_data: [u8; ASSOCIATED_DATA_LENGTH],
+}
+
+impl fmt::Debug for SharedKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedKey(..)")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SharedKey {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl SharedKey {
+    /// View the shared key SK as bytes
+    pub fn as_bytes(&self) -> &[u8; SHARED_KEY_LENGTH] {
+        &self.key
+    }
+
+    /// The associated data AD = Encode(IK_A) || Encode(IK_B)
+    pub fn associated_data(&self) -> &[u8; ASSOCIATED_DATA_LENGTH] {
+        &self.associated_data
+    }
+}
+
+/// Computes DH(secret, public), rejecting the all-zero output of small order keys
+fn dh(secret: &StaticSecret, public: &PublicKey) -> Result<SharedSecret, X3dhError> {
+    secret
+        .diffie_hellman(public)
+        .ok_or(X3dhError::InvalidPublicKey)
+}
+
+/// SK = HKDF-SHA512(salt = 0, ikm = F || DH1 || ... || DHn, info), and AD
+fn derive(
+    info: &[u8],
+    dhs: &[SharedSecret],
+    identity_a: &PublicKey,
+    identity_b: &PublicKey,
+) -> SharedKey {
+    #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+    let mut ikm = [0xffu8; PADDING_LENGTH + 4 * X448_KEY_LENGTH];
+    for (i, dh) in dhs.iter().enumerate() {
+        let start = PADDING_LENGTH + i * X448_KEY_LENGTH;
+        ikm[start..start + X448_KEY_LENGTH].copy_from_slice(dh.as_bytes());
+    }
+    let length = PADDING_LENGTH + dhs.len() * X448_KEY_LENGTH;
+
+    let mut key = [0u8; SHARED_KEY_LENGTH];
+    Hkdf::<Sha512>::new(Some(&[0u8; 64]), &ikm[..length])
+        .expand(info, &mut key)
+        .expect("32 bytes is a valid length for HKDF-SHA512");
+
+    #[cfg(feature = "zeroize")]
+    ikm.zeroize();
+
+    let mut associated_data = [0u8; ASSOCIATED_DATA_LENGTH];
+    associated_data[..ENCODED_KEY_LENGTH].copy_from_slice(&encode(identity_a));
+    associated_data[ENCODED_KEY_LENGTH..].copy_from_slice(&encode(identity_b));
+    SharedKey {
+        key,
+        associated_data,
+    }
+}
+
+/// Computes the shared key of Alice with the given ephemeral secret key
+fn initiate_with_ephemeral(
+    info: &[u8],
+    identity_secret: &StaticSecret,
+    bundle: &PreKeyBundle,
+    ephemeral_secret: &StaticSecret,
+) -> Result<(SharedKey, InitialMessage), X3dhError> {
+    bundle.verify()?;
+
+    let mut dhs = vec![
+        dh(identity_secret, &bundle.signed_prekey)?,
+        dh(ephemeral_secret, &bundle.identity_key)?,
+        dh(ephemeral_secret, &bundle.signed_prekey)?,
+    ];
+    if let Some((_, one_time_prekey)) = &bundle.one_time_prekey {
+        dhs.push(dh(ephemeral_secret, one_time_prekey)?);
+    }
+
+    let identity_key = PublicKey::from(identity_secret);
+    let message = InitialMessage {
+        identity_key,
+        ephemeral_key: PublicKey::from(ephemeral_secret),
+        signed_prekey_id: bundle.signed_prekey_id,
+        one_time_prekey_id: bundle.one_time_prekey.map(|(id, _)| id),
+    };
+    let shared_key = derive(info, &dhs, &identity_key, &bundle.identity_key);
+    Ok((shared_key, message))
+}
+
+/// Verifies Bob's prekey bundle and computes the shared key of Alice, along with the initial message
+///
+/// `info` identifies the application. The ephemeral secret key is generated with `rng` and
+/// discarded afterwards.
+pub fn initiate<R: RngCore + CryptoRng>(
+    info: &[u8],
+    identity_secret: &StaticSecret,
+    bundle: &PreKeyBundle,
+    rng: &mut R,
+) -> Result<(SharedKey, InitialMessage), X3dhError> {
+    let ephemeral_secret = StaticSecret::random_from_rng(rng);
+    initiate_with_ephemeral(info, identity_secret, bundle, &ephemeral_secret)
+}
+
+/// Computes the shared key of Bob from Alice's initial message
+///
+/// Bob looks up the secret keys of the signed prekey and of the one-time prekey with the
+/// identifiers of the message. The caller must delete the one-time prekey afterwards.
+pub fn respond(
+    info: &[u8],
+    identity_secret: &StaticSecret,
+    signed_prekey_secret: &StaticSecret,
+    one_time_prekey_secret: Option<&StaticSecret>,
+    message: &InitialMessage,
+) -> Result<SharedKey, X3dhError> {
+    if message.one_time_prekey_id.is_some() != one_time_prekey_secret.is_some() {
+        return Err(X3dhError::OneTimePreKeyMismatch);
+    }
+
+    let mut dhs = vec![
+        dh(signed_prekey_secret, &message.identity_key)?,
+        dh(identity_secret, &message.ephemeral_key)?,
+        dh(signed_prekey_secret, &message.ephemeral_key)?,
+    ];
+    if let Some(one_time_prekey_secret) = one_time_prekey_secret {
+        dhs.push(dh(one_time_prekey_secret, &message.ephemeral_key)?);
+    }
+
+    Ok(derive(
+        info,
+        &dhs,
+        &message.identity_key,
+        &PublicKey::from(identity_secret),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::rand_core::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    const INFO: &[u8] = b"X3DH test";
+
+    struct Bob {
+        identity: StaticSecret,
+        signed_prekey: StaticSecret,
+        one_time_prekey: StaticSecret,
+    }
+
+    fn bob(rng: &mut ChaCha20Rng) -> Bob {
+        Bob {
+            identity: StaticSecret::random_from_rng(&mut *rng),
+            signed_prekey: StaticSecret::random_from_rng(&mut *rng),
+            one_time_prekey: StaticSecret::random_from_rng(&mut *rng),
+        }
+    }
+
+    fn bundle(bob: &Bob, one_time_prekey: bool, rng: &mut ChaCha20Rng) -> PreKeyBundle {
+        PreKeyBundle::new(
+            &bob.identity,
+            7,
+            &PublicKey::from(&bob.signed_prekey),
+            one_time_prekey.then(|| (42, PublicKey::from(&bob.one_time_prekey))),
+            rng,
+        )
+    }
+
+    #[test]
+    fn test_key_agreement() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let alice = StaticSecret::random_from_rng(&mut rng);
+        let bob = bob(&mut rng);
+
+        for one_time_prekey in [false, true] {
+            let bundle = bundle(&bob, one_time_prekey, &mut rng);
+            assert_eq!(bundle.verify(), Ok(()));
+            let (alice_key, message) = initiate(INFO, &alice, &bundle, &mut rng).unwrap();
+            assert_eq!(message.signed_prekey_id, 7);
+            assert_eq!(message.one_time_prekey_id, one_time_prekey.then_some(42));
+
+            let bob_key = respond(
+                INFO,
+                &bob.identity,
+                &bob.signed_prekey,
+                one_time_prekey.then_some(&bob.one_time_prekey),
+                &message,
+            )
+            .unwrap();
+            assert_eq!(alice_key.as_bytes(), bob_key.as_bytes());
+            assert_eq!(alice_key.associated_data(), bob_key.associated_data());
+            assert_eq!(
+                alice_key.associated_data()[..ENCODED_KEY_LENGTH],
+                encode(&PublicKey::from(&alice))
+            );
+
+            // Another application derives another key
+            let other = respond(
+                b"other",
+                &bob.identity,
+                &bob.signed_prekey,
+                one_time_prekey.then_some(&bob.one_time_prekey),
+                &message,
+            )
+            .unwrap();
+            assert_ne!(other.as_bytes(), bob_key.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_key_derivation() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let alice = StaticSecret::random_from_rng(&mut rng);
+        let ephemeral = StaticSecret::random_from_rng(&mut rng);
+        let bob = bob(&mut rng);
+        let bundle = bundle(&bob, true, &mut rng);
+        let (shared_key, _) = initiate_with_ephemeral(INFO, &alice, &bundle, &ephemeral).unwrap();
+
+        // SK = HKDF(0, F || DH1 || DH2 || DH3 || DH4, info)
+        let mut ikm = vec![0xff; 57];
+        for (secret, public) in [
+            (&alice, &bob.signed_prekey),
+            (&ephemeral, &bob.identity),
+            (&ephemeral, &bob.signed_prekey),
+            (&ephemeral, &bob.one_time_prekey),
+        ] {
+            let shared = secret.diffie_hellman(&PublicKey::from(public)).unwrap();
+            ikm.extend_from_slice(shared.as_bytes());
+        }
+        let mut expected = [0u8; 32];
+        Hkdf::<Sha512>::new(Some(&[0; 64]), &ikm)
+            .expand(INFO, &mut expected)
+            .unwrap();
+        assert_eq!(shared_key.as_bytes(), &expected);
+    }
+
+    #[test]
+    fn test_encoding() {
+        let mut rng = ChaCha20Rng::seed_from_u64(2);
+        let alice = StaticSecret::random_from_rng(&mut rng);
+        let bob = bob(&mut rng);
+
+        for (one_time_prekey, bundle_length, message_length) in [
+            (false, PreKeyBundle::LENGTH, InitialMessage::LENGTH),
+            (
+                true,
+                PreKeyBundle::LENGTH_WITH_ONE_TIME_PREKEY,
+                InitialMessage::LENGTH_WITH_ONE_TIME_PREKEY,
+            ),
+        ] {
+            let bundle = bundle(&bob, one_time_prekey, &mut rng);
+            let bytes = bundle.to_bytes();
+            assert_eq!(bytes.len(), bundle_length);
+            assert_eq!(PreKeyBundle::from_bytes(&bytes), Ok(bundle));
+            assert_eq!(
+                PreKeyBundle::from_bytes(&bytes[1..]),
+                Err(X3dhError::InvalidEncoding)
+            );
+
+            let (_, message) = initiate(INFO, &alice, &bundle, &mut rng).unwrap();
+            let bytes = message.to_bytes();
+            assert_eq!(bytes.len(), message_length);
+            assert_eq!(InitialMessage::from_bytes(&bytes), Ok(message));
+            assert_eq!(
+                InitialMessage::from_bytes(&bytes[..bytes.len() - 1]),
+                Err(X3dhError::InvalidEncoding)
+            );
+        }
+
+        // Keys of another type
+        let mut bytes = bundle(&bob, false, &mut rng).to_bytes();
+        bytes[ENCODED_KEY_LENGTH + 4] = 0x05;
+        assert_eq!(
+            PreKeyBundle::from_bytes(&bytes),
+            Err(X3dhError::InvalidEncoding)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut rng = ChaCha20Rng::seed_from_u64(3);
+        let alice = StaticSecret::random_from_rng(&mut rng);
+        let bob = bob(&mut rng);
+
+        // The signature covers the signed prekey and is bound to the identity key
+        let mut forged = bundle(&bob, false, &mut rng);
+        forged.signed_prekey = PublicKey::from(&bob.one_time_prekey);
+        assert_eq!(forged.verify(), Err(X3dhError::InvalidSignature));
+        assert_eq!(
+            initiate(INFO, &alice, &forged, &mut rng).unwrap_err(),
+            X3dhError::InvalidSignature
+        );
+        let mut forged = bundle(&bob, false, &mut rng);
+        forged.identity_key = PublicKey::from(&alice);
+        assert_eq!(forged.verify(), Err(X3dhError::InvalidSignature));
+
+        // A small order one-time prekey
+        let mut small_order = bundle(&bob, false, &mut rng);
+        small_order.one_time_prekey = Some((1, PublicKey::from([0u8; 56])));
+        assert_eq!(
+            initiate(INFO, &alice, &small_order, &mut rng).unwrap_err(),
+            X3dhError::InvalidPublicKey
+        );
+
+        let (_, message) = initiate(INFO, &alice, &bundle(&bob, true, &mut rng), &mut rng).unwrap();
+        assert_eq!(
+            respond(INFO, &bob.identity, &bob.signed_prekey, None, &message).unwrap_err(),
+            X3dhError::OneTimePreKeyMismatch
+        );
+        let (_, message) =
+            initiate(INFO, &alice, &bundle(&bob, false, &mut rng), &mut rng).unwrap();
+        assert_eq!(
+            respond(
+                INFO,
+                &bob.identity,
+                &bob.signed_prekey,
+                Some(&bob.one_time_prekey),
+                &message
+            )
+            .unwrap_err(),
+            X3dhError::OneTimePreKeyMismatch
+        );
+    }
+}
@@ -0,0 +1,432 @@
+// This is the module for X448 Diffie-Hellman, as specified in RFC 7748
+// https://www.rfc-editor.org/rfc/rfc7748#section-5
+//
+// X448 does not multiply by a `Scalar` reduced modulo ℓ: the secret is a raw 448 bit
+// integer with the two low bits cleared and the top bit set. `Scalar::from_bytes` loads the
+// bytes without reducing them, so the Montgomery ladder runs over exactly the clamped bits.
+//
+// Public keys are not computed with the ladder. The 4-isogeny `ExtendedPoint::to_montgomery`
+// sends the Ed448 generator to u = 5, so [k]5 is the image of [k]B, which is computed with a
+// precomputed table on the twisted curve. Since k is a multiple of four, [k]B is the untwisted
+// image of [k/4] times the twisted generator.
+//
+// For transports which must look like random bytes, `EphemeralSecret::random_representable`
+// encodes the public key as an Elligator 2 representative.
+
+use crate::curve::edwards::ExtendedPoint;
+use crate::curve::scalar_mul::fixed_base;
+use crate::curve::MontgomeryPoint;
+use crate::field::Scalar;
+use std::fmt;
+use subtle::ConstantTimeEq;
+
+#[cfg(feature = "rand")]
+use crate::curve::montgomery::AffineMontgomeryPoint;
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "rand")]
+use subtle::{Choice, ConditionallySelectable};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The length of an X448 secret key, public key or shared secret, in bytes
+pub const X448_KEY_LENGTH: usize = 56;
+
+/// The u-coordinate of the base point, u = 5
+pub const X448_BASEPOINT_BYTES: [u8; X448_KEY_LENGTH] = MontgomeryPoint::generator().0;
+
+/// Clears the two low bits and sets the top bit of the secret, see decodeScalar448 in RFC 7748
+pub(crate) fn clamp(mut k: [u8; X448_KEY_LENGTH]) -> [u8; X448_KEY_LENGTH] {
+    k[0] &= 252;
+    k[55] |= 128;
+    k
+}
+
+/// Multiplies the point with u-coordinate `u` by the clamped scalar `k`
+fn ladder(k: &[u8; X448_KEY_LENGTH], u: &MontgomeryPoint) -> MontgomeryPoint {
+    #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+    let mut scalar = Scalar::from_bytes(clamp(*k));
+    let point = u * &scalar;
+
+    #[cfg(feature = "zeroize")]
+    scalar.zeroize();
+
+    point
+}
+
+/// Computes [k]B on Ed448 for the clamped scalar `k`, whose image under the 4-isogeny is [k]5
+pub(crate) fn mul_base_edwards(k: &[u8; X448_KEY_LENGTH]) -> ExtendedPoint {
+    let mut scalar = Scalar::from_bytes(clamp(*k));
+    scalar.div_by_four();
+    let point = fixed_base(&scalar).to_untwisted();
+
+    #[cfg(feature = "zeroize")]
+    scalar.zeroize();
+
+    point
+}
+
+/// Computes the public key [k]5 for the clamped scalar `k`, with the same result as the ladder
+fn mul_base(k: &[u8; X448_KEY_LENGTH]) -> MontgomeryPoint {
+    mul_base_edwards(k).to_montgomery()
+}
+
+/// Returns [t]T, where T = (-1, v) is a point of order 4 on Curve448
+#[cfg(feature = "rand")]
+fn low_order_point(t: u8) -> AffineMontgomeryPoint {
+    let mut minus_one = [0xff; X448_KEY_LENGTH];
+    minus_one[0] = 0xfe;
+    minus_one[28] = 0xfe;
+    let point = MontgomeryPoint(minus_one)
+        .to_affine(0)
+        .expect("u = -1 is on the curve");
+    &point * &Scalar::from(t as u32)
+}
+
+/// The X448 function of RFC 7748
+///
+/// The scalar `k` is clamped and non-canonical u-coordinates are reduced modulo p.
+/// No check is made on the result, which is all zero if `u` is of small order;
+/// use [`StaticSecret::diffie_hellman`] for a key exchange.
+pub fn x448(k: [u8; X448_KEY_LENGTH], u: [u8; X448_KEY_LENGTH]) -> [u8; X448_KEY_LENGTH] {
+    ladder(&k, &MontgomeryPoint(u)).0
+}
+
+/// An X448 public key, which is the u-coordinate of a point on Curve448
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PublicKey(pub(crate) MontgomeryPoint);
+
+impl From<[u8; X448_KEY_LENGTH]> for PublicKey {
+    fn from(bytes: [u8; X448_KEY_LENGTH]) -> PublicKey {
+        PublicKey(MontgomeryPoint(bytes))
+    }
+}
+
+impl PublicKey {
+    /// View the public key as bytes
+    pub fn as_bytes(&self) -> &[u8; X448_KEY_LENGTH] {
+        self.0.as_bytes()
+    }
+
+    /// Returns the encoding of the public key
+    pub fn to_bytes(&self) -> [u8; X448_KEY_LENGTH] {
+        self.0 .0
+    }
+
+    /// Decodes a public key which was sent as an Elligator 2 representative
+    ///
+    /// See [`EphemeralSecret::random_representable`]. Every 56 bytes decode to a point on the curve.
+    pub fn from_representative(representative: &[u8; X448_KEY_LENGTH]) -> PublicKey {
+        PublicKey(MontgomeryPoint::from_representative(representative))
+    }
+}
+
+/// Computes the shared secret, rejecting the all-zero output of small order points
+fn diffie_hellman(k: &[u8; X448_KEY_LENGTH], their_public: &PublicKey) -> Option<SharedSecret> {
+    let shared = SharedSecret(ladder(k, &their_public.0).0);
+    if bool::from(shared.0.ct_eq(&[0u8; X448_KEY_LENGTH])) {
+        return None;
+    }
+    Some(shared)
+}
+
+/// An X448 secret key which can be used for any number of key exchanges
+///
+/// The bytes are kept as given and clamped whenever they are used.
+#[derive(Clone)]
+pub struct StaticSecret([u8; X448_KEY_LENGTH]);
+
+impl fmt::Debug for StaticSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("StaticSecret(..)")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for StaticSecret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl From<[u8; X448_KEY_LENGTH]> for StaticSecret {
+    fn from(bytes: [u8; X448_KEY_LENGTH]) -> StaticSecret {
+        StaticSecret(bytes)
+    }
+}
+
+impl StaticSecret {
+    /// Generates a new secret key
+    #[cfg(feature = "rand")]
+    pub fn random_from_rng<R: RngCore + CryptoRng>(rng: &mut R) -> StaticSecret {
+        let mut bytes = [0u8; X448_KEY_LENGTH];
+        rng.fill_bytes(&mut bytes);
+        StaticSecret(bytes)
+    }
+
+    /// Computes the shared secret with `their_public`
+    ///
+    /// Returns `None` if the shared secret is all zero, which happens when
+    /// `their_public` is of small order.
+    pub fn diffie_hellman(&self, their_public: &PublicKey) -> Option<SharedSecret> {
+        diffie_hellman(&self.0, their_public)
+    }
+
+    /// View the secret key as bytes
+    pub fn as_bytes(&self) -> &[u8; X448_KEY_LENGTH] {
+        &self.0
+    }
+
+    /// Returns the secret key as given, without clamping
+    pub fn to_bytes(&self) -> [u8; X448_KEY_LENGTH] {
+        self.0
+    }
+}
+
+impl From<&StaticSecret> for PublicKey {
+    fn from(secret: &StaticSecret) -> PublicKey {
+        PublicKey(mul_base(&secret.0))
+    }
+}
+
+/// An X448 secret key which can be used for a single key exchange
+///
+/// `diffie_hellman` consumes the secret, so it cannot be reused.
+#[cfg(feature = "rand")]
+pub struct EphemeralSecret([u8; X448_KEY_LENGTH]);
+
+#[cfg(feature = "rand")]
+impl fmt::Debug for EphemeralSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EphemeralSecret(..)")
+    }
+}
+
+#[cfg(all(feature = "rand", feature = "zeroize"))]
+impl Drop for EphemeralSecret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "rand")]
+impl EphemeralSecret {
+    /// Generates a new secret key
+    pub fn random_from_rng<R: RngCore + CryptoRng>(rng: &mut R) -> EphemeralSecret {
+        let mut bytes = [0u8; X448_KEY_LENGTH];
+        rng.fill_bytes(&mut bytes);
+        EphemeralSecret(bytes)
+    }
+
+    /// Computes the shared secret with `their_public`, consuming the secret
+    ///
+    /// Returns `None` if the shared secret is all zero, which happens when
+    /// `their_public` is of small order.
+    pub fn diffie_hellman(self, their_public: &PublicKey) -> Option<SharedSecret> {
+        diffie_hellman(&self.0, their_public)
+    }
+
+    /// Generates a new secret key together with an Elligator 2 representative of its public key
+    ///
+    /// The representative is indistinguishable from 56 uniformly random bytes, and is decoded
+    /// with [`PublicKey::from_representative`]. Only about half of the public keys have a
+    /// representative, so secret keys are drawn until one does.
+    ///
+    /// The public keys [k]5 all lie in the prime-order subgroup, which a representative would
+    /// give away, so a random point of order dividing 4 is added first. This does not change
+    /// the shared secret, as the clamped secret of the peer is a multiple of 4. The sign of v and
+    /// the top bit, which the map ignores, are chosen at random as well.
+    pub fn random_representable<R: RngCore + CryptoRng>(
+        rng: &mut R,
+    ) -> (EphemeralSecret, [u8; X448_KEY_LENGTH]) {
+        loop {
+            let secret = EphemeralSecret::random_from_rng(rng);
+            let mut tweak = [0u8; 1];
+            rng.fill_bytes(&mut tweak);
+
+            let public = AffineMontgomeryPoint::from_edwards(&mul_base_edwards(&secret.0));
+            let mut point = &public + &low_order_point(tweak[0] & 3);
+            point.conditional_assign(&point.negate(), Choice::from((tweak[0] >> 2) & 1));
+
+            if let Some(mut representative) = point.to_representative() {
+                representative[X448_KEY_LENGTH - 1] |= tweak[0] & 0x80;
+                return (secret, representative);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rand")]
+impl From<&EphemeralSecret> for PublicKey {
+    fn from(secret: &EphemeralSecret) -> PublicKey {
+        PublicKey(mul_base(&secret.0))
+    }
+}
+
+/// The result of an X448 key exchange
+///
+/// This is not uniformly random and should be passed through a key derivation function.
+pub struct SharedSecret([u8; X448_KEY_LENGTH]);
+
+impl fmt::Debug for SharedSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedSecret(..)")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl SharedSecret {
+    /// View the shared secret as bytes
+    pub fn as_bytes(&self) -> &[u8; X448_KEY_LENGTH] {
+        &self.0
+    }
+
+    /// Returns the shared secret as bytes
+    pub fn to_bytes(&self) -> [u8; X448_KEY_LENGTH] {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_rfc7748_vectors() {
+        // RFC 7748 Section 5.2
+        let k = hex!("3d262fddf9ec8e88495266fea19a34d28882acef045104d0d1aae121700a779c984c24f8cdd78fbff44943eba368f54b29259a4f1c600ad3");
+        let u = hex!("06fce640fa3487bfda5f6cf2d5263f8aad88334cbd07437f020f08f9814dc031ddbdc38c19c6da2583fa5429db94ada18aa7a7fb4ef8a086");
+        assert_eq!(
+            x448(k, u),
+            hex!("ce3e4ff95a60dc6697da1db1d85e6afbdf79b50a2412d7546d5f239fe14fbaadeb445fc66a01b0779d98223961111e21766282f73dd96b6f")
+        );
+
+        let k = hex!("203d494428b8399352665ddca42f9de8fef600908e0d461cb021f8c538345dd77c3e4806e25f46d3315c44e0a5b4371282dd2c8d5be3095f");
+        let u = hex!("0fbcc2f993cd56d3305b0b7d9e55d4c1a8fb5dbb52f8e9a1e9b6201b165d015894e56c4d3570bee52fe205e28a78b91cdfbde71ce8d157db");
+        assert_eq!(
+            x448(k, u),
+            hex!("884a02576239ff7a2f2f63b2db6a9ff37047ac13568e1e30fe63c4a7ad1b3ee3a5700df34321d62077e63633c575c1c954514e99da7c179d")
+        );
+    }
+
+    #[test]
+    fn test_rfc7748_iterations() {
+        // RFC 7748 Section 5.2: k, u = X448(k, u), k
+        let mut k = X448_BASEPOINT_BYTES;
+        let mut u = X448_BASEPOINT_BYTES;
+        for i in 1..=1000 {
+            let output = x448(k, u);
+            u = k;
+            k = output;
+
+            if i == 1 {
+                assert_eq!(k, hex!("3f482c8a9f19b01e6c46ee9711d9dc14fd4bf67af30765c2ae2b846a4d23a8cd0db897086239492caf350b51f833868b9bc2b3bca9cf4113"));
+            }
+        }
+        assert_eq!(k, hex!("aa3b4749d55b9daf1e5b00288826c467274ce3ebbdd5c17b975e09d4af6c67cf10d087202db88286e2b79fceea3ec353ef54faa26e219f38"));
+    }
+
+    #[test]
+    fn test_rfc7748_diffie_hellman() {
+        // RFC 7748 Section 6.2
+        let alice = StaticSecret::from(hex!("9a8f4925d1519f5775cf46b04b5800d4ee9ee8bae8bc5565d498c28dd9c9baf574a9419744897391006382a6f127ab1d9ac2d8c0a598726b"));
+        let bob = StaticSecret::from(hex!("1c306a7ac2a0e2e0990b294470cba339e6453772b075811d8fad0d1d6927c120bb5ee8972b0d3e21374c9c921b09d1b0366f10b65173992d"));
+
+        let alice_public = PublicKey::from(&alice);
+        let bob_public = PublicKey::from(&bob);
+        assert_eq!(alice_public.to_bytes(), hex!("9b08f7cc31b7e3e67d22d5aea121074a273bd2b83de09c63faa73d2c22c5d9bbc836647241d953d40c5b12da88120d53177f80e532c41fa0"));
+        assert_eq!(bob_public.to_bytes(), hex!("3eb7a829b0cd20f5bcfc0b599b6feccf6da4627107bdb0d4f345b43027d8b972fc3e34fb4232a13ca706dcb57aec3dae07bdc1c67bf33609"));
+
+        let shared = hex!("07fff4181ac6cc95ec1c16a94a0f74d12da232ce40a77552281d282bb60c0b56fd2464c335543936521c24403085d59a449a5037514a879d");
+        assert_eq!(
+            alice.diffie_hellman(&bob_public).unwrap().to_bytes(),
+            shared
+        );
+        assert_eq!(
+            bob.diffie_hellman(&alice_public).unwrap().to_bytes(),
+            shared
+        );
+    }
+
+    #[test]
+    fn test_mul_base_matches_ladder() {
+        // 4ℓ is a valid clamped scalar, whose public key is the point at infinity u = 0
+        let four_ell = hex!("cc1361ad4a0ae38d543d1637ca09b38540da58bb266d3b11a78f28f3fdffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+        let k: [u8; 56] = std::array::from_fn(|i| (i as u8).wrapping_mul(97));
+        for k in [[0u8; 56], [0xff; 56], X448_BASEPOINT_BYTES, k, four_ell] {
+            assert_eq!(mul_base(&k), ladder(&k, &MontgomeryPoint::generator()),);
+        }
+        assert_eq!(mul_base(&four_ell).0, [0u8; 56]);
+    }
+
+    #[test]
+    fn test_low_order_rejected() {
+        let secret = StaticSecret::from([0x42; 56]);
+
+        // u = 0, u = 1 and u = p - 1 are the points of small order on the curve and its twist
+        let mut one = [0u8; 56];
+        one[0] = 1;
+        let mut p_minus_one = [0xff; 56];
+        p_minus_one[0] = 0xfe;
+        p_minus_one[28] = 0xfe;
+        for u in [[0u8; 56], one, p_minus_one] {
+            assert!(secret.diffie_hellman(&PublicKey::from(u)).is_none());
+        }
+
+        // u = p + 1 is a non-canonical encoding of u = 1
+        let mut p_plus_one = [0xff; 56];
+        p_plus_one[..28].fill(0);
+        assert_eq!(x448([0x42; 56], p_plus_one), [0u8; 56]);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_ephemeral() {
+        use rand_chacha::rand_core::SeedableRng;
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+
+        let alice = EphemeralSecret::random_from_rng(&mut rng);
+        let bob = StaticSecret::random_from_rng(&mut rng);
+        let alice_public = PublicKey::from(&alice);
+        let bob_public = PublicKey::from(&bob);
+
+        let alice_shared = alice.diffie_hellman(&bob_public).unwrap();
+        let bob_shared = bob.diffie_hellman(&alice_public).unwrap();
+        assert_eq!(alice_shared.as_bytes(), bob_shared.as_bytes());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_representable() {
+        use rand_chacha::rand_core::SeedableRng;
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let bob = StaticSecret::random_from_rng(&mut rng);
+        let bob_public = PublicKey::from(&bob);
+
+        let mut top_bits = 0;
+        let mut prime_order = 0;
+        for _ in 0..16 {
+            let (alice, representative) = EphemeralSecret::random_representable(&mut rng);
+            top_bits += representative[55] >> 7;
+
+            // The decoded key differs from [k]5 by a point of small order, if at all
+            let alice_public = PublicKey::from_representative(&representative);
+            if alice_public == PublicKey::from(&alice) {
+                prime_order += 1;
+            }
+
+            let bob_shared = bob.diffie_hellman(&alice_public).unwrap();
+            let alice_shared = alice.diffie_hellman(&bob_public).unwrap();
+            assert_eq!(alice_shared.as_bytes(), bob_shared.as_bytes());
+        }
+        assert!(top_bits > 0 && top_bits < 16);
+        assert!(prime_order < 16);
+    }
+}
@@ -0,0 +1,520 @@
+// This is the module for XEdDSA-Goldilocks, a variant of Signal's XEdDSA signatures with X448 keys
+// https://signal.org/docs/specifications/xeddsa/
+//
+// N.B. This variant is not interoperable with the XEdDSA specification. The specification
+// converts a Montgomery u-coordinate to Edwards form with the birational map u_to_y,
+// y = (u - 1) / (u + 1), while XEdDSA-Goldilocks uses the 4-isogeny of RFC 7748 to
+// Ed448-Goldilocks, so that all of the arithmetic is the one of Ed448. The Edwards public keys,
+// and with them all signatures, differ from the ones of an implementation of the specification.
+//
+// XEdDSA signs with an X448 secret key, so that a single key pair serves for both key agreement
+// and signatures. The X448 public key [k]5 is the image of [k]B on Ed448, and
+// `MontgomeryPoint::to_edwards` inverts the isogeny on the prime-order subgroup. P and -P have the
+// same u-coordinate, so the Edwards public key is the one with a non-negative x, and the secret
+// scalar is negated along with it.
+//
+// For curve448, b = 456 and the hash function has a 2b-bit output, which is SHAKE256 with 114
+// bytes of output, reduced modulo ℓ as in Ed448. Signatures have the 114-byte encoding R || s
+// of Ed448.
+//
+// VXEdDSA extends XEdDSA to a verifiable random function: the signature V || h || s proves that
+// V = [a]Bv for the point Bv = hash_to_point(A || M), and the VRF output is hash_5([c]V), which
+// only the holder of the secret key can compute but anyone can check. hash_to_point maps the
+// first 448 bits of hash_2(X) to Curve448 with Elligator 2 and takes bit 455 as the sign. The
+// cofactor is cleared with [c] = [4] on Curve448, and the result is moved to Ed448 with the
+// inverse of the 4-isogeny, which is defined on the whole prime-order subgroup. The sign is only
+// applied to this final point, while the specification sets it on the Edwards form of the
+// Elligator point before clearing the cofactor.
+//
+// N.B. Together with the 4-isogeny, this makes hash_to_point, and so VXEdDSA signatures and VRF
+// outputs, specific to this crate: they are not interoperable with the specification either.
+
+use crate::curve::edwards::{CompressedEdwardsY, ExtendedPoint};
+use crate::curve::montgomery::elligator;
+use crate::curve::MontgomeryPoint;
+use crate::ed448::{Signature, SignatureError};
+use crate::field::{FieldElement, Scalar};
+use crate::x448::{clamp, mul_base_edwards, PublicKey, StaticSecret};
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use subtle::{Choice, ConditionallySelectable};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The length of the random data Z which is hashed into the nonce, in bytes
+pub const RANDOM_LENGTH: usize = 64;
+
+/// The length of the output of the hash function, 2b bits
+const HASH_LENGTH: usize = 114;
+
+/// The length of a VXEdDSA signature V || h || s
+pub const VRF_SIGNATURE_LENGTH: usize = 3 * 57;
+
+/// The length of the VRF output, b bits
+pub const VRF_OUTPUT_LENGTH: usize = 57;
+
+/// Computes hash_i(X) = hash(2^b - 1 - i || X) for X = parts[0] || ... || parts[n]
+///
+/// `None` computes hash(X), without the prefix.
+pub(crate) fn hash(i: Option<u8>, parts: &[&[u8]]) -> [u8; HASH_LENGTH] {
+    let mut hasher = Shake256::default();
+    if let Some(i) = i {
+        // 2^b - 1 - i as a b-bit little-endian integer
+        let mut prefix = [0xff; 57];
+        prefix[0] -= i;
+        hasher.update(&prefix);
+    }
+    for part in parts {
+        hasher.update(part);
+    }
+
+    let mut output = [0u8; HASH_LENGTH];
+    hasher.finalize_xof().read(&mut output);
+    output
+}
+
+/// Computes hash_i(X) modulo ℓ
+pub(crate) fn hash_to_scalar(i: Option<u8>, parts: &[&[u8]]) -> Scalar {
+    Scalar::from_bytes_mod_order_wide(&hash(i, parts))
+}
+
+/// Returns the Edwards public key A and the secret scalar a of an X448 secret key, see calculate_key_pair
+///
+/// A has a non-negative x-coordinate, and is the Edwards form of the X448 public key under the
+/// 4-isogeny, not under the birational map of the XEdDSA specification.
+pub fn calculate_key_pair(secret: &StaticSecret) -> (CompressedEdwardsY, Scalar) {
+    let point = mul_base_edwards(secret.as_bytes());
+
+    #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+    let mut wide = [0u8; 114];
+    wide[..56].copy_from_slice(&clamp(*secret.as_bytes()));
+    let scalar = Scalar::from_bytes_mod_order_wide(&wide);
+
+    #[cfg(feature = "zeroize")]
+    wide.zeroize();
+
+    let negative = point.to_affine().x.is_negative();
+    let A = ExtendedPoint::conditional_select(&point, &point.negate(), negative);
+    let a = Scalar::conditional_select(&scalar, &(Scalar::zero() - scalar), negative);
+    (A.compress(), a)
+}
+
+/// Converts an X448 public key to its Edwards form, with a non-negative x-coordinate, see convert_mont
+///
+/// Rejects non-canonical u-coordinates, u-coordinates on the twist or outside of the image of the
+/// prime-order subgroup, and the identity.
+pub(crate) fn convert_mont(public: &PublicKey) -> Result<ExtendedPoint, SignatureError> {
+    let u = public.as_bytes();
+    if FieldElement::from_bytes(u).to_bytes() != *u {
+        return Err(SignatureError::InvalidPublicKey);
+    }
+    let A = MontgomeryPoint(*u)
+        .to_edwards(0)
+        .ok_or(SignatureError::InvalidPublicKey)?;
+    if A == ExtendedPoint::identity() {
+        return Err(SignatureError::InvalidPublicKey);
+    }
+    Ok(A)
+}
+
+/// Signs `message` with an X448 secret key, hashing the random data `random` into the nonce
+///
+/// The signature is an XEdDSA-Goldilocks signature, which only verifies with this module.
+/// `random` must be 64 fresh random bytes for each signature.
+pub fn sign_with_random(
+    secret: &StaticSecret,
+    message: &[u8],
+    random: &[u8; RANDOM_LENGTH],
+) -> Signature {
+    let (A, a) = calculate_key_pair(secret);
+
+    // r = hash_1(a || M || Z) mod ℓ
+    #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+    let mut a_bytes = a.to_bytes_rfc_8032();
+    let r = hash_to_scalar(Some(1), &[&a_bytes, message, random]);
+    let R = ExtendedPoint::generator().scalar_mul(&r).compress();
+
+    #[cfg(feature = "zeroize")]
+    a_bytes.zeroize();
+
+    // h = hash(R || A || M) mod ℓ
+    let h = hash_to_scalar(None, &[R.as_bytes(), A.as_bytes(), message]);
+    let s = r + h * a;
+
+    Signature {
+        R,
+        s: s.to_bytes_rfc_8032(),
+    }
+}
+
+/// Signs `message` with an X448 secret key
+#[cfg(feature = "rand")]
+pub fn sign<R: RngCore + CryptoRng>(
+    secret: &StaticSecret,
+    message: &[u8],
+    rng: &mut R,
+) -> Signature {
+    let mut random = [0u8; RANDOM_LENGTH];
+    rng.fill_bytes(&mut random);
+    let signature = sign_with_random(secret, message, &random);
+
+    #[cfg(feature = "zeroize")]
+    random.zeroize();
+
+    signature
+}
+
+/// Verifies a signature over `message` with an X448 public key
+///
+/// s must be reduced modulo ℓ, which is what the signer produces.
+pub fn verify(
+    public: &PublicKey,
+    message: &[u8],
+    signature: &Signature,
+) -> Result<(), SignatureError> {
+    let A = convert_mont(public)?;
+    let s = Scalar::from_canonical_bytes(signature.s).ok_or(SignatureError::InvalidSignature)?;
+
+    // R == sB - hA
+    let h = hash_to_scalar(
+        None,
+        &[signature.R.as_bytes(), A.compress().as_bytes(), message],
+    );
+    let R = ExtendedPoint::generator()
+        .scalar_mul(&s)
+        .add(&A.scalar_mul(&h).negate());
+    if R.compress().0 != signature.R.0 {
+        return Err(SignatureError::VerificationFailed);
+    }
+    Ok(())
+}
+
+/// Maps `input` to a point of the prime-order subgroup, see hash_to_point
+///
+/// The discrete logarithm of the point is unknown. It is the identity with negligible probability.
+/// The sign is applied after clearing the cofactor, so the point differs from the one of the
+/// XEdDSA specification.
+pub fn hash_to_point(input: &[u8]) -> ExtendedPoint {
+    let h = hash(Some(2), &[input]);
+
+    // r = h mod 2^|p|, s is bit b - 1
+    let mut r = [0u8; 56];
+    r.copy_from_slice(&h[..56]);
+    let s = Choice::from(h[56] >> 7);
+
+    // [c]P on Curve448 lies in the prime-order subgroup, so it has a preimage on Ed448
+    let point = elligator::map(&FieldElement::from_bytes(&r))
+        .double()
+        .double()
+        .to_edwards()
+        .expect("points of the prime-order subgroup map back to Ed448");
+    ExtendedPoint::conditional_select(&point, &point.negate(), s)
+}
+
+/// hash_5([c]V) mod 2^b
+fn vrf_output(V: &ExtendedPoint) -> [u8; VRF_OUTPUT_LENGTH] {
+    let cV = V.double().double().compress();
+    let mut output = [0u8; VRF_OUTPUT_LENGTH];
+    output.copy_from_slice(&hash(Some(5), &[cV.as_bytes()])[..VRF_OUTPUT_LENGTH]);
+    output
+}
+
+/// A VXEdDSA signature V || h || s, which proves that V is the VRF point of the message
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VrfSignature {
+    pub(crate) V: CompressedEdwardsY,
+    pub(crate) h: [u8; 57],
+    pub(crate) s: [u8; 57],
+}
+
+impl VrfSignature {
+    /// Parses a signature from the encoding V || h || s
+    pub fn from_bytes(bytes: &[u8; VRF_SIGNATURE_LENGTH]) -> VrfSignature {
+        VrfSignature {
+            V: CompressedEdwardsY(std::array::from_fn(|i| bytes[i])),
+            h: std::array::from_fn(|i| bytes[57 + i]),
+            s: std::array::from_fn(|i| bytes[114 + i]),
+        }
+    }
+
+    /// Encodes the signature as V || h || s
+    pub fn to_bytes(&self) -> [u8; VRF_SIGNATURE_LENGTH] {
+        let mut bytes = [0u8; VRF_SIGNATURE_LENGTH];
+        bytes[..57].copy_from_slice(&self.V.0);
+        bytes[57..114].copy_from_slice(&self.h);
+        bytes[114..].copy_from_slice(&self.s);
+        bytes
+    }
+}
+
+/// Signs `message` with an X448 secret key as a VRF, hashing the random data `random` into the nonce
+///
+/// Returns the signature and the VRF output, which only depends on the key and the message.
+/// `random` must be 64 fresh random bytes for each signature. Like the signature, the VRF
+/// output is specific to this crate, see [`hash_to_point`].
+pub fn vxeddsa_sign_with_random(
+    secret: &StaticSecret,
+    message: &[u8],
+    random: &[u8; RANDOM_LENGTH],
+) -> (VrfSignature, [u8; VRF_OUTPUT_LENGTH]) {
+    let (A, a) = calculate_key_pair(secret);
+    let Bv = hash_to_point(&[A.as_bytes(), message].concat());
+    let V = Bv.scalar_mul(&a);
+    let V_bytes = V.compress();
+
+    // r = hash_3(a || V || Z) mod ℓ
+    #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+    let mut a_bytes = a.to_bytes_rfc_8032();
+    let r = hash_to_scalar(Some(3), &[&a_bytes, V_bytes.as_bytes(), random]);
+    let R = ExtendedPoint::generator().scalar_mul(&r).compress();
+    let Rv = Bv.scalar_mul(&r).compress();
+
+    #[cfg(feature = "zeroize")]
+    a_bytes.zeroize();
+
+    // h = hash_4(A || V || R || Rv || M) mod ℓ
+    let h = hash_to_scalar(
+        Some(4),
+        &[
+            A.as_bytes(),
+            V_bytes.as_bytes(),
+            R.as_bytes(),
+            Rv.as_bytes(),
+            message,
+        ],
+    );
+    let s = r + h * a;
+
+    let signature = VrfSignature {
+        V: V_bytes,
+        h: h.to_bytes_rfc_8032(),
+        s: s.to_bytes_rfc_8032(),
+    };
+    (signature, vrf_output(&V))
+}
+
+/// Signs `message` with an X448 secret key as a VRF, returning the signature and the VRF output
+#[cfg(feature = "rand")]
+pub fn vxeddsa_sign<R: RngCore + CryptoRng>(
+    secret: &StaticSecret,
+    message: &[u8],
+    rng: &mut R,
+) -> (VrfSignature, [u8; VRF_OUTPUT_LENGTH]) {
+    let mut random = [0u8; RANDOM_LENGTH];
+    rng.fill_bytes(&mut random);
+    let result = vxeddsa_sign_with_random(secret, message, &random);
+
+    #[cfg(feature = "zeroize")]
+    random.zeroize();
+
+    result
+}
+
+/// Verifies a VXEdDSA signature over `message` with an X448 public key, and returns the VRF output
+///
+/// V must be canonically encoded, and h and s must be reduced modulo ℓ, which is what the signer produces.
+pub fn vxeddsa_verify(
+    public: &PublicKey,
+    message: &[u8],
+    signature: &VrfSignature,
+) -> Result<[u8; VRF_OUTPUT_LENGTH], SignatureError> {
+    let A = convert_mont(public)?;
+    let V = signature
+        .V
+        .decompress_strict()
+        .ok_or(SignatureError::InvalidSignature)?;
+    let h = Scalar::from_canonical_bytes(signature.h).ok_or(SignatureError::InvalidSignature)?;
+    let s = Scalar::from_canonical_bytes(signature.s).ok_or(SignatureError::InvalidSignature)?;
+
+    let A_bytes = A.compress();
+    let Bv = hash_to_point(&[A_bytes.as_bytes(), message].concat());
+    if V.double().double() == ExtendedPoint::identity() || Bv == ExtendedPoint::identity() {
+        return Err(SignatureError::InvalidSignature);
+    }
+
+    // R = sB - hA, Rv = sBv - hV
+    let R = ExtendedPoint::generator()
+        .scalar_mul(&s)
+        .add(&A.scalar_mul(&h).negate());
+    let Rv = Bv.scalar_mul(&s).add(&V.scalar_mul(&h).negate());
+    let h_check = hash_to_scalar(
+        Some(4),
+        &[
+            A_bytes.as_bytes(),
+            signature.V.as_bytes(),
+            R.compress().as_bytes(),
+            Rv.compress().as_bytes(),
+            message,
+        ],
+    );
+    if h_check != h {
+        return Err(SignatureError::VerificationFailed);
+    }
+    Ok(vrf_output(&V))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::x448::X448_BASEPOINT_BYTES;
+
+    fn secrets() -> Vec<StaticSecret> {
+        (0..16u8)
+            .map(|i| StaticSecret::from(std::array::from_fn(|j| (j as u8) ^ i.wrapping_mul(37))))
+            .collect()
+    }
+
+    #[test]
+    fn test_calculate_key_pair() {
+        let mut negated = 0;
+        for secret in secrets() {
+            let (A, a) = calculate_key_pair(&secret);
+
+            // A = aB has a non-negative x, and is the Edwards form of the X448 public key
+            assert_eq!(ExtendedPoint::generator().scalar_mul(&a).compress().0, A.0);
+            assert_eq!(A.0[56] & 0x80, 0);
+            let public = PublicKey::from(&secret);
+            assert_eq!(convert_mont(&public).unwrap().compress().0, A.0);
+
+            let point = mul_base_edwards(secret.as_bytes());
+            if point.compress().0 != A.0 {
+                negated += 1;
+            }
+        }
+        // Both signs of x occur
+        assert!(negated > 0 && negated < 16);
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        for (i, secret) in secrets().iter().enumerate() {
+            let public = PublicKey::from(secret);
+            let random = [i as u8; RANDOM_LENGTH];
+            let signature = sign_with_random(secret, b"message", &random);
+            assert_eq!(verify(&public, b"message", &signature), Ok(()));
+            assert_eq!(
+                verify(&public, b"other message", &signature),
+                Err(SignatureError::VerificationFailed)
+            );
+
+            // The signature depends on the random data
+            let other = sign_with_random(secret, b"message", &[0xaa; RANDOM_LENGTH]);
+            assert_ne!(other, signature);
+            assert_eq!(verify(&public, b"message", &other), Ok(()));
+
+            // Tampered signatures
+            let mut bytes = signature.to_bytes();
+            bytes[0] ^= 1;
+            assert!(verify(&public, b"message", &Signature::from_bytes(&bytes)).is_err());
+            let mut bytes = signature.to_bytes();
+            bytes[113] = 0xff;
+            assert_eq!(
+                verify(&public, b"message", &Signature::from_bytes(&bytes)),
+                Err(SignatureError::InvalidSignature)
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_public_keys() {
+        let secret = &secrets()[0];
+        let signature = sign_with_random(secret, b"message", &[0; RANDOM_LENGTH]);
+
+        // u = 0 and u = -1 of small order, u = 1 which is not the image of a point of order ℓ,
+        // and the non-canonical encoding p + 5 of the generator
+        let mut minus_one = [0xff; 56];
+        minus_one[0] = 0xfe;
+        minus_one[28] = 0xfe;
+        let mut one = [0u8; 56];
+        one[0] = 1;
+        let mut p_plus_five = [0xff; 56];
+        p_plus_five[..28].copy_from_slice(&[0; 28]);
+        p_plus_five[0] = 4;
+        assert!(convert_mont(&PublicKey::from(X448_BASEPOINT_BYTES)).is_ok());
+        for u in [[0u8; 56], minus_one, one, p_plus_five] {
+            assert_eq!(
+                verify(&PublicKey::from(u), b"message", &signature),
+                Err(SignatureError::InvalidPublicKey)
+            );
+        }
+    }
+
+    #[test]
+    fn test_hash_to_point() {
+        let mut points = Vec::new();
+        for i in 0..16u8 {
+            let point = hash_to_point(&[i]);
+            assert!(point.is_torsion_free());
+            assert_ne!(point, ExtendedPoint::identity());
+            assert_eq!(point, hash_to_point(&[i]));
+            points.push(point.compress().0);
+        }
+        points.sort();
+        points.dedup();
+        assert_eq!(points.len(), 16);
+    }
+
+    #[test]
+    fn test_vxeddsa() {
+        for (i, secret) in secrets().iter().enumerate() {
+            let public = PublicKey::from(secret);
+            let (signature, output) =
+                vxeddsa_sign_with_random(secret, b"message", &[i as u8; RANDOM_LENGTH]);
+            assert_eq!(vxeddsa_verify(&public, b"message", &signature), Ok(output));
+            assert_eq!(
+                vxeddsa_verify(&public, b"other message", &signature),
+                Err(SignatureError::VerificationFailed)
+            );
+
+            // The VRF output only depends on the key and the message
+            let (other, other_output) =
+                vxeddsa_sign_with_random(secret, b"message", &[0xaa; RANDOM_LENGTH]);
+            assert_ne!(other, signature);
+            assert_eq!(other_output, output);
+            let (_, message_output) =
+                vxeddsa_sign_with_random(secret, b"other message", &[0xaa; RANDOM_LENGTH]);
+            assert_ne!(message_output, output);
+
+            // Encoding round trip, and tampered signatures
+            let bytes = signature.to_bytes();
+            assert_eq!(VrfSignature::from_bytes(&bytes), signature);
+            for (index, value) in [(60, bytes[60] ^ 1), (120, bytes[120] ^ 1), (170, 0xff)] {
+                let mut tampered = bytes;
+                tampered[index] = value;
+                assert!(
+                    vxeddsa_verify(&public, b"message", &VrfSignature::from_bytes(&tampered))
+                        .is_err()
+                );
+            }
+
+            // Another key does not verify, and has another VRF output
+            let other_secret = &secrets()[(i + 1) % 16];
+            assert_eq!(
+                vxeddsa_verify(&PublicKey::from(other_secret), b"message", &signature),
+                Err(SignatureError::VerificationFailed)
+            );
+            let (_, key_output) =
+                vxeddsa_sign_with_random(other_secret, b"message", &[0; RANDOM_LENGTH]);
+            assert_ne!(key_output, output);
+        }
+
+        // V of small order, and a non-canonical V
+        let secret = &secrets()[0];
+        let public = PublicKey::from(secret);
+        let (signature, _) = vxeddsa_sign_with_random(secret, b"message", &[0; RANDOM_LENGTH]);
+        let mut small_order = signature;
+        small_order.V = ExtendedPoint::identity().compress();
+        assert_eq!(
+            vxeddsa_verify(&public, b"message", &small_order),
+            Err(SignatureError::InvalidSignature)
+        );
+        let mut non_canonical = signature;
+        non_canonical.V.0[56] |= 1;
+        assert_eq!(
+            vxeddsa_verify(&public, b"message", &non_canonical),
+            Err(SignatureError::InvalidSignature)
+        );
+    }
+}